@@ -19,6 +19,27 @@ pub trait Backend where
     Self: HasSqlType<types::Timestamp>,
 {
     type QueryBuilder: QueryBuilder<Self>;
+
+    /// Whether schema changes, such as `CREATE TABLE`, are rolled back along
+    /// with the transaction they were made in. Backends which commit schema
+    /// changes immediately should return `false`, so that a migration which
+    /// fails part way through is reported as partially applied.
+    fn supports_transactional_ddl() -> bool {
+        false
+    }
+
+    /// The `LIMIT` to render for a query which has an `OFFSET` but no limit.
+    /// Backends which accept `OFFSET` on its own should return `None`.
+    fn limit_for_offset_without_limit() -> Option<&'static str> {
+        None
+    }
+
+    /// Whether the values of `LIMIT` and `OFFSET` can be sent as bind
+    /// parameters. Backends which only accept literals there should return
+    /// `false`, and the values will be written into the SQL instead.
+    fn supports_parameterized_limit() -> bool {
+        true
+    }
 }
 
 pub trait TypeMetadata {
@@ -29,6 +50,10 @@ pub struct Debug;
 
 impl Backend for Debug {
     type QueryBuilder = DebugQueryBuilder;
+
+    fn limit_for_offset_without_limit() -> Option<&'static str> {
+        Some("18446744073709551615")
+    }
 }
 
 impl TypeMetadata for Debug {
@@ -45,6 +70,10 @@ pub struct PgTypeMetadata {
 
 impl Backend for Pg {
     type QueryBuilder = PgQueryBuilder;
+
+    fn supports_transactional_ddl() -> bool {
+        true
+    }
 }
 
 impl TypeMetadata for Pg {
@@ -0,0 +1,190 @@
+//! A connection wrapper which keeps track of the queries run through it. This
+//! is intended to be used in tests, to guard against N+1 query regressions.
+use std::cell::RefCell;
+
+use backend::Pg;
+use query_builder::{AsQuery, QueryFragment};
+use query_source::Queryable;
+use result::*;
+use super::{SimpleConnection, Connection, PgConnection};
+use types::HasSqlType;
+
+/// Wraps a [`PgConnection`](../pg/struct.PgConnection.html), recording the SQL
+/// of every query run through it. Transaction statements are not recorded.
+///
+/// # Example
+///
+/// ```ignore
+/// let connection = CountingConnection::new(PgConnection::establish(&url).unwrap());
+/// let (users, count) = connection.count_queries(|| {
+///     users_with_posts(&connection)
+/// });
+/// assert_eq!(2, count.total());
+/// assert_eq!(1, count.for_table("posts"));
+/// ```
+pub struct CountingConnection {
+    inner: PgConnection,
+    executed_queries: RefCell<Vec<String>>,
+}
+
+impl CountingConnection {
+    pub fn new(inner: PgConnection) -> Self {
+        CountingConnection {
+            inner: inner,
+            executed_queries: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Runs the given function, returning its result along with the queries
+    /// which were run on this connection while it was executing.
+    pub fn count_queries<F, T>(&self, f: F) -> (T, QueryCount) where
+        F: FnOnce() -> T,
+    {
+        let start = self.executed_queries.borrow().len();
+        let result = f();
+        let queries = self.executed_queries.borrow()[start..].to_vec();
+        (result, QueryCount { queries: queries })
+    }
+
+    /// Returns the SQL of every query run on this connection, in order.
+    pub fn executed_queries(&self) -> Vec<String> {
+        self.executed_queries.borrow().clone()
+    }
+
+    /// Returns the wrapped connection.
+    pub fn into_inner(self) -> PgConnection {
+        self.inner
+    }
+
+    fn record(&self, sql: String) {
+        self.executed_queries.borrow_mut().push(sql);
+    }
+}
+
+/// The queries executed inside of
+/// [`CountingConnection::count_queries`](struct.CountingConnection.html#method.count_queries).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryCount {
+    queries: Vec<String>,
+}
+
+impl QueryCount {
+    /// The total number of queries executed.
+    pub fn total(&self) -> usize {
+        self.queries.len()
+    }
+
+    /// The number of queries which referenced the given table anywhere in
+    /// their SQL, whether quoted or not.
+    pub fn for_table(&self, table_name: &str) -> usize {
+        self.queries.iter()
+            .filter(|sql| references_identifier(sql, table_name))
+            .count()
+    }
+
+    /// The SQL of every query executed, in order.
+    pub fn queries(&self) -> &[String] {
+        &self.queries
+    }
+}
+
+fn references_identifier(sql: &str, identifier: &str) -> bool {
+    fn is_identifier_char(c: Option<char>) -> bool {
+        c.map(|c| c.is_alphanumeric() || c == '_').unwrap_or(false)
+    }
+
+    sql.match_indices(identifier).any(|(idx, _)| {
+        let before = sql[..idx].chars().next_back();
+        let after = sql[idx + identifier.len()..].chars().next();
+        !is_identifier_char(before) && !is_identifier_char(after)
+    })
+}
+
+impl SimpleConnection for CountingConnection {
+    fn batch_execute(&self, query: &str) -> QueryResult<()> {
+        self.record(query.to_string());
+        self.inner.batch_execute(query)
+    }
+}
+
+impl Connection for CountingConnection {
+    type Backend = Pg;
+
+    fn establish(database_url: &str) -> ConnectionResult<CountingConnection> {
+        PgConnection::establish(database_url).map(CountingConnection::new)
+    }
+
+    fn execute(&self, query: &str) -> QueryResult<usize> {
+        self.record(query.to_string());
+        self.inner.execute(query)
+    }
+
+    fn query_all<'a, T, U: 'a>(&self, source: T) -> QueryResult<Box<Iterator<Item=U> + 'a>> where
+        T: AsQuery,
+        T::Query: QueryFragment<Pg>,
+        T::SqlType: 'static,
+        Pg: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Pg>,
+    {
+        self.inner.query_all_inspecting_sql(source, |sql| self.record(sql.to_string()))
+    }
+
+    fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize> where
+        T: QueryFragment<Pg>,
+    {
+        self.inner.execute_returning_count_inspecting_sql(source, |sql| {
+            self.record(sql.to_string())
+        })
+    }
+
+    fn silence_notices<F: FnOnce() -> T, T>(&self, f: F) -> T {
+        self.inner.silence_notices(f)
+    }
+
+    fn begin_transaction(&self) -> QueryResult<()> {
+        self.inner.begin_transaction()
+    }
+
+    fn rollback_transaction(&self) -> QueryResult<()> {
+        self.inner.rollback_transaction()
+    }
+
+    fn commit_transaction(&self) -> QueryResult<()> {
+        self.inner.commit_transaction()
+    }
+
+    fn get_transaction_depth(&self) -> i32 {
+        self.inner.get_transaction_depth()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueryCount, references_identifier};
+
+    #[test]
+    fn quoted_and_unquoted_identifiers_are_matched() {
+        assert!(references_identifier("SELECT \"users\".\"id\" FROM \"users\"", "users"));
+        assert!(references_identifier("DELETE FROM users", "users"));
+    }
+
+    #[test]
+    fn identifiers_must_match_exactly() {
+        assert!(!references_identifier("SELECT \"posts\".\"user_id\" FROM \"posts\"", "user"));
+        assert!(!references_identifier("SELECT * FROM users2", "users"));
+    }
+
+    #[test]
+    fn queries_are_counted_by_table() {
+        let count = QueryCount { queries: vec![
+            "SELECT \"users\".\"id\" FROM \"users\"".to_string(),
+            "SELECT \"posts\".\"title\" FROM \"posts\" WHERE \"posts\".\"user_id\" = $1".to_string(),
+            "SELECT \"posts\".\"title\" FROM \"posts\" WHERE \"posts\".\"user_id\" = $1".to_string(),
+        ]};
+
+        assert_eq!(3, count.total());
+        assert_eq!(1, count.for_table("users"));
+        assert_eq!(2, count.for_table("posts"));
+        assert_eq!(0, count.for_table("comments"));
+    }
+}
@@ -0,0 +1,351 @@
+//! A connection which never talks to a database. Every query it is given is
+//! built with the `Debug` backend and recorded, and results are served from a
+//! queue of canned responses. This makes it possible to unit test code which
+//! builds queries without needing a running database.
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+
+use backend::Debug;
+use query_builder::{AsQuery, QueryFragment, Binds};
+use query_builder::debug::DebugQueryBuilder;
+use query_source::Queryable;
+use result::*;
+use row::Row;
+use super::{SimpleConnection, Connection};
+use types::{HasSqlType, FromSqlRow};
+
+/// A query which was run against a [`MockConnection`](struct.MockConnection.html).
+/// Bind parameters are recorded in the order they appear in the SQL, in the
+/// same binary format that would have been sent to the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutedQuery {
+    pub sql: String,
+    pub binds: Binds,
+}
+
+/// A response which will be returned for the next query run against a
+/// [`MockConnection`](struct.MockConnection.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CannedResult {
+    /// Rows which will be deserialized by `load` and friends. Each value is
+    /// expected to be in the binary format that the database would send.
+    Rows(Vec<Binds>),
+    /// The number of rows affected by a command such as `update` or `delete`.
+    RowsAffected(usize),
+}
+
+/// A test double for [`Connection`](../trait.Connection.html). Queries are
+/// built using the `Debug` backend, so the SQL recorded will match the output
+/// of `debug_sql!`.
+///
+/// Results are returned in the order they were pushed. When no results are
+/// queued, queries return no rows and commands report 0 affected rows.
+/// Transaction statements are recorded, but never consume a canned result.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # use diesel::*;
+/// # use diesel::connection::mock::MockConnection;
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// # fn main() {
+/// use self::users::dsl::*;
+/// let connection = MockConnection::new();
+/// connection.push_rows(vec![vec![Some(b"Sean".to_vec())]]);
+///
+/// let names = users.select(name).filter(id.eq(1))
+///     .load::<String>(&connection).unwrap().collect::<Vec<_>>();
+/// assert_eq!(vec!["Sean".to_string()], names);
+///
+/// let query = connection.last_query().unwrap();
+/// assert_eq!("SELECT `users`.`name` FROM `users` WHERE `users`.`id` = ?", query.sql);
+/// assert_eq!(vec![Some(vec![0, 0, 0, 1])], query.binds);
+/// # }
+/// ```
+pub struct MockConnection {
+    executed_queries: RefCell<Vec<ExecutedQuery>>,
+    canned_results: RefCell<VecDeque<QueryResult<CannedResult>>>,
+    transaction_depth: Cell<i32>,
+}
+
+impl MockConnection {
+    pub fn new() -> Self {
+        MockConnection {
+            executed_queries: RefCell::new(Vec::new()),
+            canned_results: RefCell::new(VecDeque::new()),
+            transaction_depth: Cell::new(0),
+        }
+    }
+
+    /// Queues rows to be returned by the next query.
+    pub fn push_rows(&self, rows: Vec<Binds>) {
+        self.push_result(Ok(CannedResult::Rows(rows)));
+    }
+
+    /// Queues an affected row count to be returned by the next command.
+    pub fn push_rows_affected(&self, count: usize) {
+        self.push_result(Ok(CannedResult::RowsAffected(count)));
+    }
+
+    /// Queues an error to be returned by the next query or command.
+    pub fn push_error(&self, error: Error) {
+        self.push_result(Err(error));
+    }
+
+    pub fn push_result(&self, result: QueryResult<CannedResult>) {
+        self.canned_results.borrow_mut().push_back(result);
+    }
+
+    /// Returns every query run against this connection, in order.
+    pub fn executed_queries(&self) -> Vec<ExecutedQuery> {
+        self.executed_queries.borrow().clone()
+    }
+
+    /// Returns the most recent query run against this connection.
+    pub fn last_query(&self) -> Option<ExecutedQuery> {
+        self.executed_queries.borrow().last().cloned()
+    }
+
+    /// Forgets all recorded queries. Canned results are left untouched.
+    pub fn clear_executed_queries(&self) {
+        self.executed_queries.borrow_mut().clear();
+    }
+
+    fn record<T: QueryFragment<Debug>>(&self, source: &T) -> QueryResult<()> {
+        let mut query_builder = DebugQueryBuilder::new();
+        try!(source.to_sql(&mut query_builder).map_err(|e| {
+            Error::QueryBuilderError(e.description().to_string())
+        }));
+        self.executed_queries.borrow_mut().push(ExecutedQuery {
+            sql: query_builder.sql,
+            binds: query_builder.binds,
+        });
+        Ok(())
+    }
+
+    fn record_sql(&self, query: &str) {
+        self.executed_queries.borrow_mut().push(ExecutedQuery {
+            sql: query.to_string(),
+            binds: Vec::new(),
+        });
+    }
+
+    fn next_result(&self) -> QueryResult<Option<CannedResult>> {
+        match self.canned_results.borrow_mut().pop_front() {
+            Some(Ok(result)) => Ok(Some(result)),
+            Some(Err(e)) => Err(e),
+            None => Ok(None),
+        }
+    }
+
+    fn change_transaction_depth(&self, by: i32, query: &str) -> QueryResult<()> {
+        self.record_sql(query);
+        self.transaction_depth.set(self.transaction_depth.get() + by);
+        Ok(())
+    }
+}
+
+impl SimpleConnection for MockConnection {
+    fn batch_execute(&self, query: &str) -> QueryResult<()> {
+        self.record_sql(query);
+        self.next_result().map(|_| ())
+    }
+}
+
+impl Connection for MockConnection {
+    type Backend = Debug;
+
+    /// The URL is ignored. A new `MockConnection` is always returned.
+    fn establish(_database_url: &str) -> ConnectionResult<MockConnection> {
+        Ok(MockConnection::new())
+    }
+
+    fn execute(&self, query: &str) -> QueryResult<usize> {
+        self.record_sql(query);
+        self.next_result().map(rows_affected)
+    }
+
+    fn query_all<'a, T, U: 'a>(&self, source: T) -> QueryResult<Box<Iterator<Item=U> + 'a>> where
+        T: AsQuery,
+        T::Query: QueryFragment<Debug>,
+        Debug: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Debug>,
+    {
+        try!(self.record(&source.as_query()));
+        let rows = match try!(self.next_result()) {
+            Some(CannedResult::Rows(rows)) => rows,
+            _ => Vec::new(),
+        };
+        let values = rows.iter().map(|values| {
+            let mut row = MockRow::new(values);
+            match U::Row::build_from_row(&mut row) {
+                Ok(value) => U::build(value),
+                Err(reason) => panic!("Error reading values: {}", U::describe_error(reason)),
+            }
+        }).collect::<Vec<_>>();
+        Ok(Box::new(values.into_iter()))
+    }
+
+    fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize> where
+        T: QueryFragment<Debug>,
+    {
+        try!(self.record(source));
+        self.next_result().map(rows_affected)
+    }
+
+    fn silence_notices<F: FnOnce() -> T, T>(&self, f: F) -> T {
+        f()
+    }
+
+    fn begin_transaction(&self) -> QueryResult<()> {
+        let transaction_depth = self.transaction_depth.get();
+        if transaction_depth == 0 {
+            self.change_transaction_depth(1, "BEGIN")
+        } else {
+            self.change_transaction_depth(1,
+                &format!("SAVEPOINT diesel_savepoint_{}", transaction_depth))
+        }
+    }
+
+    fn rollback_transaction(&self) -> QueryResult<()> {
+        let transaction_depth = self.transaction_depth.get();
+        if transaction_depth == 1 {
+            self.change_transaction_depth(-1, "ROLLBACK")
+        } else {
+            self.change_transaction_depth(-1,
+                &format!("ROLLBACK TO SAVEPOINT diesel_savepoint_{}", transaction_depth - 1))
+        }
+    }
+
+    fn commit_transaction(&self) -> QueryResult<()> {
+        let transaction_depth = self.transaction_depth.get();
+        if transaction_depth <= 1 {
+            self.change_transaction_depth(-1, "COMMIT")
+        } else {
+            self.change_transaction_depth(-1,
+                &format!("RELEASE SAVEPOINT diesel_savepoint_{}", transaction_depth - 1))
+        }
+    }
+
+    fn get_transaction_depth(&self) -> i32 {
+        self.transaction_depth.get()
+    }
+}
+
+fn rows_affected(result: Option<CannedResult>) -> usize {
+    match result {
+        Some(CannedResult::RowsAffected(count)) => count,
+        Some(CannedResult::Rows(rows)) => rows.len(),
+        None => 0,
+    }
+}
+
+struct MockRow<'a> {
+    values: &'a [Option<Vec<u8>>],
+    col_idx: usize,
+}
+
+impl<'a> MockRow<'a> {
+    fn new(values: &'a [Option<Vec<u8>>]) -> Self {
+        MockRow {
+            values: values,
+            col_idx: 0,
+        }
+    }
+}
+
+impl<'a> Row for MockRow<'a> {
+    fn take(&mut self) -> Option<&[u8]> {
+        let current_idx = self.col_idx;
+        self.col_idx += 1;
+        self.values.get(current_idx)
+            .and_then(|value| value.as_ref())
+            .map(|value| &value[..])
+    }
+
+    fn next_is_null(&self, count: usize) -> bool {
+        (0..count).all(|i| {
+            self.values.get(self.col_idx + i)
+                .map(|value| value.is_none())
+                .unwrap_or(true)
+        })
+    }
+
+    fn advance(&mut self, count: usize) {
+        self.col_idx += count;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use connection::Connection;
+    use expression::AsExpression;
+    use expression::dsl::sql;
+    use query_builder::functions::select;
+    use query_dsl::LoadDsl;
+    use result::Error;
+    use types::{Integer, VarChar};
+
+    #[test]
+    fn queries_are_recorded_with_their_binds() {
+        let connection = MockConnection::new();
+        let _ = select(AsExpression::<Integer>::as_expression(1))
+            .load::<i32>(&connection).unwrap();
+
+        let expected = ExecutedQuery {
+            sql: "SELECT ?".to_string(),
+            binds: vec![Some(vec![0, 0, 0, 1])],
+        };
+        assert_eq!(Some(expected), connection.last_query());
+    }
+
+    #[test]
+    fn canned_rows_are_returned_in_order() {
+        let connection = MockConnection::new();
+        connection.push_rows(vec![vec![Some(vec![0, 0, 0, 1])], vec![None]]);
+        connection.push_rows(vec![vec![Some(b"Sean".to_vec())]]);
+
+        let first = select(sql::<::types::Nullable<Integer>>("1"))
+            .load::<Option<i32>>(&connection).unwrap().collect::<Vec<_>>();
+        let second = select(sql::<VarChar>("'Sean'"))
+            .load::<String>(&connection).unwrap().collect::<Vec<_>>();
+        let third = select(sql::<VarChar>("'Tess'"))
+            .load::<String>(&connection).unwrap().collect::<Vec<_>>();
+
+        assert_eq!(vec![Some(1), None], first);
+        assert_eq!(vec!["Sean".to_string()], second);
+        assert_eq!(Vec::<String>::new(), third);
+    }
+
+    #[test]
+    fn canned_errors_are_returned() {
+        let connection = MockConnection::new();
+        connection.push_error(Error::NotFound);
+
+        let result = select(sql::<Integer>("1")).load::<i32>(&connection);
+        assert_eq!(Err(Error::NotFound), result.map(|r| r.collect::<Vec<_>>()));
+    }
+
+    #[test]
+    fn transactions_are_recorded_without_consuming_results() {
+        let connection = MockConnection::new();
+        connection.push_rows_affected(3);
+
+        let count = connection.transaction::<_, (), _>(|| {
+            connection.execute("UPDATE users SET name = 'Jim'").map_err(|_| ())
+        }).unwrap();
+        let queries = connection.executed_queries().into_iter()
+            .map(|q| q.sql).collect::<Vec<_>>();
+
+        assert_eq!(3, count);
+        assert_eq!(vec!["BEGIN", "UPDATE users SET name = 'Jim'", "COMMIT"], queries);
+        assert_eq!(0, connection.get_transaction_depth());
+    }
+}
@@ -1,8 +1,14 @@
+#[cfg(feature = "postgres")]
 extern crate libc;
 
+#[cfg(feature = "postgres")]
+pub mod counting;
+pub mod mock;
+#[cfg(feature = "postgres")]
 pub mod pg;
 
-pub use self::pg::PgConnection;
+#[cfg(feature = "postgres")]
+pub use self::pg::{PgConnection, QueryEvent, LargeObject, LargeObjectMode};
 
 use backend::Backend;
 use query_builder::{AsQuery, QueryFragment};
@@ -11,7 +17,8 @@ use result::*;
 use types::HasSqlType;
 
 pub trait SimpleConnection {
-    #[doc(hidden)]
+    /// Executes one or more SQL statements, separated by semicolons, without
+    /// returning any results. No values can be bound to the query.
     fn batch_execute(&self, query: &str) -> QueryResult<()>;
 }
 
@@ -38,7 +38,7 @@ impl<ST, T> Iterator for Cursor<ST, T> where
             self.current_row += 1;
             let values = match T::Row::build_from_row(&mut row) {
                 Ok(value) => value,
-                Err(reason) => panic!("Error reading values {}", reason.description()),
+                Err(reason) => panic!("Error reading values: {}", T::describe_error(reason)),
             };
             let result = T::build(values);
             Some(result)
@@ -0,0 +1,105 @@
+use backend::Pg;
+use types::{self, FromSql};
+
+/// A query which is about to be sent to the database. Passed to the hook set by
+/// [`PgConnection::set_query_hook`](struct.PgConnection.html#method.set_query_hook).
+pub struct QueryEvent<'a> {
+    sql: &'a str,
+    binds: Vec<String>,
+}
+
+impl<'a> QueryEvent<'a> {
+    #[doc(hidden)]
+    pub fn new(
+        sql: &'a str,
+        binds: &[Option<Vec<u8>>],
+        bind_types: &Option<Vec<u32>>,
+        redact_binds: bool,
+    ) -> Self {
+        let binds = binds.iter().enumerate().map(|(i, bind)| {
+            let oid = bind_types.as_ref().and_then(|types| types.get(i).cloned());
+            if redact_binds {
+                "[REDACTED]".to_string()
+            } else {
+                render_bind(oid, bind.as_ref().map(|b| &b[..]))
+            }
+        }).collect();
+        QueryEvent {
+            sql: sql,
+            binds: binds,
+        }
+    }
+
+    /// The SQL which will be executed, exactly as it will be sent to the
+    /// database.
+    pub fn sql(&self) -> &str {
+        self.sql
+    }
+
+    /// A human readable rendering of each bind parameter, in the same order as
+    /// the placeholders in the SQL (`$1` is the first element). Integers,
+    /// floats, booleans and text are shown as their values. Other types are
+    /// shown as their size in bytes. When bind values are redacted, every value
+    /// is shown as `[REDACTED]`.
+    pub fn binds(&self) -> &[String] {
+        &self.binds
+    }
+}
+
+fn render_bind(oid: Option<u32>, bytes: Option<&[u8]>) -> String {
+    macro_rules! render {
+        ($ty:ty, $sql_type:ty) => {
+            <$ty as FromSql<$sql_type, Pg>>::from_sql(bytes)
+                .map(|value| format!("{:?}", value))
+                .unwrap_or_else(|_| unknown_bind(bytes))
+        }
+    }
+
+    if bytes.is_none() {
+        return "NULL".to_string();
+    }
+
+    match oid {
+        Some(16) => render!(bool, types::Bool),
+        Some(21) => render!(i16, types::SmallInt),
+        Some(23) => render!(i32, types::Integer),
+        Some(20) => render!(i64, types::BigInt),
+        Some(700) => render!(f32, types::Float),
+        Some(701) => render!(f64, types::Double),
+        Some(25) | Some(1043) => render!(String, types::Text),
+        _ => unknown_bind(bytes),
+    }
+}
+
+fn unknown_bind(bytes: Option<&[u8]>) -> String {
+    format!("<{} bytes>", bytes.map(|b| b.len()).unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueryEvent, render_bind};
+
+    #[test]
+    fn common_types_are_rendered_as_their_values() {
+        assert_eq!("NULL", render_bind(Some(23), None));
+        assert_eq!("true", render_bind(Some(16), Some(&[1][..])));
+        assert_eq!("1", render_bind(Some(23), Some(&[0, 0, 0, 1][..])));
+        assert_eq!("\"Sean\"", render_bind(Some(1043), Some(&b"Sean"[..])));
+    }
+
+    #[test]
+    fn other_types_are_rendered_as_their_size() {
+        assert_eq!("<3 bytes>", render_bind(Some(17), Some(&[1, 2, 3][..])));
+        assert_eq!("<4 bytes>", render_bind(None, Some(&[0, 0, 0, 1][..])));
+    }
+
+    #[test]
+    fn binds_can_be_redacted() {
+        let binds = vec![Some(b"hunter2".to_vec())];
+        let types = Some(vec![25]);
+        let event = QueryEvent::new("SELECT $1", &binds, &types, true);
+
+        assert_eq!("SELECT $1", event.sql());
+        assert_eq!(vec!["[REDACTED]".to_string()], event.binds().to_vec());
+    }
+}
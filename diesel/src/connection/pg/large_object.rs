@@ -0,0 +1,205 @@
+//! PostgreSQL large objects are stored outside of any table, and referenced by
+//! their `Oid`. Unlike `bytea`, they can be read and written in chunks, so the
+//! whole value never needs to be held in memory. Large objects can only be
+//! accessed inside of a transaction, and are closed when it ends.
+extern crate libc;
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use result::{Error, QueryResult};
+use super::PgConnection;
+
+const INV_WRITE: libc::c_int = 0x0002_0000;
+const INV_READ: libc::c_int = 0x0004_0000;
+
+const SEEK_SET: libc::c_int = 0;
+const SEEK_CUR: libc::c_int = 1;
+const SEEK_END: libc::c_int = 2;
+
+/// Whether a large object is opened for reading, writing, or both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LargeObjectMode {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl LargeObjectMode {
+    fn flags(&self) -> libc::c_int {
+        match *self {
+            LargeObjectMode::Read => INV_READ,
+            LargeObjectMode::Write => INV_WRITE,
+            LargeObjectMode::ReadWrite => INV_READ | INV_WRITE,
+        }
+    }
+}
+
+/// An open large object, returned by
+/// [`PgConnection::open_large_object`](struct.PgConnection.html#method.open_large_object).
+/// Implements `Read`, `Write` and `Seek`, and is closed when dropped.
+pub struct LargeObject<'a> {
+    connection: &'a PgConnection,
+    fd: libc::c_int,
+    oid: u32,
+}
+
+impl PgConnection {
+    /// Creates a new, empty large object, and returns its `Oid`. This must be
+    /// called inside of a transaction.
+    pub fn create_large_object(&self) -> QueryResult<u32> {
+        let mode = LargeObjectMode::ReadWrite.flags();
+        match unsafe { self.raw_connection.lo_creat(mode) } {
+            0 => Err(Error::DatabaseError(self.last_error_message())),
+            oid => Ok(oid),
+        }
+    }
+
+    /// Opens the large object with the given `Oid`. This must be called inside
+    /// of a transaction, and the large object can't be used after the
+    /// transaction ends.
+    pub fn open_large_object(&self, oid: u32, mode: LargeObjectMode) -> QueryResult<LargeObject> {
+        let fd = unsafe { self.raw_connection.lo_open(oid, mode.flags()) };
+        if fd < 0 {
+            Err(Error::DatabaseError(self.last_error_message()))
+        } else {
+            Ok(LargeObject {
+                connection: self,
+                fd: fd,
+                oid: oid,
+            })
+        }
+    }
+
+    /// Deletes the large object with the given `Oid`.
+    pub fn unlink_large_object(&self, oid: u32) -> QueryResult<()> {
+        if unsafe { self.raw_connection.lo_unlink(oid) } < 0 {
+            Err(Error::DatabaseError(self.last_error_message()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'a> LargeObject<'a> {
+    /// The `Oid` of this large object
+    pub fn oid(&self) -> u32 {
+        self.oid
+    }
+
+    fn last_error(&self) -> io::Error {
+        io::Error::new(io::ErrorKind::Other, self.connection.last_error_message())
+    }
+}
+
+impl<'a> Read for LargeObject<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = ::std::cmp::min(buf.len(), libc::c_int::max_value() as usize);
+        let read = unsafe {
+            self.connection.raw_connection.lo_read(
+                self.fd,
+                buf.as_mut_ptr() as *mut libc::c_char,
+                len as libc::size_t,
+            )
+        };
+        if read < 0 {
+            Err(self.last_error())
+        } else {
+            Ok(read as usize)
+        }
+    }
+}
+
+impl<'a> Write for LargeObject<'a> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = ::std::cmp::min(buf.len(), libc::c_int::max_value() as usize);
+        let written = unsafe {
+            self.connection.raw_connection.lo_write(
+                self.fd,
+                buf.as_ptr() as *const libc::c_char,
+                len as libc::size_t,
+            )
+        };
+        if written < 0 {
+            Err(self.last_error())
+        } else {
+            Ok(written as usize)
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> Seek for LargeObject<'a> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => (offset as i64, SEEK_SET),
+            SeekFrom::Current(offset) => (offset, SEEK_CUR),
+            SeekFrom::End(offset) => (offset, SEEK_END),
+        };
+        if offset > libc::c_int::max_value() as i64 || offset < libc::c_int::min_value() as i64 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "Large object offsets must fit in 32 bits"));
+        }
+        let position = unsafe {
+            self.connection.raw_connection.lo_lseek(self.fd, offset as libc::c_int, whence)
+        };
+        if position < 0 {
+            Err(self.last_error())
+        } else {
+            Ok(position as u64)
+        }
+    }
+}
+
+impl<'a> Drop for LargeObject<'a> {
+    fn drop(&mut self) {
+        unsafe { self.connection.raw_connection.lo_close(self.fd) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    use prelude::*;
+    use super::LargeObjectMode;
+    use test_helpers;
+
+    fn connection() -> PgConnection {
+        let connection = test_helpers::connection();
+        connection.begin_test_transaction().unwrap();
+        connection
+    }
+
+    #[test]
+    fn large_objects_can_be_written_and_read_in_chunks() {
+        let connection = connection();
+        let oid = connection.create_large_object().unwrap();
+
+        {
+            let mut large_object = connection.open_large_object(oid, LargeObjectMode::Write).unwrap();
+            for chunk in 0..4u8 {
+                large_object.write_all(&[chunk; 1024]).unwrap();
+            }
+        }
+
+        let mut large_object = connection.open_large_object(oid, LargeObjectMode::Read).unwrap();
+        assert_eq!(oid, large_object.oid());
+        assert_eq!(2048, large_object.seek(SeekFrom::Start(2048)).unwrap());
+        let mut contents = Vec::new();
+        large_object.read_to_end(&mut contents).unwrap();
+        assert_eq!(2048, contents.len());
+        assert_eq!(vec![2; 1024], &contents[..1024]);
+        assert_eq!(vec![3; 1024], &contents[1024..]);
+    }
+
+    #[test]
+    fn unlinked_large_objects_cannot_be_opened() {
+        let connection = connection();
+        let oid = connection.create_large_object().unwrap();
+        connection.unlink_large_object(oid).unwrap();
+        assert!(connection.open_large_object(oid, LargeObjectMode::Read).is_err());
+    }
+}
@@ -1,10 +1,15 @@
 extern crate libc;
 
 mod cursor;
+mod instrumentation;
+mod large_object;
 #[doc(hidden)]
 pub mod raw;
 
-use std::cell::Cell;
+pub use self::instrumentation::QueryEvent;
+pub use self::large_object::{LargeObject, LargeObjectMode};
+
+use std::cell::{Cell, RefCell};
 use std::ffi::{CString, CStr};
 use std::rc::Rc;
 use std::ptr;
@@ -26,12 +31,15 @@ use types::{ToSql, HasSqlType};
 pub struct PgConnection {
     raw_connection: Rc<RawConnection>,
     transaction_depth: Cell<i32>,
+    query_hook: RefCell<Option<Box<Fn(&QueryEvent) + Send>>>,
+    redact_bind_values: Cell<bool>,
 }
 
 unsafe impl Send for PgConnection {}
 
 impl SimpleConnection for PgConnection {
     fn batch_execute(&self, query: &str) -> QueryResult<()> {
+        self.run_query_hook(query, &Vec::new(), &None);
         let query = try!(CString::new(query));
         let inner_result = unsafe {
             self.raw_connection.exec(query.as_ptr())
@@ -49,6 +57,8 @@ impl Connection for PgConnection {
             PgConnection {
                 raw_connection: Rc::new(raw_conn),
                 transaction_depth: Cell::new(0),
+                query_hook: RefCell::new(None),
+                redact_bind_values: Cell::new(false),
             }
         })
     }
@@ -64,17 +74,13 @@ impl Connection for PgConnection {
         Pg: HasSqlType<T::SqlType>,
         U: Queryable<T::SqlType, Pg>,
     {
-        let (sql, params, types) = self.prepare_query(&source.as_query());
-        self.exec_sql_params(&sql, &params, &Some(types))
-            .map(|r| Box::new(Cursor::new(r)) as Box<Iterator<Item=U>>)
+        self.query_all_inspecting_sql(source, |_| ())
     }
 
     fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize> where
         T: QueryFragment<Pg>,
     {
-        let (sql, params, param_types) = self.prepare_query(source);
-        self.exec_sql_params(&sql, &params, &Some(param_types))
-            .map(|r| r.rows_affected())
+        self.execute_returning_count_inspecting_sql(source, |_| ())
     }
 
     fn silence_notices<F: FnOnce() -> T, T>(&self, f: F) -> T {
@@ -119,7 +125,63 @@ impl Connection for PgConnection {
 }
 
 impl PgConnection {
+    /// Sets a function to be called with every query run on this connection,
+    /// immediately before it is sent to the database. This is intended for
+    /// logging and instrumentation. Any previously set hook is replaced. The
+    /// hook must be `Send`, since the connection can be moved to another
+    /// thread, so it cannot hold a reference to the connection it is set on.
+    pub fn set_query_hook<F>(&self, hook: F) where
+        F: Fn(&QueryEvent) + Send + 'static,
+    {
+        *self.query_hook.borrow_mut() = Some(Box::new(hook));
+    }
+
+    /// Removes the hook set by `set_query_hook`.
+    pub fn clear_query_hook(&self) {
+        *self.query_hook.borrow_mut() = None;
+    }
+
+    /// When set to `true`, the values of bind parameters will not be given to
+    /// the query hook. Use this when queries may contain sensitive data such as
+    /// passwords, which should never be written to logs.
+    pub fn redact_bind_values(&self, redact: bool) {
+        self.redact_bind_values.set(redact);
+    }
+
+    /// Like `query_all`, but gives the SQL of the query to `inspect_sql` once
+    /// it has been built, before it is run.
+    #[doc(hidden)]
+    pub fn query_all_inspecting_sql<'a, T, U: 'a, F>(&self, source: T, inspect_sql: F)
+        -> QueryResult<Box<Iterator<Item=U> + 'a>> where
+        T: AsQuery,
+        T::Query: QueryFragment<Pg>,
+        T::SqlType: 'static,
+        Pg: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Pg>,
+        F: FnOnce(&str),
+    {
+        let (sql, params, types) = try!(self.prepare_query(&source.as_query()));
+        inspect_sql(&sql);
+        self.exec_sql_params(&sql, &params, &Some(types))
+            .map(|r| Box::new(Cursor::new(r)) as Box<Iterator<Item=U>>)
+    }
+
+    /// Like `execute_returning_count`, but gives the SQL of the query to
+    /// `inspect_sql` once it has been built, before it is run.
+    #[doc(hidden)]
+    pub fn execute_returning_count_inspecting_sql<T, F>(&self, source: &T, inspect_sql: F)
+        -> QueryResult<usize> where
+        T: QueryFragment<Pg>,
+        F: FnOnce(&str),
+    {
+        let (sql, params, param_types) = try!(self.prepare_query(source));
+        inspect_sql(&sql);
+        self.exec_sql_params(&sql, &params, &Some(param_types))
+            .map(|r| r.rows_affected())
+    }
+
     fn exec_sql_params(&self, query: &str, param_data: &Vec<Option<Vec<u8>>>, param_types: &Option<Vec<u32>>) -> QueryResult<PgResult> {
+        self.run_query_hook(query, param_data, param_types);
         let query = try!(CString::new(query));
         let params_pointer = param_data.iter()
             .map(|data| data.as_ref().map(|d| d.as_ptr() as *const libc::c_char)
@@ -150,11 +212,20 @@ impl PgConnection {
     }
 
     fn prepare_query<T: QueryFragment<Pg>>(&self, source: &T)
-        -> (String, Vec<Option<Vec<u8>>>, Vec<u32>)
+        -> QueryResult<(String, Vec<Option<Vec<u8>>>, Vec<u32>)>
     {
-        let mut query_builder = PgQueryBuilder::new(&self.raw_connection);
-        source.to_sql(&mut query_builder).unwrap();
-        (query_builder.sql, query_builder.binds, query_builder.bind_types)
+        let mut query_builder = PgQueryBuilder::new();
+        try!(source.to_sql(&mut query_builder).map_err(|e| {
+            Error::QueryBuilderError(e.description().to_string())
+        }));
+        Ok((query_builder.sql, query_builder.binds, query_builder.bind_types))
+    }
+
+    fn run_query_hook(&self, query: &str, param_data: &[Option<Vec<u8>>], param_types: &Option<Vec<u32>>) {
+        if let Some(ref hook) = *self.query_hook.borrow() {
+            hook(&QueryEvent::new(query, param_data, param_types,
+                                  self.redact_bind_values.get()));
+        }
     }
 
     fn execute_inner(&self, query: &str) -> QueryResult<PgResult> {
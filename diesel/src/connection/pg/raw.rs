@@ -81,6 +81,34 @@ impl RawConnection {
             result_format,
         )
     }
+
+    pub unsafe fn lo_creat(&self, mode: libc::c_int) -> Oid {
+        lo_creat(self.internal_connection, mode)
+    }
+
+    pub unsafe fn lo_open(&self, oid: Oid, mode: libc::c_int) -> libc::c_int {
+        lo_open(self.internal_connection, oid, mode)
+    }
+
+    pub unsafe fn lo_close(&self, fd: libc::c_int) -> libc::c_int {
+        lo_close(self.internal_connection, fd)
+    }
+
+    pub unsafe fn lo_read(&self, fd: libc::c_int, buf: *mut libc::c_char, len: libc::size_t) -> libc::c_int {
+        lo_read(self.internal_connection, fd, buf, len)
+    }
+
+    pub unsafe fn lo_write(&self, fd: libc::c_int, buf: *const libc::c_char, len: libc::size_t) -> libc::c_int {
+        lo_write(self.internal_connection, fd, buf, len)
+    }
+
+    pub unsafe fn lo_lseek(&self, fd: libc::c_int, offset: libc::c_int, whence: libc::c_int) -> libc::c_int {
+        lo_lseek(self.internal_connection, fd, offset, whence)
+    }
+
+    pub unsafe fn lo_unlink(&self, oid: Oid) -> libc::c_int {
+        lo_unlink(self.internal_connection, oid)
+    }
 }
 
 pub type NoticeProcessor = extern "C" fn(arg: *mut libc::c_void, message: *const libc::c_char);
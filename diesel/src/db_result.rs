@@ -61,6 +61,17 @@ impl PgResult {
         }
     }
 
+    pub fn column_name(&self, col_idx: usize) -> Option<&str> {
+        unsafe {
+            let name_ptr = PQfname(self.internal_result, col_idx as libc::c_int);
+            if name_ptr.is_null() {
+                None
+            } else {
+                str::from_utf8(CStr::from_ptr(name_ptr).to_bytes()).ok()
+            }
+        }
+    }
+
     pub fn is_null(&self, row_idx: usize, col_idx: usize) -> bool {
         unsafe {
             0 != PQgetisnull(
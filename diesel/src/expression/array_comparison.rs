@@ -1,9 +1,10 @@
 use std::marker::PhantomData;
 
-use backend::{Backend, Pg};
+use backend::{Backend, Debug, Pg};
 use query_builder::*;
 use super::{AsExpression, Expression, SelectableExpression, NonAggregate};
-use types::{Array, HasSqlType};
+use super::bound::Bound;
+use types::{Array, Bool, HasSqlType, ToSql};
 
 /// Creates a PostgreSQL `ANY` expression.
 ///
@@ -88,3 +89,87 @@ impl<Expr, ST> NonAggregate for Any<Expr, ST> where
     Any<Expr, ST>: Expression,
 {
 }
+
+/// The return type of
+/// [`eq_any`](../expression_methods/global_expression_methods/trait.ExpressionMethods.html#method.eq_any).
+/// PostgreSQL renders `lhs = ANY($1)`, with the values sent as a single array
+/// bind parameter. Other backends render `lhs IN (?, ?, ...)`, or a `VALUES`
+/// derived table once there are more than `VALUES_JOIN_THRESHOLD` values.
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct EqAny<Lhs, T> {
+    lhs: Lhs,
+    values: Vec<T>,
+}
+
+impl<Lhs, T> EqAny<Lhs, T> {
+    pub fn new(lhs: Lhs, values: Vec<T>) -> Self {
+        EqAny {
+            lhs: lhs,
+            values: values,
+        }
+    }
+}
+
+/// Beyond this many values, backends without array bind parameters compare
+/// against a `VALUES` derived table instead of an `IN` list, which databases
+/// plan as a join rather than evaluating every alternative for every row.
+const VALUES_JOIN_THRESHOLD: usize = 1000;
+
+impl<Lhs: Expression, T> Expression for EqAny<Lhs, T> {
+    type SqlType = Bool;
+}
+
+impl<Lhs, T> QueryFragment<Pg> for EqAny<Lhs, T> where
+    Lhs: Expression + QueryFragment<Pg>,
+    Pg: HasSqlType<Lhs::SqlType>,
+    for<'a> &'a [T]: ToSql<Array<Lhs::SqlType>, Pg>,
+{
+    fn to_sql(&self, out: &mut <Pg as Backend>::QueryBuilder) -> BuildQueryResult {
+        try!(self.lhs.to_sql(out));
+        out.push_sql(" = ANY(");
+        try!(Bound::<Array<Lhs::SqlType>, _>::new(&self.values[..]).to_sql(out));
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<Lhs, T> QueryFragment<Debug> for EqAny<Lhs, T> where
+    Lhs: Expression + QueryFragment<Debug>,
+    Debug: HasSqlType<Lhs::SqlType>,
+    T: ToSql<Lhs::SqlType, Debug>,
+{
+    fn to_sql(&self, out: &mut <Debug as Backend>::QueryBuilder) -> BuildQueryResult {
+        if self.values.is_empty() {
+            out.push_sql("1 = 0");
+            return Ok(());
+        }
+        let (open, separator, close) = if self.values.len() > VALUES_JOIN_THRESHOLD {
+            (" IN (SELECT column_0 FROM (VALUES ROW(", "), ROW(", ")) AS eq_any_values)")
+        } else {
+            (" IN (", ", ", ")")
+        };
+        try!(self.lhs.to_sql(out));
+        out.push_sql(open);
+        for (i, value) in self.values.iter().enumerate() {
+            if i != 0 {
+                out.push_sql(separator);
+            }
+            try!(Bound::<Lhs::SqlType, _>::new(value).to_sql(out));
+        }
+        out.push_sql(close);
+        Ok(())
+    }
+}
+
+impl<Lhs, T, QS> SelectableExpression<QS> for EqAny<Lhs, T> where
+    EqAny<Lhs, T>: Expression,
+    Lhs: SelectableExpression<QS>,
+{
+}
+
+impl<Lhs, T> NonAggregate for EqAny<Lhs, T> where
+    EqAny<Lhs, T>: Expression,
+    Lhs: NonAggregate,
+{
+}
@@ -29,7 +29,7 @@ impl<T, U, DB> QueryFragment<DB> for Bound<T, U> where
     U: ToSql<T, DB>,
 {
     fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
-        let mut bytes = Vec::new();
+        let mut bytes = Vec::with_capacity(self.item.size_hint());
         match try!(self.item.to_sql(&mut bytes)) {
             IsNull::Yes => {
                 out.push_bound_value::<T>(None);
@@ -0,0 +1,172 @@
+use backend::{Backend, Debug, Pg};
+use expression::{Expression, SelectableExpression, NonAggregate};
+use query_builder::*;
+use std::marker::PhantomData;
+use types;
+
+/// A SQL type which values can be cast to with
+/// [`cast`](../expression_methods/global_expression_methods/trait.ExpressionMethods.html#method.cast).
+/// This writes the name of the type as it is spelled by the given backend.
+pub trait CastTarget<DB: Backend> {
+    fn write_type_name(out: &mut DB::QueryBuilder);
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Cast<Expr, ST> {
+    expr: Expr,
+    _marker: PhantomData<ST>,
+}
+
+impl<Expr, ST> Cast<Expr, ST> {
+    pub fn new(expr: Expr) -> Self {
+        Cast {
+            expr: expr,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<Expr, ST> Expression for Cast<Expr, ST> where
+    Expr: Expression,
+{
+    type SqlType = ST;
+}
+
+impl<Expr, ST, DB> QueryFragment<DB> for Cast<Expr, ST> where
+    DB: Backend,
+    Expr: QueryFragment<DB>,
+    ST: CastTarget<DB>,
+{
+    fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        out.push_sql("CAST(");
+        try!(self.expr.to_sql(out));
+        out.push_sql(" AS ");
+        ST::write_type_name(out);
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<Expr, ST, QS> SelectableExpression<QS> for Cast<Expr, ST> where
+    Expr: SelectableExpression<QS>,
+{
+}
+
+impl<Expr, ST> NonAggregate for Cast<Expr, ST> where
+    Expr: NonAggregate,
+{
+}
+
+impl<ST, DB> CastTarget<DB> for types::Nullable<ST> where
+    DB: Backend,
+    ST: types::NotNull + CastTarget<DB>,
+{
+    fn write_type_name(out: &mut DB::QueryBuilder) {
+        ST::write_type_name(out)
+    }
+}
+
+impl<ST, DB> CastTarget<DB> for types::Array<ST> where
+    DB: Backend,
+    ST: CastTarget<DB>,
+{
+    fn write_type_name(out: &mut DB::QueryBuilder) {
+        ST::write_type_name(out);
+        out.push_sql("[]");
+    }
+}
+
+macro_rules! pg_cast_targets {
+    ($($Source:ident -> $name:expr),+,) => {$(
+        impl CastTarget<Pg> for types::$Source {
+            fn write_type_name(out: &mut <Pg as Backend>::QueryBuilder) {
+                out.push_sql($name)
+            }
+        }
+
+        impl CastTarget<Debug> for types::$Source {
+            fn write_type_name(out: &mut <Debug as Backend>::QueryBuilder) {
+                out.push_sql($name)
+            }
+        }
+    )+}
+}
+
+pg_cast_targets! {
+    Bool -> "boolean",
+    TinyInt -> "\"char\"",
+    SmallInt -> "smallint",
+    Integer -> "integer",
+    BigInt -> "bigint",
+    Oid -> "oid",
+    Regclass -> "regclass",
+    Regtype -> "regtype",
+    OidVector -> "oidvector",
+    Float -> "real",
+    Double -> "double precision",
+    Numeric -> "numeric",
+    Money -> "money",
+    VarChar -> "varchar",
+    Text -> "text",
+    Char -> "bpchar",
+    Xml -> "xml",
+    Binary -> "bytea",
+    Varbit -> "varbit",
+    Inet -> "inet",
+    Cidr -> "cidr",
+    Hstore -> "hstore",
+    Ltree -> "ltree",
+    Lquery -> "lquery",
+    TsVector -> "tsvector",
+    TsQuery -> "tsquery",
+    Point -> "point",
+    Rectangle -> "box",
+    Circle -> "circle",
+    Path -> "path",
+    Polygon -> "polygon",
+    Date -> "date",
+    Interval -> "interval",
+    Time -> "time",
+    Timestamp -> "timestamp",
+    Timestamptz -> "timestamptz",
+    Jsonpath -> "jsonpath",
+}
+
+#[cfg(feature = "serde_json")]
+pg_cast_targets! {
+    Json -> "json",
+    Jsonb -> "jsonb",
+}
+
+#[cfg(feature = "uuid")]
+pg_cast_targets! {
+    Uuid -> "uuid",
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use backend::Pg;
+    use expression::dsl::sql;
+    use prelude::*;
+    use query_builder::QueryFragment;
+    use query_builder::pg::PgQueryBuilder;
+    use test_helpers::connection;
+    use types::{Array, Integer, Nullable, Text, VarChar};
+
+    #[test]
+    fn cast_uses_the_backend_type_name() {
+        let expr = sql::<VarChar>("'{1}'").cast::<Nullable<Array<Integer>>>();
+        let mut query_builder = PgQueryBuilder::new();
+        QueryFragment::<Pg>::to_sql(&expr, &mut query_builder).unwrap();
+        assert_eq!("CAST('{1}' AS integer[])", query_builder.sql);
+    }
+
+    #[test]
+    fn cast_converts_between_types() {
+        let connection = connection();
+        let query = select(sql::<VarChar>("'42'").cast::<Integer>());
+        assert_eq!(Ok(42), query.get_result(&connection));
+        let query = select(sql::<Integer>("42").cast::<Text>());
+        assert_eq!(Ok("42".to_string()), query.get_result(&connection));
+    }
+}
@@ -0,0 +1,38 @@
+use expression::{Expression, AsExpression};
+use expression::predicates::{ContainedBy, Contains, Distance};
+use types::Geometric;
+
+pub trait GeometricExpressionMethods: Expression + Sized {
+    /// Returns a SQL `<->` expression, which is the distance between this
+    /// value and `other`.
+    fn distance<T, ST>(self, other: T) -> Distance<Self, T::Expression> where
+        T: AsExpression<ST>,
+        ST: Geometric,
+    {
+        Distance::new(self, other.as_expression())
+    }
+
+    /// Returns a SQL `@>` expression, which is true if `other` is inside of
+    /// this value.
+    fn contains<T, ST>(self, other: T) -> Contains<Self, T::Expression> where
+        T: AsExpression<ST>,
+        ST: Geometric,
+    {
+        Contains::new(self, other.as_expression())
+    }
+
+    /// Returns a SQL `<@` expression, which is true if this value is inside of
+    /// `other`.
+    fn is_contained_by<T, ST>(self, other: T) -> ContainedBy<Self, T::Expression> where
+        T: AsExpression<ST>,
+        ST: Geometric,
+    {
+        ContainedBy::new(self, other.as_expression())
+    }
+}
+
+impl<T> GeometricExpressionMethods for T where
+    T: Expression,
+    T::SqlType: Geometric,
+{
+}
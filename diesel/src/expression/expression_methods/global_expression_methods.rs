@@ -1,5 +1,7 @@
 use expression::{Expression, AsExpression, nullable};
 use expression::aliased::Aliased;
+use expression::array_comparison::EqAny;
+use expression::cast::Cast;
 use expression::predicates::*;
 
 pub trait ExpressionMethods: Expression + Sized {
@@ -83,6 +85,42 @@ pub trait ExpressionMethods: Expression + Sized {
         NotEq::new(self, other.as_expression())
     }
 
+    /// Creates an expression which is true if the expression is equal to any
+    /// of the given values. On PostgreSQL this is `= ANY($1)`, and the values
+    /// are sent as a single array bind parameter. This means that the query is
+    /// the same regardless of how many values are given, and that very large
+    /// lists will not exceed PostgreSQL's limit of 65535 bind parameters per
+    /// query. Other backends use an `IN` list, with one bind parameter per
+    /// value. Lists of more than 1000 values are instead compared against a
+    /// `VALUES` derived table, which the database can plan as a join.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("src/doctest_setup.rs");
+    /// #
+    /// # table! {
+    /// #     users {
+    /// #         id -> Serial,
+    /// #         name -> VarChar,
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     use self::users::dsl::*;
+    /// #     let connection = establish_connection();
+    /// let data = users.select(id).filter(name.eq_any(vec!["Sean", "Jim"]));
+    /// assert_eq!(vec![1], data.load(&connection).unwrap().collect::<Vec<i32>>());
+    /// # }
+    /// ```
+    fn eq_any<T>(self, values: T) -> EqAny<Self, T::Item> where
+        T: IntoIterator,
+        T::Item: AsExpression<Self::SqlType>,
+    {
+        EqAny::new(self, values.into_iter().collect())
+    }
+
     /// Creates a SQL `IS NULL` expression.
     fn is_null(self) -> IsNull<Self> {
        IsNull::new(self)
@@ -253,6 +291,10 @@ pub trait ExpressionMethods: Expression + Sized {
     /// as nullable. This method has no impact on the generated SQL, and is only
     /// used to allow certain comparisons that would otherwise fail to compile.
     ///
+    /// When called on a tuple of columns from the right side of a left outer
+    /// join (such as `posts::all_columns`), the whole tuple can be selected as
+    /// a single nullable value, and loaded as an `Option`.
+    ///
     /// # Example
     /// ```no_run
     /// # #![allow(dead_code)]
@@ -305,6 +347,36 @@ pub trait ExpressionMethods: Expression + Sized {
     fn nullable(self) -> nullable::Nullable<Self> {
         nullable::Nullable::new(self)
     }
+
+    /// Creates a SQL `CAST(expr AS type)` expression, converting the
+    /// expression to the given SQL type. The name of the type is written as
+    /// the backend spells it, and it must implement
+    /// [`CastTarget`](../../cast/trait.CastTarget.html).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("src/doctest_setup.rs");
+    /// # use diesel::types::Text;
+    /// #
+    /// # table! {
+    /// #     users {
+    /// #         id -> Serial,
+    /// #         name -> VarChar,
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     use self::users::dsl::*;
+    /// #     let connection = establish_connection();
+    /// let data = users.select(id.cast::<Text>()).filter(name.eq("Tess"));
+    /// assert_eq!(Ok("2".to_string()), data.first(&connection));
+    /// # }
+    /// ```
+    fn cast<ST>(self) -> Cast<Self, ST> {
+        Cast::new(self)
+    }
 }
 
 impl<T: Expression> ExpressionMethods for T {}
@@ -0,0 +1,24 @@
+use expression::{Expression, AsExpression};
+use expression::predicates::{Contains, GetKey, HasKey};
+use types::{Hstore, Text};
+
+pub trait HstoreExpressionMethods: Expression<SqlType=Hstore> + Sized {
+    /// Returns a SQL `->` expression, which is the value for the given key, or
+    /// `NULL` if the key is not present.
+    fn get<T: AsExpression<Text>>(self, key: T) -> GetKey<Self, T::Expression> {
+        GetKey::new(self, key.as_expression())
+    }
+
+    /// Returns a SQL `?` expression, which is true if the given key is present.
+    fn has_key<T: AsExpression<Text>>(self, key: T) -> HasKey<Self, T::Expression> {
+        HasKey::new(self, key.as_expression())
+    }
+
+    /// Returns a SQL `@>` expression, which is true if every pair in `other`
+    /// is also present in this one.
+    fn contains<T: AsExpression<Hstore>>(self, other: T) -> Contains<Self, T::Expression> {
+        Contains::new(self, other.as_expression())
+    }
+}
+
+impl<T: Expression<SqlType=Hstore>> HstoreExpressionMethods for T {}
@@ -0,0 +1,49 @@
+use expression::AsExpression;
+use expression::helper_types::AsExprOf;
+
+/// Converts a plain Rust value into an expression of a given SQL type. This is
+/// implemented for every type, but `into_sql` can only be called when the value
+/// implements [`AsExpression`](../../trait.AsExpression.html) for the requested
+/// type.
+///
+/// The value is sent as a bind parameter, so this is useful wherever an
+/// expression is needed and there is no column to infer the type from, such as
+/// selecting a constant alongside each row.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("src/doctest_setup.rs");
+/// # use diesel::types::VarChar;
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// #     use self::users::dsl::*;
+/// #     let connection = establish_connection();
+/// let data = users.select((name, "user".into_sql::<VarChar>()))
+///     .order(id)
+///     .load::<(String, String)>(&connection)
+///     .unwrap().collect::<Vec<_>>();
+/// let expected = vec![
+///     ("Sean".to_string(), "user".to_string()),
+///     ("Tess".to_string(), "user".to_string()),
+/// ];
+/// assert_eq!(expected, data);
+/// # }
+/// ```
+pub trait IntoSql {
+    fn into_sql<T>(self) -> AsExprOf<Self, T> where
+        Self: AsExpression<T> + Sized,
+    {
+        self.as_expression()
+    }
+}
+
+impl<T> IntoSql for T {}
@@ -0,0 +1,25 @@
+use expression::{Expression, AsExpression};
+use expression::predicates::{ContainedBy, Contains, LqueryMatches};
+use types::{Lquery, Ltree};
+
+pub trait LtreeExpressionMethods: Expression<SqlType=Ltree> + Sized {
+    /// Returns a SQL `@>` expression, which is true if this path is an
+    /// ancestor of `other`, or is equal to it.
+    fn is_ancestor_of<T: AsExpression<Ltree>>(self, other: T) -> Contains<Self, T::Expression> {
+        Contains::new(self, other.as_expression())
+    }
+
+    /// Returns a SQL `<@` expression, which is true if this path is a
+    /// descendant of `other`, or is equal to it.
+    fn is_descendant_of<T: AsExpression<Ltree>>(self, other: T) -> ContainedBy<Self, T::Expression> {
+        ContainedBy::new(self, other.as_expression())
+    }
+
+    /// Returns a SQL `~` expression, which is true if this path matches the
+    /// given `lquery`.
+    fn matches<T: AsExpression<Lquery>>(self, query: T) -> LqueryMatches<Self, T::Expression> {
+        LqueryMatches::new(self, query.as_expression())
+    }
+}
+
+impl<T: Expression<SqlType=Ltree>> LtreeExpressionMethods for T {}
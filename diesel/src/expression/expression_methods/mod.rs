@@ -7,11 +7,21 @@
 pub mod global_expression_methods;
 pub mod bool_expression_methods;
 pub mod text_expression_methods;
+pub mod hstore_expression_methods;
+pub mod ltree_expression_methods;
+pub mod geometric_expression_methods;
+pub mod text_search_expression_methods;
+pub mod into_sql;
 #[doc(hidden)]
 pub mod timestamp_expression_methods;
 
 pub use self::global_expression_methods::ExpressionMethods;
 pub use self::bool_expression_methods::BoolExpressionMethods;
 pub use self::text_expression_methods::{TextExpressionMethods, VarCharExpressionMethods};
+pub use self::hstore_expression_methods::HstoreExpressionMethods;
+pub use self::ltree_expression_methods::LtreeExpressionMethods;
+pub use self::geometric_expression_methods::GeometricExpressionMethods;
+pub use self::text_search_expression_methods::{TsQueryExpressionMethods, TsVectorExpressionMethods};
+pub use self::into_sql::IntoSql;
 #[doc(hidden)]
 pub use self::timestamp_expression_methods::TimestampExpressionMethods;
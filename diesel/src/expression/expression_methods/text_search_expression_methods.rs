@@ -0,0 +1,23 @@
+use expression::{Expression, AsExpression};
+use expression::predicates::Matches;
+use types::{TsQuery, TsVector};
+
+pub trait TsVectorExpressionMethods: Expression<SqlType=TsVector> + Sized {
+    /// Returns a SQL `@@` expression, which is true if this document matches
+    /// the query.
+    fn matches<T: AsExpression<TsQuery>>(self, query: T) -> Matches<Self, T::Expression> {
+        Matches::new(self, query.as_expression())
+    }
+}
+
+impl<T: Expression<SqlType=TsVector>> TsVectorExpressionMethods for T {}
+
+pub trait TsQueryExpressionMethods: Expression<SqlType=TsQuery> + Sized {
+    /// Returns a SQL `@@` expression, which is true if the document matches
+    /// this query.
+    fn matches<T: AsExpression<TsVector>>(self, document: T) -> Matches<Self, T::Expression> {
+        Matches::new(self, document.as_expression())
+    }
+}
+
+impl<T: Expression<SqlType=TsQuery>> TsQueryExpressionMethods for T {}
@@ -239,7 +239,7 @@ impl DayAndMonthIntervalDsl for f64 {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "postgres"))]
 mod tests {
     extern crate dotenv;
     extern crate quickcheck;
@@ -0,0 +1,34 @@
+use types::*;
+
+sql_function!(jsonb_path_exists, jsonb_path_exists_t, (target: Jsonb, path: Jsonpath) -> Bool,
+"Represents the SQL JSONB_PATH_EXISTS() function, which checks whether the
+path returns any item for the given `jsonb` value. Requires PostgreSQL 12.");
+sql_function!(jsonb_path_query_first, jsonb_path_query_first_t,
+(target: Jsonb, path: Jsonpath) -> Nullable<Jsonb>,
+"Represents the SQL JSONB_PATH_QUERY_FIRST() function, which returns the
+first item returned by the path, or `NULL` if there are none. Requires
+PostgreSQL 12.");
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    extern crate serde_json;
+
+    use self::serde_json::Value;
+
+    use expression::dsl::*;
+    use prelude::*;
+    use test_helpers::connection;
+    use types::Jsonb;
+
+    #[test]
+    fn jsonb_values_can_be_queried_with_a_path() {
+        let connection = connection();
+        let document = || sql::<Jsonb>(r#"'{"tags": [{"name": "rust"}]}'::jsonb"#);
+        let query = select(jsonb_path_exists(document(), "$.tags[*] ? (@.name == \"rust\")"));
+        assert_eq!(Ok(true), query.get_result(&connection));
+        let query = select(jsonb_path_query_first(document(), "$.tags[0].name"));
+        assert_eq!(Ok(Some(Value::String("rust".to_string()))), query.get_result(&connection));
+        let query = select(jsonb_path_query_first(document(), "$.missing"));
+        assert_eq!(Ok(None::<Value>), query.get_result(&connection));
+    }
+}
@@ -159,3 +159,6 @@ macro_rules! no_arg_sql_function {
 
 pub mod aggregate_ordering;
 pub mod date_and_time;
+#[cfg(feature = "serde_json")]
+pub mod json;
+pub mod text_search;
@@ -0,0 +1,28 @@
+use types::*;
+
+sql_function!(to_tsvector, to_tsvector_t, (document: Text) -> TsVector,
+"Represents the SQL TO_TSVECTOR() function. The document is parsed using the
+database's `default_text_search_config`.");
+sql_function!(to_tsquery, to_tsquery_t, (query: Text) -> TsQuery,
+"Represents the SQL TO_TSQUERY() function. The query must be written with
+the `tsquery` operators, such as `'fat & rat'`.");
+sql_function!(plainto_tsquery, plainto_tsquery_t, (query: Text) -> TsQuery,
+"Represents the SQL PLAINTO_TSQUERY() function. Punctuation in the query is
+ignored, and every word must match, so this is suitable for text typed in by
+a user.");
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use expression::dsl::*;
+    use prelude::*;
+    use test_helpers::connection;
+
+    #[test]
+    fn documents_can_be_matched_against_queries() {
+        let connection = connection();
+        let document = || to_tsvector("The fat rats were eaten by a cat");
+        assert_eq!(Ok(true), select(document().matches(to_tsquery("fat & rat"))).get_result(&connection));
+        assert_eq!(Ok(false), select(document().matches(to_tsquery("fat & dog"))).get_result(&connection));
+        assert_eq!(Ok(true), select(plainto_tsquery("a fat cat!").matches(document())).get_result(&connection));
+    }
+}
@@ -8,6 +8,8 @@ pub type AsExpr<Item, TargetExpr> = <Item as AsExpression<
     <TargetExpr as Expression>::SqlType
 >>::Expression;
 
+pub type AsExprOf<Item, Type> = <Item as AsExpression<Type>>::Expression;
+
 macro_rules! gen_helper_type {
     ($name:ident) => {
         pub type $name<Lhs, Rhs> = super::predicates::$name<Lhs, AsExpr<Rhs, Lhs>>;
@@ -36,4 +38,6 @@ pub type Between<Lhs, Rhs> = super::predicates::Between<Lhs,
 pub type NotBetween<Lhs, Rhs> = super::predicates::NotBetween<Lhs,
     super::predicates::And<AsExpr<Rhs, Lhs>, AsExpr<Rhs, Lhs>>>;
 
+pub type EqAny<Lhs, Rhs> = super::array_comparison::EqAny<Lhs, <Rhs as IntoIterator>::Item>;
+
 pub use super::predicates::{IsNull, IsNotNull, Asc, Desc};
@@ -23,12 +23,14 @@ pub mod aliased;
 pub mod array_comparison;
 #[doc(hidden)]
 pub mod bound;
+pub mod cast;
 #[doc(hidden)]
 pub mod count;
 #[doc(hidden)]
 pub mod date_and_time;
 pub mod expression_methods;
 pub mod extensions;
+#[macro_use]
 #[doc(hidden)]
 pub mod functions;
 #[doc(hidden)]
@@ -48,6 +50,9 @@ pub mod dsl {
     #[doc(inline)] pub use super::count::{count, count_star};
     #[doc(inline)] pub use super::functions::date_and_time::*;
     #[doc(inline)] pub use super::functions::aggregate_ordering::*;
+    #[cfg(feature = "serde_json")]
+    #[doc(inline)] pub use super::functions::json::*;
+    #[doc(inline)] pub use super::functions::text_search::*;
     #[doc(inline)] pub use super::sql_literal::sql;
 
     pub use super::extensions::*;
@@ -142,6 +147,50 @@ use query_builder::QueryFragment;
 /// Helper trait used when boxing expressions. This exists to work around the
 /// fact that Rust will not let us use non-core types as bounds on a trait
 /// object (you could not return `Box<Expression+NonAggregate>`)
+///
+/// Boxed expressions are useful when the shape of a query is only known at
+/// runtime, such as building the predicates for a search form. Since boxed
+/// predicates all have the same type, they can be stored in a `Vec` and
+/// combined with `.and` or `.or`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("src/doctest_setup.rs");
+/// # use diesel::backend::Pg;
+/// # use diesel::types::Bool;
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// #     use self::users::dsl::*;
+/// #     let connection = establish_connection();
+/// type Predicate = Box<BoxableExpression<users, Bool, Pg, SqlType=Bool>>;
+///
+/// let search_name = Some("Sean");
+/// let min_id = None::<i32>;
+///
+/// let mut predicates = Vec::<Predicate>::new();
+/// if let Some(search_name) = search_name {
+///     predicates.push(Box::new(name.eq(search_name)));
+/// }
+/// if let Some(min_id) = min_id {
+///     predicates.push(Box::new(id.ge(min_id)));
+/// }
+///
+/// let always_true: Predicate = Box::new(AsExpression::<Bool>::as_expression(true));
+/// let predicate = predicates.into_iter()
+///     .fold(always_true, |acc, predicate| Box::new(acc.and(predicate)));
+/// let names = users.select(name).filter(predicate).load::<String>(&connection);
+/// assert_eq!(vec!["Sean".to_string()], names.unwrap().collect::<Vec<_>>());
+/// # }
+/// ```
 pub trait BoxableExpression<QS, ST, DB> where
     DB: Backend,
     Self: Expression,
@@ -1,7 +1,7 @@
 use backend::Backend;
 use expression::{Expression, SelectableExpression, NonAggregate};
 use query_builder::*;
-use types::IntoNullable;
+use types::{self, IntoNullable, NotNull};
 
 pub struct Nullable<T>(T);
 
@@ -28,11 +28,35 @@ impl<T, DB> QueryFragment<DB> for Nullable<T> where
 }
 
 impl<T, QS> SelectableExpression<QS> for Nullable<T> where
-    T: SelectableExpression<QS>,
+    T: Expression,
+    T::SqlType: SelectableAsNullable<T, QS>,
     Nullable<T>: Expression,
 {
 }
 
+/// Determines whether `Nullable<Expr>` can be selected from `QS`, based on the
+/// SQL type of `Expr`. For single values this is the case whenever `Expr`
+/// itself can be selected. For tuples, every element must be selectable as
+/// nullable, which allows the entire right side of a left outer join to be
+/// marked as nullable (e.g. `posts::all_columns.nullable()`).
+///
+/// This is implemented on the SQL type rather than the expression, as Rust
+/// would otherwise consider the impls for tuples and other expressions to
+/// overlap.
+pub trait SelectableAsNullable<Expr, QS> {
+}
+
+impl<ST, Expr, QS> SelectableAsNullable<Expr, QS> for types::Nullable<ST> where
+    ST: NotNull,
+    Expr: SelectableExpression<QS>,
+{
+}
+
+impl<Expr, QS> SelectableAsNullable<Expr, QS> for () where
+    Expr: SelectableExpression<QS>,
+{
+}
+
 impl<T> NonAggregate for Nullable<T> where
     T: NonAggregate,
     Nullable<T>: Expression,
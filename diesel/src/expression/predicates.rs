@@ -138,13 +138,20 @@ macro_rules! postfix_expression {
 
 infix_predicate!(And, " AND ");
 infix_predicate!(Between, " BETWEEN ");
+infix_predicate!(ContainedBy, " <@ ");
+infix_predicate!(Contains, " @> ");
+infix_predicate!(Distance, " <-> ", ::types::Double);
 infix_predicate!(Eq, " = ");
+infix_predicate!(GetKey, " -> ", ::types::Nullable<::types::Text>);
 infix_predicate!(IsNotDistinctFrom, " IS NOT DISTINCT FROM ");
 infix_predicate!(Gt, " > ");
 infix_predicate!(GtEq, " >= ");
+infix_predicate!(HasKey, " ? ");
 infix_predicate!(Like, " LIKE ");
+infix_predicate!(LqueryMatches, " ~ ");
 infix_predicate!(Lt, " < ");
 infix_predicate!(LtEq, " <= ");
+infix_predicate!(Matches, " @@ ");
 infix_predicate!(NotBetween, " NOT BETWEEN ");
 infix_predicate!(NotEq, " != ");
 infix_predicate!(NotLike, " NOT LIKE ");
@@ -8,6 +8,9 @@ use types::HasSqlType;
 /// Available for when you truly cannot represent something using the expression
 /// DSL. You will need to provide the type of the expression, in addition to the
 /// SQL. The compiler will be unable to verify the correctness of this type.
+///
+/// A complete SQL statement can also be used as a query, and loaded like any
+/// other query.
 pub struct SqlLiteral<ST> {
     sql: String,
     _marker: PhantomData<ST>,
@@ -26,6 +29,10 @@ impl<ST> Expression for SqlLiteral<ST> {
     type SqlType = ST;
 }
 
+impl<ST> Query for SqlLiteral<ST> {
+    type SqlType = ST;
+}
+
 impl<ST, DB> QueryFragment<DB> for SqlLiteral<ST> where
     DB: Backend + HasSqlType<ST>,
 {
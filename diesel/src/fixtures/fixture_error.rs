@@ -0,0 +1,68 @@
+use result::{self, TransactionError};
+
+use std::convert::From;
+use std::{fmt, io};
+use std::error::Error;
+
+use self::FixtureError::*;
+
+#[derive(Debug)]
+pub enum FixtureError {
+    FixtureDirectoryNotFound,
+    IoError(io::Error),
+    QueryError(result::Error),
+    CircularDependency(Vec<String>),
+    InvalidFixture(String),
+}
+
+impl Error for FixtureError {
+    fn description(&self) -> &str {
+        match *self {
+            FixtureDirectoryNotFound => "Unable to find fixtures directory in this directory or any parent directories.",
+            IoError(ref error) => error.description(),
+            QueryError(ref error) => error.description(),
+            CircularDependency(_) => "Unable to determine the order to load fixtures in, as the tables have circular foreign keys.",
+            InvalidFixture(ref message) => &message[..],
+        }
+    }
+}
+
+impl fmt::Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.description().fmt(f)
+    }
+}
+
+impl PartialEq for FixtureError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (&FixtureDirectoryNotFound, &FixtureDirectoryNotFound) => true,
+            (&QueryError(ref e1), &QueryError(ref e2)) => e1 == e2,
+            (&CircularDependency(ref t1), &CircularDependency(ref t2)) => t1 == t2,
+            (&InvalidFixture(ref m1), &InvalidFixture(ref m2)) => m1 == m2,
+            _ => false
+        }
+    }
+}
+
+impl From<io::Error> for FixtureError {
+    fn from(e: io::Error) -> Self {
+        IoError(e)
+    }
+}
+
+impl From<result::Error> for FixtureError {
+    fn from(e: result::Error) -> Self {
+        QueryError(e)
+    }
+}
+
+impl From<TransactionError<FixtureError>> for FixtureError {
+    fn from(e: TransactionError<FixtureError>) -> Self {
+        use result::TransactionError::*;
+        match e {
+            CouldntCreateTransaction(e) => FixtureError::from(e),
+            UserReturnedError(e) => e,
+        }
+    }
+}
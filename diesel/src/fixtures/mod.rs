@@ -0,0 +1,294 @@
+//! Provides functions for loading seed data into the database in tests.
+//!
+//! Fixtures should be placed in a `/fixtures` directory at the root of your project (the same
+//! directory as `Cargo.toml`). As with migrations, Diesel will search for the fixtures directory
+//! in the current directory and its parents.
+//!
+//! Each fixture is a file named after the table it populates, and can be one of:
+//!
+//! - `{table_name}.sql`, which is run as is.
+//! - `{table_name}.json`, an array of objects mapping column names to values. Requires the
+//!   `serde_json` feature.
+//! - `{table_name}.toml`, an array of tables named `rows`, each mapping column names to values.
+//!   Requires the `toml` feature.
+//!
+//! Files are loaded in an order which satisfies the foreign keys between the tables, so a fixture
+//! for `posts` will always be loaded after the fixture for `users` if `posts` references `users`.
+//! Files with any other extension, or beginning with `.`, are ignored. Having more than one
+//! fixture for the same table is an error.
+//!
+//! All fixtures are loaded inside of a single transaction. When called inside of a test
+//! transaction, the data will be rolled back along with everything else at the end of the test.
+//!
+//! ## Example
+//!
+//! ```text
+//! # Directory Structure
+//! - fixtures
+//!     - posts.json
+//!     - users.sql
+//! ```
+//!
+//! ```sql
+//! -- fixtures/users.sql
+//! INSERT INTO users (id, name) VALUES (1, 'Sean'), (2, 'Tess');
+//! ```
+//!
+//! ```json
+//! // fixtures/posts.json
+//! [
+//!     { "user_id": 1, "title": "Hello" },
+//!     { "user_id": 2, "title": "World", "body": null }
+//! ]
+//! ```
+//!
+//! ```ignore
+//! let connection = PgConnection::establish(&database_url).unwrap();
+//! connection.begin_test_transaction().unwrap();
+//! fixtures::load_fixtures(&connection).unwrap();
+//! ```
+mod fixture_error;
+mod rows;
+
+pub use self::fixture_error::*;
+
+use backend::Pg;
+use expression::dsl::sql;
+use query_builder::functions::select;
+use query_dsl::*;
+use types::VarChar;
+use Connection;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::path::{PathBuf, Path};
+
+/// Loads every fixture in the fixtures directory. See the [module level
+/// documentation](index.html) for information on how fixtures should be structured, and where
+/// Diesel will look for them by default.
+pub fn load_fixtures<Conn>(conn: &Conn) -> Result<(), FixtureError> where
+    Conn: Connection<Backend=Pg>,
+{
+    let fixtures_dir = try!(find_fixtures_directory());
+    load_fixtures_in_directory(conn, &fixtures_dir)
+}
+
+/// Loads every fixture in the given directory, in an order which satisfies the foreign keys
+/// between the tables being loaded. Returns `FixtureError::CircularDependency` if no such order
+/// exists.
+pub fn load_fixtures_in_directory<Conn>(conn: &Conn, path: &Path) -> Result<(), FixtureError> where
+    Conn: Connection<Backend=Pg>,
+{
+    let fixtures = try!(fixtures_in_directory(path));
+    conn.transaction(|| {
+        let foreign_keys = try!(foreign_keys(conn));
+        let tables = fixtures.keys().cloned().collect();
+        for table in try!(load_order(tables, &foreign_keys)) {
+            try!(load_fixture(conn, &table, &fixtures[&table]));
+        }
+        Ok(())
+    }).map_err(|e| e.into())
+}
+
+fn fixtures_in_directory(path: &Path) -> Result<BTreeMap<String, PathBuf>, FixtureError> {
+    let mut fixtures = BTreeMap::new();
+    for entry in try!(path.read_dir()) {
+        let path = try!(entry).path();
+        let is_fixture = path.extension().and_then(|ext| ext.to_str())
+            .map(|ext| ext == "sql" || ext == "json" || ext == "toml")
+            .unwrap_or(false);
+        let table_name = path.file_stem().and_then(|stem| stem.to_str())
+            .map(|stem| stem.to_string());
+        match table_name {
+            Some(name) => if is_fixture && !name.starts_with(".") {
+                if let Some(other) = fixtures.insert(name.clone(), path.clone()) {
+                    return Err(FixtureError::InvalidFixture(format!(
+                        "Found more than one fixture for `{}`: {} and {}",
+                        name, other.display(), path.display())));
+                }
+            },
+            None => {},
+        }
+    }
+    Ok(fixtures)
+}
+
+fn foreign_keys<Conn>(conn: &Conn) -> Result<Vec<(String, String)>, FixtureError> where
+    Conn: Connection<Backend=Pg>,
+{
+    let query = select(sql::<(VarChar, VarChar)>(
+        "source.relname::varchar, target.relname::varchar
+         FROM pg_constraint
+         INNER JOIN pg_class source ON source.oid = pg_constraint.conrelid
+         INNER JOIN pg_class target ON target.oid = pg_constraint.confrelid
+         WHERE pg_constraint.contype = 'f'"));
+    let keys = try!(query.load(conn));
+    Ok(keys.collect())
+}
+
+/// Orders the given tables so that every table comes after the tables it references. Tables
+/// which don't depend on each other are ordered by name, so the result is deterministic.
+fn load_order(tables: BTreeSet<String>, foreign_keys: &[(String, String)])
+    -> Result<Vec<String>, FixtureError>
+{
+    let mut dependencies = tables.iter()
+        .map(|table| (table.clone(), BTreeSet::new()))
+        .collect::<BTreeMap<_, _>>();
+    for &(ref source, ref target) in foreign_keys {
+        if source != target && tables.contains(target) {
+            if let Some(deps) = dependencies.get_mut(source) {
+                deps.insert(target.clone());
+            }
+        }
+    }
+
+    let mut ordered = Vec::with_capacity(tables.len());
+    while !dependencies.is_empty() {
+        let next = dependencies.iter()
+            .find(|&(_, deps)| deps.is_empty())
+            .map(|(table, _)| table.clone());
+        match next {
+            Some(table) => {
+                dependencies.remove(&table);
+                for deps in dependencies.values_mut() {
+                    deps.remove(&table);
+                }
+                ordered.push(table);
+            }
+            None => return Err(FixtureError::CircularDependency(
+                dependencies.keys().cloned().collect())),
+        }
+    }
+    Ok(ordered)
+}
+
+fn load_fixture<Conn: Connection>(conn: &Conn, table: &str, path: &Path)
+    -> Result<(), FixtureError>
+{
+    let mut source = String::new();
+    let mut file = try!(File::open(path));
+    try!(file.read_to_string(&mut source));
+    let sql = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => rows::insert_statements(table, &try!(rows::rows_from_json(&source))),
+        Some("toml") => rows::insert_statements(table, &try!(rows::rows_from_toml(&source))),
+        _ => source,
+    };
+    try!(conn.batch_execute(&sql));
+    Ok(())
+}
+
+/// Returns the directory containing fixtures. Will look at for $PWD/fixtures. If it is not found,
+/// it will search the parents of the current directory, until it reaches the root directory.
+/// Returns `FixtureError::FixtureDirectoryNotFound` if no directory is found.
+pub fn find_fixtures_directory() -> Result<PathBuf, FixtureError> {
+    search_for_fixtures_directory(&try!(env::current_dir()))
+}
+
+fn search_for_fixtures_directory(path: &Path) -> Result<PathBuf, FixtureError> {
+    let fixtures_path = path.join("fixtures");
+    if fixtures_path.is_dir() {
+        Ok(fixtures_path)
+    } else {
+        path.parent().map(search_for_fixtures_directory)
+            .unwrap_or(Err(FixtureError::FixtureDirectoryNotFound))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate tempdir;
+
+    use super::*;
+    use super::{fixtures_in_directory, load_order, search_for_fixtures_directory};
+
+    use self::tempdir::TempDir;
+    use std::collections::BTreeSet;
+    use std::fs;
+
+    fn tables(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn key(source: &str, target: &str) -> (String, String) {
+        (source.to_string(), target.to_string())
+    }
+
+    #[test]
+    fn fixture_directory_not_found_if_no_fixture_dir_exists() {
+        let dir = TempDir::new("diesel").unwrap();
+
+        assert_eq!(Err(FixtureError::FixtureDirectoryNotFound),
+            search_for_fixtures_directory(dir.path()));
+    }
+
+    #[test]
+    fn fixture_directory_checks_parents() {
+        let dir = TempDir::new("diesel").unwrap();
+        let temp_path = dir.path().canonicalize().unwrap();
+        let fixtures_path = temp_path.join("fixtures");
+        let child_path = temp_path.join("child");
+
+        fs::create_dir(&child_path).unwrap();
+        fs::create_dir(&fixtures_path).unwrap();
+
+        assert_eq!(Ok(fixtures_path), search_for_fixtures_directory(&child_path));
+    }
+
+    #[test]
+    fn only_sql_json_and_toml_files_are_fixtures() {
+        let dir = TempDir::new("diesel").unwrap();
+
+        fs::File::create(dir.path().join("users.sql")).unwrap();
+        fs::File::create(dir.path().join("posts.json")).unwrap();
+        fs::File::create(dir.path().join("comments.toml")).unwrap();
+        fs::File::create(dir.path().join("README")).unwrap();
+        fs::File::create(dir.path().join(".tags.sql")).unwrap();
+
+        let fixtures = fixtures_in_directory(dir.path()).unwrap();
+        let names = fixtures.keys().cloned().collect::<Vec<_>>();
+        assert_eq!(vec!["comments".to_string(), "posts".to_string(), "users".to_string()], names);
+    }
+
+    #[test]
+    fn more_than_one_fixture_for_a_table_is_an_error() {
+        let dir = TempDir::new("diesel").unwrap();
+
+        fs::File::create(dir.path().join("users.sql")).unwrap();
+        fs::File::create(dir.path().join("users.json")).unwrap();
+
+        assert!(fixtures_in_directory(dir.path()).is_err());
+    }
+
+    #[test]
+    fn unrelated_tables_are_loaded_in_alphabetical_order() {
+        let order = load_order(tables(&["users", "comments", "posts"]), &[]);
+
+        assert_eq!(Ok(vec!["comments".into(), "posts".into(), "users".into()]), order);
+    }
+
+    #[test]
+    fn referenced_tables_are_loaded_first() {
+        let keys = vec![key("comments", "posts"), key("posts", "users")];
+        let order = load_order(tables(&["users", "comments", "posts"]), &keys);
+
+        assert_eq!(Ok(vec!["users".into(), "posts".into(), "comments".into()]), order);
+    }
+
+    #[test]
+    fn keys_to_tables_without_fixtures_and_self_references_are_ignored() {
+        let keys = vec![key("posts", "users"), key("posts", "posts")];
+        let order = load_order(tables(&["posts"]), &keys);
+
+        assert_eq!(Ok(vec!["posts".into()]), order);
+    }
+
+    #[test]
+    fn circular_foreign_keys_are_an_error() {
+        let keys = vec![key("posts", "users"), key("users", "posts")];
+        let order = load_order(tables(&["users", "posts", "comments"]), &keys);
+
+        assert_eq!(Err(FixtureError::CircularDependency(vec!["posts".into(), "users".into()])), order);
+    }
+}
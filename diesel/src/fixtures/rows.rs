@@ -0,0 +1,165 @@
+//! JSON and TOML fixtures are a list of rows, each of which maps column names to values. Each
+//! row is loaded with its own `INSERT` statement, so rows don't need to set the same columns.
+use super::FixtureError;
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(String),
+    Text(String),
+}
+
+pub type Row = BTreeMap<String, Value>;
+
+pub fn insert_statements(table: &str, rows: &[Row]) -> String {
+    let mut sql = String::new();
+    for row in rows {
+        let columns = row.keys().map(|c| quote_identifier(c)).collect::<Vec<_>>();
+        let values = row.values().map(literal).collect::<Vec<_>>();
+        if row.is_empty() {
+            sql.push_str(&format!("INSERT INTO {} DEFAULT VALUES;\n", quote_identifier(table)));
+        } else {
+            sql.push_str(&format!("INSERT INTO {} ({}) VALUES ({});\n",
+                quote_identifier(table), columns.join(", "), values.join(", ")));
+        }
+    }
+    sql
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace("\"", "\"\""))
+}
+
+fn literal(value: &Value) -> String {
+    match *value {
+        Value::Null => "NULL".into(),
+        Value::Bool(true) => "TRUE".into(),
+        Value::Bool(false) => "FALSE".into(),
+        Value::Number(ref n) => n.clone(),
+        Value::Text(ref s) => format!("'{}'", s.replace("'", "''")),
+    }
+}
+
+/// A JSON fixture is an array of objects. Nested arrays and objects are sent as their JSON text,
+/// so they can be loaded into `json` and `jsonb` columns.
+#[cfg(feature = "serde_json")]
+pub fn rows_from_json(source: &str) -> Result<Vec<Row>, FixtureError> {
+    extern crate serde_json;
+
+    use self::serde_json::Value as Json;
+
+    let rows = match try!(serde_json::from_str(source).map_err(invalid)) {
+        Json::Array(rows) => rows,
+        _ => return Err(FixtureError::InvalidFixture("Expected an array of rows".into())),
+    };
+    rows.into_iter().map(|row| match row {
+        Json::Object(columns) => Ok(columns.into_iter().map(|(column, value)| {
+            let value = match value {
+                Json::Null => Value::Null,
+                Json::Bool(b) => Value::Bool(b),
+                Json::Number(n) => Value::Number(n.to_string()),
+                Json::String(s) => Value::Text(s),
+                nested => Value::Text(nested.to_string()),
+            };
+            (column, value)
+        }).collect()),
+        _ => Err(FixtureError::InvalidFixture("Expected each row to be an object".into())),
+    }).collect()
+}
+
+#[cfg(not(feature = "serde_json"))]
+pub fn rows_from_json(_: &str) -> Result<Vec<Row>, FixtureError> {
+    Err(FixtureError::InvalidFixture("JSON fixtures require the `serde_json` feature".into()))
+}
+
+/// A TOML fixture is an array of tables named `rows`. Dates and times are sent as text, and
+/// nested arrays and tables are sent as their TOML text.
+#[cfg(feature = "toml")]
+pub fn rows_from_toml(source: &str) -> Result<Vec<Row>, FixtureError> {
+    extern crate toml;
+
+    use self::toml::Value as Toml;
+
+    let mut document = match try!(source.parse::<Toml>().map_err(invalid)) {
+        Toml::Table(document) => document,
+        _ => return Err(FixtureError::InvalidFixture("Expected a TOML document".into())),
+    };
+    let rows = match document.remove("rows") {
+        Some(Toml::Array(rows)) => rows,
+        None => Vec::new(),
+        _ => return Err(FixtureError::InvalidFixture("Expected `rows` to be an array of tables".into())),
+    };
+    rows.into_iter().map(|row| match row {
+        Toml::Table(columns) => Ok(columns.into_iter().map(|(column, value)| {
+            let value = match value {
+                Toml::Boolean(b) => Value::Bool(b),
+                Toml::Integer(i) => Value::Number(i.to_string()),
+                Toml::Float(f) => Value::Number(f.to_string()),
+                Toml::String(s) => Value::Text(s),
+                Toml::Datetime(d) => Value::Text(d.to_string()),
+                nested => Value::Text(nested.to_string()),
+            };
+            (column, value)
+        }).collect()),
+        _ => Err(FixtureError::InvalidFixture("Expected each row to be a table".into())),
+    }).collect()
+}
+
+#[cfg(not(feature = "toml"))]
+pub fn rows_from_toml(_: &str) -> Result<Vec<Row>, FixtureError> {
+    Err(FixtureError::InvalidFixture("TOML fixtures require the `toml` feature".into()))
+}
+
+#[cfg(any(feature = "serde_json", feature = "toml"))]
+fn invalid<E: ::std::fmt::Display>(e: E) -> FixtureError {
+    FixtureError::InvalidFixture(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(columns: &[(&str, Value)]) -> Row {
+        columns.iter().map(|&(ref c, ref v)| (c.to_string(), v.clone())).collect()
+    }
+
+    #[test]
+    fn each_row_is_inserted_separately_with_quoted_values() {
+        let rows = vec![
+            row(&[("id", Value::Number("1".into())), ("name", Value::Text("Sean's".into()))]),
+            row(&[("hair_color", Value::Null), ("admin", Value::Bool(true))]),
+        ];
+
+        assert_eq!(
+            "INSERT INTO \"users\" (\"id\", \"name\") VALUES (1, 'Sean''s');\n\
+             INSERT INTO \"users\" (\"admin\", \"hair_color\") VALUES (TRUE, NULL);\n",
+            insert_statements("users", &rows));
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn json_fixtures_are_an_array_of_objects() {
+        let rows = rows_from_json(r#"[{"id": 1, "name": "Sean", "tags": ["a"]}]"#).unwrap();
+
+        assert_eq!(vec![row(&[
+            ("id", Value::Number("1".into())),
+            ("name", Value::Text("Sean".into())),
+            ("tags", Value::Text("[\"a\"]".into())),
+        ])], rows);
+        assert!(rows_from_json(r#"{"id": 1}"#).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn toml_fixtures_are_an_array_of_rows() {
+        let rows = rows_from_toml("[[rows]]\nid = 1\nname = \"Sean\"\n\n[[rows]]\nid = 2\n").unwrap();
+
+        assert_eq!(vec![
+            row(&[("id", Value::Number("1".into())), ("name", Value::Text("Sean".into()))]),
+            row(&[("id", Value::Number("2".into()))]),
+        ], rows);
+    }
+}
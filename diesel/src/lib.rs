@@ -5,6 +5,7 @@
 #![cfg_attr(feature = "unstable", feature(time2))]
 pub mod backend;
 pub mod connection;
+#[macro_use]
 pub mod expression;
 #[doc(hidden)]
 pub mod persistable;
@@ -13,7 +14,10 @@ pub mod types;
 #[macro_use]
 mod macros;
 
+#[cfg(feature = "postgres")]
 mod db_result;
+#[cfg(feature = "postgres")]
+pub mod fixtures;
 pub mod migrations;
 pub mod query_builder;
 mod query_dsl;
@@ -21,6 +25,8 @@ pub mod query_source;
 pub mod result;
 #[doc(hidden)]
 pub mod row;
+#[cfg(all(test, feature = "postgres"))]
+mod test_helpers;
 
 pub mod helper_types {
     //! Provide helper types for concisely writing the return type of functions.
@@ -34,7 +40,9 @@ pub mod helper_types {
     //! `users.filter(first_name.eq("John")).order(last_name.asc()).limit(10)` would
     //! be `Limit<Order<FindBy<users, first_name, &str>, Asc<last_name>>>`
     use super::query_dsl::*;
-    use super::expression::helper_types::Eq;
+    use super::expression::count::CountStar;
+    use super::expression::helper_types::{Eq, EqAny};
+    use super::query_source::{InnerJoinSource, JoinTarget, LeftOuterJoinSource, OnClauseWrapper, Table};
 
     /// Represents the return type of `.select(selection)`
     pub type Select<Source, Selection, Type = <Selection as super::Expression>::SqlType> =
@@ -48,10 +56,38 @@ pub mod helper_types {
     pub type FindBy<Source, Column, Value> =
         Filter<Source, Eq<Column, Value>>;
 
+    /// Represents the return type of `.filter(lhs.eq_any(rhs))`
+    pub type FindAllBy<Source, Column, Values> =
+        Filter<Source, EqAny<Column, Values>>;
+
+    /// Represents the return type of `.find(primary_key)`
+    pub type Find<Source, PK> = FindBy<Source, <Source as Table>::PrimaryKey, PK>;
+
+    /// Represents the return type of `.count()`
+    pub type Count<Source> = Select<Source, CountStar>;
+
+    /// Represents the return type of `Child::belonging_to(&parent)`
+    pub type BelongingTo<Child, Parent> = <Child as BelongingToDsl<Parent>>::Output;
+
+    /// Represents the return type of `.inner_join(other)`
+    pub type InnerJoin<Left, Right> =
+        InnerJoinSource<Left, <Right as JoinTarget>::Table, <Right as JoinTarget>::On>;
+
+    /// Represents the return type of `.left_outer_join(other)`
+    pub type LeftOuterJoin<Left, Right> =
+        LeftOuterJoinSource<Left, <Right as JoinTarget>::Table, <Right as JoinTarget>::On>;
+
+    /// Represents the return type of `.on(condition)`
+    pub type On<Source, Condition> = OnClauseWrapper<Source, Condition>;
+
     /// Represents the return type of `.order(ordering)`
     pub type Order<Source, Ordering> =
         <Source as OrderDsl<Ordering>>::Output;
 
+    /// Represents the return type of `.distinct_on(expr)`
+    pub type DistinctOn<Source, Expr> =
+        <Source as DistinctOnDsl<Expr>>::Output;
+
     /// Represents the return type of `.limit()`
     pub type Limit<Source> = <Source as LimitDsl>::Output;
 
@@ -64,19 +100,27 @@ pub mod helper_types {
 
 pub mod prelude {
     //! Re-exports important traits and types. Meant to be glob imported when using Diesel.
-    pub use expression::{Expression, SelectableExpression, BoxableExpression};
+    //!
+    //! `use diesel::prelude::*;` brings in everything needed to connect, build
+    //! and run queries, and to implement the traits generated by
+    //! `diesel_codegen`, without referring to the modules they are defined in.
+    pub use connection::Connection;
+    #[cfg(feature = "postgres")]
+    pub use connection::PgConnection;
+    pub use expression::{Expression, SelectableExpression, BoxableExpression, AsExpression};
     pub use expression::expression_methods::*;
     #[doc(inline)]
     pub use persistable::Insertable;
+    #[doc(inline)]
+    pub use query_builder::AsChangeset;
+    #[doc(inline)]
+    pub use query_builder::functions::{insert, update, delete, select};
     pub use query_dsl::*;
     pub use query_source::{QuerySource, Queryable, Table, Column, JoinTo};
     pub use result::{QueryResult, TransactionError, TransactionResult, ConnectionError, ConnectionResult, OptionalExtension};
 }
 
-pub use connection::Connection;
 pub use prelude::*;
-#[doc(inline)]
-pub use query_builder::functions::{insert, update, delete, select};
 pub use result::Error::NotFound;
 #[doc(inline)]
 pub use types::structs::data_types;
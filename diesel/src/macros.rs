@@ -328,28 +328,28 @@ macro_rules! select_column_workaround {
 #[doc(hidden)]
 macro_rules! select_column_inner {
     ($parent:ident -> $child:ident $column_name:ident) => {
-        impl $crate::expression::SelectableExpression<
-            $crate::query_source::InnerJoinSource<$child::table, $parent::table>,
+        impl<On> $crate::expression::SelectableExpression<
+            $crate::query_source::InnerJoinSource<$child::table, $parent::table, On>,
         > for $parent::$column_name
         {
         }
 
-        impl $crate::expression::SelectableExpression<
-            $crate::query_source::InnerJoinSource<$parent::table, $child::table>,
+        impl<On> $crate::expression::SelectableExpression<
+            $crate::query_source::InnerJoinSource<$parent::table, $child::table, On>,
         > for $parent::$column_name
         {
         }
 
-        impl $crate::expression::SelectableExpression<
-            $crate::query_source::LeftOuterJoinSource<$child::table, $parent::table>,
+        impl<On> $crate::expression::SelectableExpression<
+            $crate::query_source::LeftOuterJoinSource<$child::table, $parent::table, On>,
             <<$parent::$column_name as $crate::Expression>::SqlType
                 as $crate::types::IntoNullable>::Nullable,
         > for $parent::$column_name
         {
         }
 
-        impl $crate::expression::SelectableExpression<
-            $crate::query_source::LeftOuterJoinSource<$parent::table, $child::table>,
+        impl<On> $crate::expression::SelectableExpression<
+            $crate::query_source::LeftOuterJoinSource<$parent::table, $child::table, On>,
         > for $parent::$column_name
         {
         }
@@ -382,6 +382,11 @@ macro_rules! join_through {
 /// Takes a query QueryFragment expression as an argument and returns a string
 /// of SQL with placeholders for the dynamic values.
 ///
+/// A backend can be given as a second argument, to get the SQL exactly as it
+/// would be sent to that backend, such as `debug_sql!(users.count(), Pg)`. This
+/// does not require a connection, so it can be used to generate SQL when Diesel
+/// is compiled without any database drivers.
+///
 /// # Example
 ///
 /// ### Returning SQL from a count statment:
@@ -405,6 +410,14 @@ macro_rules! debug_sql {
         QueryFragment::<$crate::backend::Debug>::to_sql(&$query, &mut query_builder).unwrap();
         query_builder.sql
     }};
+
+    ($query:expr, $backend:ty) => {{
+        use $crate::backend::Backend;
+        use $crate::query_builder::QueryFragment;
+        let mut query_builder = <<$backend as Backend>::QueryBuilder as Default>::default();
+        QueryFragment::<$backend>::to_sql(&$query, &mut query_builder).unwrap();
+        query_builder.sql
+    }};
 }
 
 /// Takes takes a query QueryFragment expression as an argument and prints out
@@ -429,3 +442,109 @@ macro_rules! print_sql {
         println!("{}", &debug_sql!($query));
     };
 }
+
+/// Declares a SQL type for a PostgreSQL enum, created with `CREATE TYPE ... AS
+/// ENUM`. PostgreSQL sends the values of an enum as text, so an enum which
+/// derives `DieselTextEnum` can be used with the declared type, and `String`
+/// values can be loaded from it.
+///
+/// The OID of the enum is not known until runtime. Until `register` is called,
+/// values are sent with an OID of 0, which lets PostgreSQL infer the type from
+/// the query. Arrays of the enum can be loaded, but can only be sent once
+/// `register` has looked up the OIDs of the enum and its array type. The OIDs
+/// are shared by every connection, so `register` only needs to be called once.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate diesel;
+/// # use diesel::prelude::*;
+/// pg_enum_type!(PostStatusType, "post_status");
+///
+/// # fn main() {
+/// # let connection = PgConnection::establish("postgres://localhost").unwrap();
+/// PostStatusType::register(&connection).unwrap();
+/// # }
+/// ```
+#[macro_export]
+macro_rules! pg_enum_type {
+    ($name:ident, $type_name:expr) => {
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $name;
+
+        impl $name {
+            /// Looks up the OIDs of this enum and its array type, so that
+            /// arrays of it can be sent as bind parameters. The lookup queries
+            /// `pg_type`, so `conn` must be connected to PostgreSQL.
+            pub fn register<Conn>(conn: &Conn) -> $crate::result::QueryResult<()> where
+                Conn: $crate::connection::Connection,
+            {
+                use $crate::prelude::*;
+                use $crate::types::BigInt;
+                use ::std::sync::atomic::Ordering;
+
+                let query = format!("SELECT oid::int8, typarray::int8 FROM pg_type WHERE oid = '{}'::regtype",
+                                    ($type_name as &str).replace("'", "''"));
+                let (oid, array_oid) = try!($crate::expression::dsl::sql::<(BigInt, BigInt)>(&query)
+                    .get_result::<(i64, i64)>(conn));
+                let oids = Self::registered_oids();
+                oids.0.store(oid as usize, Ordering::SeqCst);
+                oids.1.store(array_oid as usize, Ordering::SeqCst);
+                Ok(())
+            }
+
+            fn registered_oids() -> &'static (::std::sync::atomic::AtomicUsize, ::std::sync::atomic::AtomicUsize) {
+                use ::std::sync::atomic::{AtomicUsize, ATOMIC_USIZE_INIT};
+
+                static OIDS: (AtomicUsize, AtomicUsize) = (ATOMIC_USIZE_INIT, ATOMIC_USIZE_INIT);
+                &OIDS
+            }
+        }
+
+        impl $crate::types::HasSqlType<$name> for $crate::backend::Pg {
+            fn metadata() -> $crate::backend::PgTypeMetadata {
+                use ::std::sync::atomic::Ordering;
+
+                let oids = $name::registered_oids();
+                $crate::backend::PgTypeMetadata {
+                    oid: oids.0.load(Ordering::SeqCst) as u32,
+                    array_oid: oids.1.load(Ordering::SeqCst) as u32,
+                }
+            }
+        }
+
+        impl $crate::types::HasSqlType<$name> for $crate::backend::Debug {
+            fn metadata() {}
+        }
+
+        impl $crate::types::NotNull for $name {
+        }
+
+        impl<Expr, QS> $crate::expression::nullable::SelectableAsNullable<Expr, QS> for $name where
+            Expr: $crate::expression::SelectableExpression<QS>,
+        {
+        }
+
+        impl $crate::types::FromSql<$name, $crate::backend::Pg> for String {
+            fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<::std::error::Error>> {
+                $crate::types::FromSql::<$crate::types::Text, $crate::backend::Pg>::from_sql(bytes)
+            }
+        }
+
+        impl<'a> $crate::types::ToSql<$name, $crate::backend::Pg> for &'a str {
+            fn to_sql<W: ::std::io::Write>(&self, out: &mut W)
+                -> Result<$crate::types::IsNull, Box<::std::error::Error>>
+            {
+                $crate::types::ToSql::<$crate::types::Text, $crate::backend::Pg>::to_sql(self, out)
+            }
+        }
+
+        impl<'a> $crate::types::ToSql<$crate::types::Nullable<$name>, $crate::backend::Pg> for &'a str {
+            fn to_sql<W: ::std::io::Write>(&self, out: &mut W)
+                -> Result<$crate::types::IsNull, Box<::std::error::Error>>
+            {
+                $crate::types::ToSql::<$name, $crate::backend::Pg>::to_sql(self, out)
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use ::expression::dsl::sql;
+use ::query_dsl::*;
+use result::TransactionError;
+use super::{compare_versions, find_migrations_directory, previously_run_migration_versions_if_any};
+use super::{MigrationConfig, MigrationSource, RunMigrationsError};
+use {Connection, Queryable, types};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// A difference between the schema of the database, and the schema which the migrations that have
+/// been run would have produced. See [`schema_drift`](fn.schema_drift.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaDrift {
+    /// A table which is in the database, but isn't created by the migrations.
+    UnexpectedTable(String),
+    /// A table which is created by the migrations, but is not in the database.
+    MissingTable(String),
+    /// A column which is in the database, but isn't created by the migrations.
+    UnexpectedColumn { table: String, column: String },
+    /// A column which is created by the migrations, but is not in the database.
+    MissingColumn { table: String, column: String },
+    /// A column whose type or nullability is different in the database. The types are given as
+    /// PostgreSQL's internal type names, such as `int4` or `varchar NOT NULL`.
+    ChangedColumn { table: String, column: String, expected: String, actual: String },
+    /// An index which is in the database, but isn't created by the migrations.
+    UnexpectedIndex(String),
+    /// An index which is created by the migrations, but is not in the database.
+    MissingIndex(String),
+}
+
+#[doc(hidden)]
+pub type ColumnSqlType = (types::VarChar, types::VarChar, types::VarChar, types::VarChar);
+#[doc(hidden)]
+pub type ColumnRow = (String, String, String, String);
+
+/// The schema which the migrations are run in, to find what they produce.
+const SCRATCH_SCHEMA: &'static str = "__diesel_schema_drift";
+
+/// Compares the tables, columns and indexes in the current schema of the database to the ones
+/// which the migrations that have been run would have produced, and returns the differences. This
+/// catches changes which were made to the database by hand, rather than through a migration.
+///
+/// The migrations are run in a temporary schema, in a transaction which is always rolled back, so
+/// the database isn't changed. The comparison is coarse: constraints other than `NOT NULL`,
+/// defaults, views and functions are not compared, and migrations which create or refer to objects
+/// in a specific schema will give misleading results. Migrations which can't be run in a
+/// transaction can't be checked, and will return an error.
+pub fn schema_drift<Conn>(conn: &Conn) -> Result<Vec<SchemaDrift>, RunMigrationsError> where
+    Conn: Connection,
+    ColumnRow: Queryable<ColumnSqlType, Conn::Backend>,
+{
+    let migrations_dir = try!(find_migrations_directory());
+    schema_drift_in_directory(conn, &migrations_dir)
+}
+
+/// Compares the schema of the database to the one produced by the migrations in the given
+/// directory. See [`schema_drift`](fn.schema_drift.html).
+pub fn schema_drift_in_directory<Conn>(conn: &Conn, migrations_dir: &Path)
+    -> Result<Vec<SchemaDrift>, RunMigrationsError> where
+        Conn: Connection,
+        ColumnRow: Queryable<ColumnSqlType, Conn::Backend>,
+{
+    let mut drift = None;
+    let result = conn.transaction::<(), _, _>(|| {
+        drift = Some(compare_to_migrations(conn, migrations_dir));
+        Err(())
+    });
+    match (result, drift) {
+        (Err(TransactionError::CouldntCreateTransaction(e)), _) => Err(e.into()),
+        (_, Some(drift)) => drift,
+        (_, None) => unreachable!("The transaction always runs the function when it is created"),
+    }
+}
+
+fn compare_to_migrations<Conn>(conn: &Conn, migrations_dir: &Path)
+    -> Result<Vec<SchemaDrift>, RunMigrationsError> where
+        Conn: Connection,
+        ColumnRow: Queryable<ColumnSqlType, Conn::Backend>,
+{
+    let config = MigrationConfig::default();
+    let current_schema = try!(sql::<types::VarChar>("SELECT current_schema()::varchar")
+        .get_result::<String>(conn));
+    let already_run = try!(previously_run_migration_versions_if_any(conn, &config));
+    let mut migrations = try!(migrations_dir.migrations()).into_iter()
+        .filter(|m| already_run.contains(m.version()))
+        .collect::<Vec<_>>();
+    migrations.sort_by(|a, b| compare_versions(a.version(), b.version()));
+
+    let actual = try!(SchemaSnapshot::load(conn, &current_schema));
+    try!(conn.batch_execute(&format!("CREATE SCHEMA {0}; SET LOCAL search_path TO {0}", SCRATCH_SCHEMA)));
+    for migration in migrations {
+        try!(migration.run(conn).map_err(|e| e.for_migration(migration.version())));
+    }
+    let expected = try!(SchemaSnapshot::load(conn, SCRATCH_SCHEMA));
+    Ok(expected.compare(&actual, &[&config.table_name, &MigrationConfig::seeds().table_name]))
+}
+
+struct SchemaSnapshot {
+    tables: BTreeMap<String, BTreeMap<String, String>>,
+    indexes: BTreeSet<String>,
+}
+
+impl SchemaSnapshot {
+    fn load<Conn>(conn: &Conn, schema: &str) -> Result<Self, RunMigrationsError> where
+        Conn: Connection,
+        ColumnRow: Queryable<ColumnSqlType, Conn::Backend>,
+    {
+        let schema = schema.replace("'", "''");
+        let tables_query = format!("SELECT table_name::varchar FROM information_schema.tables \
+                                    WHERE table_schema = '{}' AND table_type = 'BASE TABLE'", schema);
+        let mut tables = try!(sql::<types::VarChar>(&tables_query).load::<String>(conn))
+            .map(|table| (table, BTreeMap::new()))
+            .collect::<BTreeMap<_, _>>();
+
+        let columns_query = format!("SELECT table_name::varchar, column_name::varchar, \
+                                     udt_name::varchar, is_nullable::varchar \
+                                     FROM information_schema.columns WHERE table_schema = '{}'", schema);
+        let columns = try!(sql::<ColumnSqlType>(&columns_query).load::<ColumnRow>(conn));
+        for (table, column, type_name, is_nullable) in columns {
+            if let Some(columns) = tables.get_mut(&table) {
+                let description = if is_nullable == "YES" {
+                    type_name
+                } else {
+                    format!("{} NOT NULL", type_name)
+                };
+                columns.insert(column, description);
+            }
+        }
+
+        let indexes_query = format!("SELECT indexname::varchar FROM pg_indexes WHERE schemaname = '{}'", schema);
+        let indexes = try!(sql::<types::VarChar>(&indexes_query).load::<String>(conn)).collect();
+        Ok(SchemaSnapshot { tables: tables, indexes: indexes })
+    }
+
+    /// Compares the schema the migrations produced to the schema of the database. Tables in
+    /// `ignored_tables`, such as the table which keeps track of migrations, are only in the
+    /// database, so they are left out.
+    fn compare(&self, actual: &SchemaSnapshot, ignored_tables: &[&str]) -> Vec<SchemaDrift> {
+        let mut drift = Vec::new();
+        for (table, expected_columns) in &self.tables {
+            let actual_columns = match actual.tables.get(table) {
+                Some(columns) => columns,
+                None => {
+                    drift.push(SchemaDrift::MissingTable(table.clone()));
+                    continue;
+                }
+            };
+            for (column, expected) in expected_columns {
+                match actual_columns.get(column) {
+                    None => drift.push(SchemaDrift::MissingColumn {
+                        table: table.clone(),
+                        column: column.clone(),
+                    }),
+                    Some(actual) if actual != expected => drift.push(SchemaDrift::ChangedColumn {
+                        table: table.clone(),
+                        column: column.clone(),
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    }),
+                    Some(_) => {}
+                }
+            }
+            for column in actual_columns.keys().filter(|c| !expected_columns.contains_key(*c)) {
+                drift.push(SchemaDrift::UnexpectedColumn {
+                    table: table.clone(),
+                    column: column.clone(),
+                });
+            }
+        }
+        for table in actual.tables.keys() {
+            if !self.tables.contains_key(table) && !ignored_tables.contains(&&**table) {
+                drift.push(SchemaDrift::UnexpectedTable(table.clone()));
+            }
+        }
+        for index in self.indexes.difference(&actual.indexes) {
+            drift.push(SchemaDrift::MissingIndex(index.clone()));
+        }
+        for index in actual.indexes.difference(&self.indexes) {
+            if !ignored_tables.iter().any(|table| index.starts_with(&format!("{}_", table))) {
+                drift.push(SchemaDrift::UnexpectedIndex(index.clone()));
+            }
+        }
+        drift
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SchemaDrift, SchemaSnapshot};
+
+    use std::collections::{BTreeMap, BTreeSet};
+
+    fn snapshot(tables: &[(&str, &[(&str, &str)])], indexes: &[&str]) -> SchemaSnapshot {
+        SchemaSnapshot {
+            tables: tables.iter().map(|&(table, columns)| {
+                let columns = columns.iter()
+                    .map(|&(column, ty)| (column.to_string(), ty.to_string()))
+                    .collect::<BTreeMap<_, _>>();
+                (table.to_string(), columns)
+            }).collect(),
+            indexes: indexes.iter().map(|i| i.to_string()).collect::<BTreeSet<_>>(),
+        }
+    }
+
+    #[test]
+    fn identical_schemas_have_no_drift() {
+        let expected = snapshot(&[("users", &[("id", "int4 NOT NULL")])], &["users_pkey"]);
+        let actual = snapshot(&[("users", &[("id", "int4 NOT NULL")])], &["users_pkey"]);
+        assert_eq!(Vec::<SchemaDrift>::new(), expected.compare(&actual, &[]));
+    }
+
+    #[test]
+    fn changes_to_tables_columns_and_indexes_are_reported() {
+        let expected = snapshot(&[
+            ("posts", &[("id", "int4 NOT NULL")]),
+            ("users", &[("id", "int4 NOT NULL"), ("name", "varchar NOT NULL"), ("hair_color", "varchar")]),
+        ], &["users_pkey", "users_name"]);
+        let actual = snapshot(&[
+            ("users", &[("id", "int4 NOT NULL"), ("name", "text NOT NULL"), ("age", "int4")]),
+            ("audit_log", &[("id", "int4 NOT NULL")]),
+        ], &["users_pkey", "users_age"]);
+
+        let expected_drift = vec![
+            SchemaDrift::MissingTable("posts".into()),
+            SchemaDrift::MissingColumn { table: "users".into(), column: "hair_color".into() },
+            SchemaDrift::ChangedColumn {
+                table: "users".into(),
+                column: "name".into(),
+                expected: "varchar NOT NULL".into(),
+                actual: "text NOT NULL".into(),
+            },
+            SchemaDrift::UnexpectedColumn { table: "users".into(), column: "age".into() },
+            SchemaDrift::UnexpectedTable("audit_log".into()),
+            SchemaDrift::MissingIndex("users_name".into()),
+            SchemaDrift::UnexpectedIndex("users_age".into()),
+        ];
+        assert_eq!(expected_drift, expected.compare(&actual, &[]));
+    }
+
+    #[test]
+    fn ignored_tables_are_not_unexpected() {
+        let expected = snapshot(&[], &[]);
+        let actual = snapshot(&[("__diesel_schema_migrations", &[("version", "varchar NOT NULL")])],
+                              &["__diesel_schema_migrations_pkey"]);
+        assert_eq!(Vec::<SchemaDrift>::new(), expected.compare(&actual, &["__diesel_schema_migrations"]));
+    }
+}
@@ -0,0 +1,96 @@
+use super::{MigrationError, VersionFormat};
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Creates a new, empty migration called `name` in the given directory, with the current time in
+/// UTC as its version. Returns the migration's directory, which contains an empty `up.sql` and
+/// `down.sql`. This is what `diesel migration generate` does, so build scripts and editor plugins
+/// can create migrations in the same way.
+pub fn generate_migration(migrations_dir: &Path, name: &str) -> Result<PathBuf, MigrationError> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    generate_migration_with_version(migrations_dir, &timestamp_version(now), name)
+}
+
+/// Creates a new, empty migration with the given version and name. See
+/// [`generate_migration`](fn.generate_migration.html). Returns
+/// `MigrationError::InvalidMigrationVersion` if the version isn't numeric.
+pub fn generate_migration_with_version(migrations_dir: &Path, version: &str, name: &str)
+    -> Result<PathBuf, MigrationError>
+{
+    try!(VersionFormat::Any.validate(version));
+    let migration_dir = migrations_dir.join(format!("{}_{}", version, name));
+    try!(fs::create_dir(&migration_dir));
+    try!(File::create(migration_dir.join("up.sql")));
+    try!(File::create(migration_dir.join("down.sql")));
+    Ok(migration_dir)
+}
+
+/// Formats the given number of seconds since the unix epoch as `YYYYMMDDHHMMSS` in UTC.
+fn timestamp_version(seconds_since_epoch: u64) -> String {
+    let days = (seconds_since_epoch / 86400) as i64;
+    let seconds_of_day = seconds_since_epoch % 86400;
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}{:02}{:02}{:02}{:02}{:02}", year, month, day,
+            seconds_of_day / 3600, seconds_of_day / 60 % 60, seconds_of_day % 60)
+}
+
+// Converts days since the unix epoch to a date in the proleptic Gregorian calendar, using
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let day_of_era = z - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate tempdir;
+
+    use super::{generate_migration, generate_migration_with_version, timestamp_version};
+    use super::super::{migration_paths_in_directory, MigrationError, VersionFormat};
+
+    use self::tempdir::TempDir;
+
+    #[test]
+    fn timestamps_are_formatted_in_utc() {
+        assert_eq!("19700101000000", timestamp_version(0));
+        assert_eq!("20160107092901", timestamp_version(1452158941));
+        assert_eq!("20000229000000", timestamp_version(951782400));
+    }
+
+    #[test]
+    fn generated_migrations_can_be_loaded() {
+        let dir = TempDir::new("diesel").unwrap();
+
+        let migration_dir = generate_migration(dir.path(), "create_users").unwrap();
+
+        let migrations = migration_paths_in_directory(dir.path()).unwrap();
+        assert_eq!(1, migrations.len());
+        assert_eq!(migration_dir, migrations[0].1);
+        assert_eq!(Ok(()), VersionFormat::Timestamp.validate(&migrations[0].0));
+        assert!(migration_dir.join("up.sql").is_file());
+        assert!(migration_dir.join("down.sql").is_file());
+    }
+
+    #[test]
+    fn generated_migrations_must_have_a_numeric_version() {
+        let dir = TempDir::new("diesel").unwrap();
+
+        let path = generate_migration_with_version(dir.path(), "0001", "create_users").unwrap();
+        assert_eq!(dir.path().join("0001_create_users"), path);
+
+        let error = MigrationError::InvalidMigrationVersion("first".into());
+        assert_eq!(Err(error), generate_migration_with_version(dir.path(), "first", "create_users"));
+    }
+}
@@ -1,18 +1,108 @@
 use connection::SimpleConnection;
-use super::{MigrationError, RunMigrationsError};
+use data_types::PgTimestamp;
+use super::{MigrationError, MigrationFailure, RunMigrationsError, VersionFormat};
+use super::sql_statements::split_statements;
 
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+/// A single migration. Diesel implements this for each directory in the migrations directory,
+/// but it can also be implemented for migrations written in Rust, which can be run alongside
+/// them with [`run_migrations`](fn.run_migrations.html).
 pub trait Migration {
+    /// The version of the migration. Migrations are run in order of version, and each version
+    /// must be unique.
     fn version(&self) -> &str;
+    /// The name of the migration, which is shown alongside its version when listing migrations.
+    /// For SQL migrations, this is the part of the directory name after the version. This is
+    /// `None` by default.
+    fn name(&self) -> Option<String> {
+        None
+    }
+    /// Applies the migration.
     fn run(&self, conn: &SimpleConnection) -> Result<(), RunMigrationsError>;
+    /// Reverts the migration.
     fn revert(&self, conn: &SimpleConnection) -> Result<(), RunMigrationsError>;
+    /// Returns the SQL which applies the migration, for use in a migration script. Migrations
+    /// which are not written in SQL should use the default, which returns
+    /// `MigrationError::NoSqlForMigration`.
+    fn up_sql(&self) -> Result<String, MigrationError> {
+        Err(MigrationError::NoSqlForMigration(self.version().into()))
+    }
+    /// Whether the migration should be run or reverted inside of a transaction. This is `true`
+    /// by default. SQL migrations can opt out with a `metadata.toml` containing
+    /// `run_in_transaction = false`, for statements such as `CREATE INDEX CONCURRENTLY`.
+    fn run_in_transaction(&self) -> bool {
+        true
+    }
+    /// Whether the migration can be reverted. This is `true` by default. SQL migrations are
+    /// irreversible if their `down.sql` only contains `-- IRREVERSIBLE`, or if they have a
+    /// `metadata.toml` containing `reversible = false`, in which case `down.sql` can be omitted.
+    /// Reverting an irreversible migration returns `MigrationError::Irreversible`.
+    fn reversible(&self) -> bool {
+        true
+    }
+}
+
+/// Information about a single migration, returned by
+/// [`pending_migrations`](fn.pending_migrations.html) and
+/// [`applied_migrations`](fn.applied_migrations.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationInfo {
+    /// The version of the migration.
+    pub version: String,
+    /// The part of the migration's directory name after the version. This is
+    /// `None` if the directory name has nothing after the version, if the
+    /// migration isn't a SQL migration, or if the migration has been run but is
+    /// no longer in the migrations directory.
+    pub name: Option<String>,
+    /// When the migration was run. This is always `None` for pending migrations.
+    pub run_on: Option<PgTimestamp>,
+    /// How long the migration took to run. This is `None` for pending migrations, and for
+    /// migrations which were marked as applied without being run.
+    pub duration: Option<Duration>,
+    /// Who or what ran the migration, as given by
+    /// [`MigrationConfig::run_by`](struct.MigrationConfig.html#structfield.run_by).
+    pub run_by: Option<String>,
+}
+
+/// The status of a single migration, returned by
+/// [`migration_status`](fn.migration_status.html). This covers migrations which are in the
+/// migrations directory, migrations which have been run, and migrations which are both.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    /// The version of the migration.
+    pub version: String,
+    /// The part of the migration's directory name after the version. This is
+    /// `None` if the directory name has nothing after the version, or if the
+    /// migration is not in the migrations directory.
+    pub name: Option<String>,
+    /// When the migration was run, or `None` if it has not been run.
+    pub applied: Option<PgTimestamp>,
+    /// Whether the migration is in the migrations directory. A migration which has
+    /// been run, but is no longer in the directory, cannot be reverted.
+    pub source_present: bool,
 }
 
 pub fn migration_from(path: PathBuf) -> Result<Box<Migration>, MigrationError> {
     if valid_sql_migration_directory(&path) {
         let version = try!(version_from_path(&path));
-        Ok(Box::new(SqlFileMigration(path, version)))
+        try!(VersionFormat::Any.validate(&version));
+        let metadata = try!(metadata_from_path(&path));
+        let down_sql_path = path.join("down.sql");
+        let reversible = if down_sql_path.is_file() {
+            metadata.reversible && !is_irreversible_sql(&try!(read_sql_file(&down_sql_path)))
+        } else if metadata.reversible {
+            return Err(MigrationError::UnknownMigrationFormat(path));
+        } else {
+            false
+        };
+        Ok(Box::new(SqlFileMigration {
+            path: path,
+            version: version,
+            run_in_transaction: metadata.run_in_transaction,
+            reversible: reversible,
+        }))
     } else {
         Err(MigrationError::UnknownMigrationFormat(path))
     }
@@ -21,7 +111,15 @@ pub fn migration_from(path: PathBuf) -> Result<Box<Migration>, MigrationError> {
 fn valid_sql_migration_directory(path: &Path) -> bool {
     let mut files = file_names(path).unwrap_or(Vec::new());
     files.sort();
-    files == ["down.sql".to_string(), "up.sql".to_string()]
+    files == ["down.sql", "up.sql"] || files == ["down.sql", "metadata.toml", "up.sql"] ||
+        files == ["metadata.toml", "up.sql"]
+}
+
+/// A `down.sql` containing only this marker makes a migration irreversible.
+pub const IRREVERSIBLE_MARKER: &'static str = "-- IRREVERSIBLE";
+
+fn is_irreversible_sql(down_sql: &str) -> bool {
+    down_sql.trim() == IRREVERSIBLE_MARKER
 }
 
 fn file_names(path: &Path) -> Result<Vec<String>, MigrationError> {
@@ -46,41 +144,202 @@ fn version_from_path(path: &Path) -> Result<String, MigrationError> {
         })
 }
 
+/// The settings in a migration's `metadata.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationMetadata {
+    pub run_in_transaction: bool,
+    pub reversible: bool,
+}
+
+impl Default for MigrationMetadata {
+    fn default() -> Self {
+        MigrationMetadata {
+            run_in_transaction: true,
+            reversible: true,
+        }
+    }
+}
+
+/// Reads `metadata.toml` from the migration's directory, if it has one. The
+/// supported keys are `run_in_transaction` and `reversible`.
+pub fn metadata_from_path(path: &Path) -> Result<MigrationMetadata, MigrationError> {
+    let metadata_path = path.join("metadata.toml");
+    if !metadata_path.is_file() {
+        return Ok(MigrationMetadata::default());
+    }
+    let metadata = try!(read_sql_file(&metadata_path));
+    parse_metadata(&metadata)
+        .ok_or(MigrationError::InvalidMigrationMetadata(metadata_path))
+}
+
+pub fn run_in_transaction_from_path(path: &Path) -> Result<bool, MigrationError> {
+    metadata_from_path(path).map(|metadata| metadata.run_in_transaction)
+}
+
+fn parse_metadata(metadata: &str) -> Option<MigrationMetadata> {
+    let mut result = MigrationMetadata::default();
+    for line in metadata.lines() {
+        let line = line.split("#").next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut parts = line.splitn(2, "=").map(|part| part.trim());
+        let (key, value) = (parts.next(), parts.next());
+        let value = match value {
+            Some("true") => true,
+            Some("false") => false,
+            _ => return None,
+        };
+        match key {
+            Some("run_in_transaction") => result.run_in_transaction = value,
+            Some("reversible") => result.reversible = value,
+            _ => return None,
+        }
+    }
+    Some(result)
+}
+
+/// Returns a hash of the migration's `up.sql`, or `None` if the migration is not
+/// written in SQL. This uses 64 bit FNV-1a, which is stable across platforms and
+/// Rust versions, so it can be stored and compared later.
+pub fn checksum(migration: &Migration) -> Option<String> {
+    migration.up_sql().ok().map(|sql| format!("{:016x}", fnv1a(sql.as_bytes())))
+}
+
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+pub fn name_from_path(path: &Path) -> Option<String> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.splitn(2, "_").nth(1))
+        .map(|name| name.into())
+}
+
 use std::fs::File;
 use std::io::Read;
 
-struct SqlFileMigration(PathBuf, String);
+struct SqlFileMigration {
+    path: PathBuf,
+    version: String,
+    run_in_transaction: bool,
+    reversible: bool,
+}
 
 impl Migration for SqlFileMigration {
     fn version(&self) -> &str {
-        &self.1
+        &self.version
+    }
+
+    fn name(&self) -> Option<String> {
+        name_from_path(&self.path)
+    }
+
+    fn run(&self, conn: &SimpleConnection) -> Result<(), RunMigrationsError> {
+        let path = self.path.join("up.sql");
+        let sql = try!(read_sql_file(&path));
+        run_sql(conn, &self.version, Some(&path), &sql)
+    }
+
+    fn revert(&self, conn: &SimpleConnection) -> Result<(), RunMigrationsError> {
+        let path = self.path.join("down.sql");
+        let sql = try!(read_sql_file(&path));
+        run_sql(conn, &self.version, Some(&path), &sql)
+    }
+
+    fn up_sql(&self) -> Result<String, MigrationError> {
+        read_sql_file(&self.path.join("up.sql"))
+    }
+
+    fn run_in_transaction(&self) -> bool {
+        self.run_in_transaction
+    }
+
+    fn reversible(&self) -> bool {
+        self.reversible
+    }
+}
+
+/// A migration which was compiled into the binary by `embed_migrations!`. This
+/// type is only public for use by the code generated by that macro.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedMigration {
+    pub version: &'static str,
+    pub up_sql: &'static str,
+    pub down_sql: &'static str,
+    pub run_in_transaction: bool,
+}
+
+impl Migration for EmbeddedMigration {
+    fn version(&self) -> &str {
+        self.version
     }
 
     fn run(&self, conn: &SimpleConnection) -> Result<(), RunMigrationsError> {
-        run_sql_from_file(conn, &self.0.join("up.sql"))
+        run_sql(conn, self.version, None, self.up_sql)
     }
 
     fn revert(&self, conn: &SimpleConnection) -> Result<(), RunMigrationsError> {
-        run_sql_from_file(conn, &self.0.join("down.sql"))
+        run_sql(conn, self.version, None, self.down_sql)
+    }
+
+    fn up_sql(&self) -> Result<String, MigrationError> {
+        Ok(self.up_sql.into())
     }
+
+    fn run_in_transaction(&self) -> bool {
+        self.run_in_transaction
+    }
+
+    fn reversible(&self) -> bool {
+        !is_irreversible_sql(self.down_sql)
+    }
+}
+
+/// Runs each statement in the SQL separately, so that if one fails, the error
+/// can say which.
+fn run_sql(conn: &SimpleConnection, version: &str, path: Option<&Path>, sql: &str)
+    -> Result<(), RunMigrationsError>
+{
+    for (line, statement) in split_statements(sql) {
+        if let Err(error) = conn.batch_execute(statement) {
+            return Err(RunMigrationsError::MigrationFailed(MigrationFailure {
+                version: version.into(),
+                path: path.map(|p| p.to_path_buf()),
+                line: Some(line),
+                statement: Some(statement.into()),
+                error: Box::new(error.into()),
+                partially_applied: false,
+            }));
+        }
+    }
+    Ok(())
 }
 
-fn run_sql_from_file(conn: &SimpleConnection, path: &Path) -> Result<(), RunMigrationsError> {
+pub fn read_sql_file(path: &Path) -> Result<String, MigrationError> {
     let mut sql = String::new();
     let mut file = try!(File::open(path));
     try!(file.read_to_string(&mut sql));
-    try!(conn.batch_execute(&sql));
-    Ok(())
+    Ok(sql)
 }
 
 #[cfg(test)]
 mod tests {
     extern crate tempdir;
 
-    use super::{version_from_path, valid_sql_migration_directory};
+    use super::{checksum, fnv1a, migration_from, name_from_path, version_from_path, valid_sql_migration_directory};
+    use super::super::{MigrationError, RunMigrationsError};
+    use super::{parse_metadata, run_in_transaction_from_path, MigrationMetadata};
+    use super::{EmbeddedMigration, Migration};
+    use connection::SimpleConnection;
 
     use self::tempdir::TempDir;
     use std::fs;
+    use std::io::Write;
     use std::path::PathBuf;
 
     #[test]
@@ -131,6 +390,73 @@ mod tests {
         assert!(valid_sql_migration_directory(&folder));
     }
 
+    #[test]
+    fn directory_containing_metadata_toml_is_valid_migration_dir() {
+        let tempdir = TempDir::new("diesel").unwrap();
+        let folder = tempdir.path().join("12345");
+
+        fs::create_dir(&folder).unwrap();
+        fs::File::create(folder.join("up.sql")).unwrap();
+        fs::File::create(folder.join("down.sql")).unwrap();
+        fs::File::create(folder.join("metadata.toml")).unwrap();
+
+        assert!(valid_sql_migration_directory(&folder));
+    }
+
+    #[test]
+    fn migration_directories_must_start_with_a_numeric_version() {
+        let tempdir = TempDir::new("diesel").unwrap();
+        let folder = tempdir.path().join("first_create_users");
+
+        fs::create_dir(&folder).unwrap();
+        fs::File::create(folder.join("up.sql")).unwrap();
+        fs::File::create(folder.join("down.sql")).unwrap();
+
+        let error = MigrationError::InvalidMigrationVersion("first".into());
+        assert_eq!(Err(error), migration_from(folder).map(|m| m.version().to_string()));
+    }
+
+    #[test]
+    fn migrations_run_in_transaction_unless_metadata_says_otherwise() {
+        let tempdir = TempDir::new("diesel").unwrap();
+
+        assert_eq!(Ok(true), run_in_transaction_from_path(tempdir.path()));
+
+        let mut file = fs::File::create(tempdir.path().join("metadata.toml")).unwrap();
+        file.write_all(b"run_in_transaction = false\n").unwrap();
+
+        assert_eq!(Ok(false), run_in_transaction_from_path(tempdir.path()));
+    }
+
+    #[test]
+    fn metadata_is_parsed_leniently_but_unknown_keys_are_rejected() {
+        let run_in_transaction = |metadata| parse_metadata(metadata).map(|m| m.run_in_transaction);
+        assert_eq!(Some(true), run_in_transaction(""));
+        assert_eq!(Some(false), run_in_transaction("# comment\n  run_in_transaction=false # yes\n"));
+        assert_eq!(Some(true), run_in_transaction("run_in_transaction = true"));
+        assert_eq!(None, run_in_transaction("run_in_transaction = \"false\""));
+        assert_eq!(None, run_in_transaction("run_in_transactoin = false"));
+    }
+
+    #[test]
+    fn migrations_can_be_marked_as_irreversible() {
+        assert_eq!(Some(MigrationMetadata { run_in_transaction: true, reversible: false }),
+                   parse_metadata("reversible = false"));
+
+        let tempdir = TempDir::new("diesel").unwrap();
+        let with_marker = tempdir.path().join("1_with_marker");
+        fs::create_dir(&with_marker).unwrap();
+        fs::File::create(with_marker.join("up.sql")).unwrap();
+        fs::File::create(with_marker.join("down.sql")).unwrap().write_all(b"-- IRREVERSIBLE\n").unwrap();
+        assert!(!migration_from(with_marker).unwrap().reversible());
+
+        let without_down_sql = tempdir.path().join("2_without_down_sql");
+        fs::create_dir(&without_down_sql).unwrap();
+        fs::File::create(without_down_sql.join("up.sql")).unwrap();
+        fs::File::create(without_down_sql.join("metadata.toml")).unwrap().write_all(b"reversible = false").unwrap();
+        assert!(!migration_from(without_down_sql).unwrap().reversible());
+    }
+
     #[test]
     fn empty_directory_is_not_valid_migration_dir() {
         let tempdir = TempDir::new("diesel").unwrap();
@@ -172,4 +498,62 @@ mod tests {
 
         assert_eq!(Ok("create".into()), version_from_path(&path));
     }
+
+    #[test]
+    fn checksums_are_64_bit_fnv1a() {
+        assert_eq!(0xcbf29ce484222325, fnv1a(b""));
+        assert_eq!(0xaf63dc4c8601ec8c, fnv1a(b"a"));
+    }
+
+    #[test]
+    fn checksum_is_based_on_up_sql() {
+        let migration = EmbeddedMigration {
+            version: "1",
+            up_sql: "a",
+            down_sql: "",
+            run_in_transaction: true,
+        };
+        assert_eq!(Some("af63dc4c8601ec8c".into()), checksum(&migration));
+        let changed = EmbeddedMigration { up_sql: "b", ..migration };
+        assert!(checksum(&migration) != checksum(&changed));
+        let different_down_sql = EmbeddedMigration { down_sql: "b", ..migration };
+        assert_eq!(checksum(&migration), checksum(&different_down_sql));
+    }
+
+    struct RustMigration;
+
+    impl Migration for RustMigration {
+        fn version(&self) -> &str {
+            "1"
+        }
+
+        fn run(&self, _: &SimpleConnection) -> Result<(), RunMigrationsError> {
+            Ok(())
+        }
+
+        fn revert(&self, _: &SimpleConnection) -> Result<(), RunMigrationsError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn migrations_which_are_not_written_in_sql_have_no_up_sql() {
+        assert_eq!(Err(MigrationError::NoSqlForMigration("1".into())), RustMigration.up_sql());
+        assert!(MigrationError::NoSqlForMigration("1".into()) != MigrationError::NoSqlForMigration("2".into()));
+        assert_eq!(None, checksum(&RustMigration));
+    }
+
+    #[test]
+    fn migration_name_is_the_rest_of_the_folder_name() {
+        let path = PathBuf::new().join("migrations").join("54321_create_stuff");
+
+        assert_eq!(Some("create_stuff".into()), name_from_path(&path));
+    }
+
+    #[test]
+    fn migration_name_is_none_when_folder_is_only_a_version() {
+        let path = PathBuf::new().join("migrations").join("12345");
+
+        assert_eq!(None, name_from_path(&path));
+    }
 }
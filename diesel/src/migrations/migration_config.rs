@@ -0,0 +1,244 @@
+use super::migration::fnv1a;
+use super::VersionFormat;
+use types::{Bool, VarChar};
+
+use std::time::Duration;
+
+sql_function!(set_config, set_config_t, (setting_name: VarChar, new_value: VarChar, is_local: Bool) -> VarChar);
+
+/// Configures the table which Diesel uses to keep track of which migrations
+/// have been run. By default this is `__diesel_schema_migrations`, in the
+/// default schema.
+///
+/// # Example
+///
+/// ```rust
+/// # use diesel::migrations::{MigrationConfig, VersionFormat};
+/// let config = MigrationConfig {
+///     table_name: "__migrations".into(),
+///     schema: Some("deploy".into()),
+///     run_by: Some("deploy-server-1".into()),
+///     version_format: VersionFormat::Timestamp,
+///     allow_out_of_order: false,
+/// };
+/// assert_eq!(r#""deploy"."__migrations""#, config.qualified_table_name());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationConfig {
+    /// The name of the table.
+    pub table_name: String,
+    /// The schema containing the table. When this is `None`, the table is
+    /// created in the default schema. The schema must already exist.
+    pub schema: Option<String>,
+    /// Who or what ran the migrations, such as the host name of the server
+    /// which ran them. This is recorded alongside each migration which is run,
+    /// and returned by
+    /// [`applied_migrations`](fn.applied_migrations.html).
+    pub run_by: Option<String>,
+    /// The format which migration versions must follow. Migrations are
+    /// checked against it before any of them are run.
+    pub version_format: VersionFormat,
+    /// Whether pending migrations which are older than the latest migration that has been run
+    /// are allowed to run. When this is `false`, running them returns
+    /// `MigrationError::OutOfOrderMigrations` instead, which is useful for enforcing a linear
+    /// history in production. Defaults to `true`.
+    pub allow_out_of_order: bool,
+}
+
+impl Default for MigrationConfig {
+    fn default() -> Self {
+        MigrationConfig {
+            table_name: "__diesel_schema_migrations".into(),
+            schema: None,
+            run_by: None,
+            version_format: VersionFormat::default(),
+            allow_out_of_order: true,
+        }
+    }
+}
+
+impl MigrationConfig {
+    /// The configuration used for seeds, which are tracked in
+    /// `__diesel_seeds`. See [`run_pending_seeds`](fn.run_pending_seeds.html).
+    pub fn seeds() -> Self {
+        MigrationConfig {
+            table_name: "__diesel_seeds".into(),
+            ..MigrationConfig::default()
+        }
+    }
+
+    /// The quoted name of the table, qualified with the schema if one was
+    /// given.
+    pub fn qualified_table_name(&self) -> String {
+        match self.schema {
+            Some(ref schema) => format!("{}.{}", quote_identifier(schema), quote_identifier(&self.table_name)),
+            None => quote_identifier(&self.table_name),
+        }
+    }
+
+    /// The key of the advisory lock which is held while migrations are run.
+    /// This is based on the table name, so migrations tracked in different
+    /// tables can be run at the same time.
+    #[doc(hidden)]
+    pub fn lock_key(&self) -> i64 {
+        fnv1a(self.qualified_table_name().as_bytes()) as i64
+    }
+
+    #[doc(hidden)]
+    pub fn lock_sql(&self) -> String {
+        format!("SELECT pg_advisory_lock({})", self.lock_key())
+    }
+
+    #[doc(hidden)]
+    pub fn transaction_lock_sql(&self) -> String {
+        format!("SELECT pg_advisory_xact_lock({})", self.lock_key())
+    }
+
+    #[doc(hidden)]
+    pub fn unlock_sql(&self) -> String {
+        format!("SELECT pg_advisory_unlock({})", self.lock_key())
+    }
+
+    #[doc(hidden)]
+    pub fn create_table_sql(&self) -> String {
+        format!("CREATE TABLE IF NOT EXISTS {} (
+    version VARCHAR PRIMARY KEY NOT NULL,
+    run_on TIMESTAMP NOT NULL DEFAULT NOW(),
+    checksum VARCHAR,
+    duration_ms BIGINT,
+    run_by VARCHAR
+)", self.qualified_table_name())
+    }
+
+    /// The columns which have been added to the table since it was first
+    /// created, along with their types. Tables created by older versions of
+    /// Diesel are upgraded by adding any of these which are missing.
+    #[doc(hidden)]
+    pub fn added_columns() -> &'static [(&'static str, &'static str)] {
+        &[("checksum", "VARCHAR"), ("duration_ms", "BIGINT"), ("run_by", "VARCHAR")]
+    }
+
+    #[doc(hidden)]
+    pub fn column_exists_sql(&self, column: &str) -> String {
+        format!("{} AND column_name = {}",
+                self.table_exists_sql().replace("information_schema.tables", "information_schema.columns"),
+                quote_string(column))
+    }
+
+    #[doc(hidden)]
+    pub fn add_column_sql(&self, column: &str, sql_type: &str) -> String {
+        format!("ALTER TABLE {} ADD COLUMN {} {}",
+                self.qualified_table_name(), quote_identifier(column), sql_type)
+    }
+
+    /// Only the configured schema is checked, or the schema the table would be created in if none
+    /// was given, so a table with the same name in another schema isn't mistaken for this one.
+    #[doc(hidden)]
+    pub fn table_exists_sql(&self) -> String {
+        let schema = match self.schema {
+            Some(ref schema) => quote_string(schema),
+            None => "current_schema()".into(),
+        };
+        format!("SELECT 1 FROM information_schema.tables WHERE table_name = {} AND table_schema = {}",
+                quote_string(&self.table_name), schema)
+    }
+
+    #[doc(hidden)]
+    pub fn insert_version_sql(&self, version: &str, checksum: Option<&str>, duration: Option<Duration>)
+        -> String
+    {
+        let checksum = checksum.map(quote_string).unwrap_or("NULL".into());
+        let duration = duration.map(|d| (d.as_secs() * 1000 + d.subsec_nanos() as u64 / 1_000_000).to_string())
+            .unwrap_or("NULL".into());
+        let run_by = self.run_by.as_ref().map(|r| quote_string(r)).unwrap_or("NULL".into());
+        format!("INSERT INTO {} (version, checksum, duration_ms, run_by) VALUES ({}, {}, {}, {})",
+                self.qualified_table_name(), quote_string(version), checksum, duration, run_by)
+    }
+
+    /// A schema search path of only the configured schema, so that tables which aren't qualified
+    /// with a schema are created there. Returns `None` if no schema was given.
+    #[doc(hidden)]
+    pub fn search_path(&self) -> Option<String> {
+        self.schema.as_ref().map(|schema| quote_identifier(schema))
+    }
+
+    #[doc(hidden)]
+    pub fn delete_version_sql(&self, version: &str) -> String {
+        format!("DELETE FROM {} WHERE version = {}",
+                self.qualified_table_name(), quote_string(version))
+    }
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace("\"", "\"\""))
+}
+
+fn quote_string(value: &str) -> String {
+    format!("'{}'", value.replace("'", "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MigrationConfig, VersionFormat};
+    use std::time::Duration;
+
+    #[test]
+    fn default_config_uses_diesel_schema_migrations() {
+        let config = MigrationConfig::default();
+        assert_eq!(r#""__diesel_schema_migrations""#, config.qualified_table_name());
+        assert_eq!(r#"INSERT INTO "__diesel_schema_migrations" (version, checksum, duration_ms, run_by) VALUES ('123', NULL, NULL, NULL)"#,
+                   config.insert_version_sql("123", None, None));
+        assert_eq!(r#"INSERT INTO "__diesel_schema_migrations" (version, checksum, duration_ms, run_by) VALUES ('123', 'abc', NULL, NULL)"#,
+                   config.insert_version_sql("123", Some("abc"), None));
+    }
+
+    #[test]
+    fn duration_and_run_by_are_recorded() {
+        let config = MigrationConfig {
+            run_by: Some("db's host".into()),
+            ..MigrationConfig::default()
+        };
+        let duration = Duration::new(2, 345_678_901);
+        assert_eq!(r#"INSERT INTO "__diesel_schema_migrations" (version, checksum, duration_ms, run_by) VALUES ('123', NULL, 2345, 'db''s host')"#,
+                   config.insert_version_sql("123", None, Some(duration)));
+    }
+
+    #[test]
+    fn lock_key_depends_on_the_table() {
+        let default_config = MigrationConfig::default();
+        let other_config = MigrationConfig {
+            table_name: "__migrations".into(),
+            ..MigrationConfig::default()
+        };
+        assert!(default_config.lock_key() != other_config.lock_key());
+        assert_eq!(format!("SELECT pg_advisory_lock({})", default_config.lock_key()),
+                   default_config.lock_sql());
+    }
+
+    #[test]
+    fn table_and_schema_names_are_escaped() {
+        let config = MigrationConfig {
+            table_name: r#"my"migrations"#.into(),
+            schema: Some("deploy".into()),
+            run_by: None,
+            version_format: VersionFormat::Any,
+            allow_out_of_order: true,
+        };
+        assert_eq!(r#""deploy"."my""migrations""#, config.qualified_table_name());
+        assert_eq!(r#"DELETE FROM "deploy"."my""migrations" WHERE version = 'it''s'"#,
+                   config.delete_version_sql("it's"));
+        assert_eq!(r#"SELECT 1 FROM information_schema.tables WHERE table_name = 'my"migrations' AND table_schema = 'deploy'"#,
+                   config.table_exists_sql());
+        assert_eq!(Some(r#""deploy""#.to_string()), config.search_path());
+        assert_eq!(None, MigrationConfig::default().search_path());
+    }
+
+    #[test]
+    fn tables_are_only_looked_up_in_the_current_schema_by_default() {
+        let config = MigrationConfig::default();
+        assert_eq!("SELECT 1 FROM information_schema.tables WHERE table_name = '__diesel_schema_migrations' AND table_schema = current_schema()",
+                   config.table_exists_sql());
+        assert_eq!("SELECT 1 FROM information_schema.columns WHERE table_name = '__diesel_schema_migrations' AND table_schema = current_schema() AND column_name = 'run_by'",
+                   config.column_exists_sql("run_by"));
+    }
+}
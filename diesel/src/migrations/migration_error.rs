@@ -13,15 +13,37 @@ pub enum MigrationError {
     UnknownMigrationFormat(PathBuf),
     IoError(io::Error),
     UnknownMigrationVersion(String),
+    NoSqlForMigration(String),
+    ChecksumMismatch(String),
+    InvalidMigrationMetadata(PathBuf),
+    InvalidMigrationVersion(String),
+    InvalidConfigFile(PathBuf),
+    PendingMigrationBeforeBaseline(String),
+    Irreversible(String),
+    OutOfOrderMigrations(Vec<String>),
+    NotRunInTransaction(String),
+    DuplicateMigrationVersion(String),
+    TransactionalDdlNotSupported,
 }
 
 impl Error for MigrationError {
     fn description(&self) -> &str {
         match *self {
             MigrationDirectoryNotFound => "Unable to find migrations directory in this directory or any parent directories.",
-            UnknownMigrationFormat(_) => "Invalid migration directory, the directory's name should be <timestamp>_<name_of_migration>, and it should only contain up.sql and down.sql.",
+            UnknownMigrationFormat(_) => "Invalid migration directory, the directory's name should be <timestamp>_<name_of_migration>, and it should only contain up.sql, down.sql, and optionally metadata.toml. down.sql can only be omitted if metadata.toml contains `reversible = false`.",
             IoError(ref error) => error.description(),
             UnknownMigrationVersion(_) => "Unable to find migration version to revert in the migrations directory.",
+            NoSqlForMigration(_) => "This migration is not written in SQL, so it cannot be included in a SQL script.",
+            ChecksumMismatch(_) => "A migration which has already been run has been changed since it was run.",
+            InvalidMigrationMetadata(_) => "Invalid metadata.toml in migration directory. The supported keys are `run_in_transaction` and `reversible`, which must be `true` or `false`.",
+            InvalidMigrationVersion(_) => "Invalid migration version. Versions must only contain digits, and match the `VersionFormat` given in the `MigrationConfig`.",
+            InvalidConfigFile(_) => "Invalid diesel.toml. The `dir` key in the `[migrations_directory]` section must be a string.",
+            PendingMigrationBeforeBaseline(_) => "Migrations can't be squashed, because a migration before the latest one that was run has not been run.",
+            Irreversible(_) => "This migration is irreversible, so it cannot be reverted.",
+            OutOfOrderMigrations(_) => "Pending migrations are older than the latest migration that was run, and the `MigrationConfig` doesn't allow out of order migrations.",
+            NotRunInTransaction(_) => "This migration opts out of running in a transaction, so it can't be run inside of a transaction with other changes.",
+            DuplicateMigrationVersion(_) => "More than one migration has the same version. Migrations written in Rust must not use the version of a migration in the migrations directory.",
+            TransactionalDdlNotSupported => "The backend can't roll back changes to the schema, so migrations can't be run in a single transaction.",
         }
     }
 }
@@ -43,6 +65,54 @@ impl PartialEq for MigrationError {
                 &MigrationError::UnknownMigrationFormat(ref p1),
                 &MigrationError::UnknownMigrationFormat(ref p2),
             ) => p1 == p2,
+            (
+                &MigrationError::InvalidMigrationMetadata(ref p1),
+                &MigrationError::InvalidMigrationMetadata(ref p2),
+            ) => p1 == p2,
+            (
+                &MigrationError::UnknownMigrationVersion(ref v1),
+                &MigrationError::UnknownMigrationVersion(ref v2),
+            ) => v1 == v2,
+            (
+                &MigrationError::NoSqlForMigration(ref v1),
+                &MigrationError::NoSqlForMigration(ref v2),
+            ) => v1 == v2,
+            (
+                &MigrationError::ChecksumMismatch(ref v1),
+                &MigrationError::ChecksumMismatch(ref v2),
+            ) => v1 == v2,
+            (
+                &MigrationError::InvalidConfigFile(ref p1),
+                &MigrationError::InvalidConfigFile(ref p2),
+            ) => p1 == p2,
+            (
+                &MigrationError::PendingMigrationBeforeBaseline(ref v1),
+                &MigrationError::PendingMigrationBeforeBaseline(ref v2),
+            ) => v1 == v2,
+            (
+                &MigrationError::Irreversible(ref v1),
+                &MigrationError::Irreversible(ref v2),
+            ) => v1 == v2,
+            (
+                &MigrationError::InvalidMigrationVersion(ref v1),
+                &MigrationError::InvalidMigrationVersion(ref v2),
+            ) => v1 == v2,
+            (
+                &MigrationError::OutOfOrderMigrations(ref v1),
+                &MigrationError::OutOfOrderMigrations(ref v2),
+            ) => v1 == v2,
+            (
+                &MigrationError::NotRunInTransaction(ref v1),
+                &MigrationError::NotRunInTransaction(ref v2),
+            ) => v1 == v2,
+            (
+                &MigrationError::DuplicateMigrationVersion(ref v1),
+                &MigrationError::DuplicateMigrationVersion(ref v2),
+            ) => v1 == v2,
+            (
+                &MigrationError::TransactionalDdlNotSupported,
+                &MigrationError::TransactionalDdlNotSupported,
+            ) => true,
             _ => false
         }
     }
@@ -58,6 +128,60 @@ impl From<io::Error> for MigrationError {
 pub enum RunMigrationsError {
     MigrationError(MigrationError),
     QueryError(result::Error),
+    MigrationFailed(MigrationFailure),
+}
+
+/// Describes a migration which failed to run or revert. For SQL migrations,
+/// this includes the statement which failed, and where it is in the file.
+#[derive(Debug, PartialEq)]
+pub struct MigrationFailure {
+    /// The version of the migration.
+    pub version: String,
+    /// The SQL file containing the statement which failed. This is `None` if
+    /// the migration isn't in the migrations directory.
+    pub path: Option<PathBuf>,
+    /// The line of the SQL which the failed statement starts on.
+    pub line: Option<usize>,
+    /// The statement which failed.
+    pub statement: Option<String>,
+    /// The error which the migration failed with.
+    pub error: Box<RunMigrationsError>,
+    /// `true` if the migration was not run in a transaction which could roll back its schema
+    /// changes, either because it opted out of running in a transaction, or because the backend
+    /// doesn't support transactional DDL. Any statements before the failed one may have been
+    /// applied, and will need to be fixed by hand before the migration is run again.
+    pub partially_applied: bool,
+}
+
+impl RunMigrationsError {
+    /// Wraps an error returned by a migration in `MigrationFailed`, unless the
+    /// migration already did.
+    #[doc(hidden)]
+    pub fn for_migration(self, version: &str) -> Self {
+        match self {
+            RunMigrationsError::MigrationFailed(failure) => RunMigrationsError::MigrationFailed(failure),
+            error => RunMigrationsError::MigrationFailed(MigrationFailure {
+                version: version.into(),
+                path: None,
+                line: None,
+                statement: None,
+                error: Box::new(error),
+                partially_applied: false,
+            }),
+        }
+    }
+
+    /// Marks a failed migration as partially applied. Other errors are returned unchanged.
+    #[doc(hidden)]
+    pub fn partially_applied(self) -> Self {
+        match self {
+            RunMigrationsError::MigrationFailed(failure) => RunMigrationsError::MigrationFailed(MigrationFailure {
+                partially_applied: true,
+                ..failure
+            }),
+            error => error,
+        }
+    }
 }
 
 impl Error for RunMigrationsError {
@@ -65,13 +189,38 @@ impl Error for RunMigrationsError {
         match *self {
             RunMigrationsError::MigrationError(ref error) => error.description(),
             RunMigrationsError::QueryError(ref error) => error.description(),
+            RunMigrationsError::MigrationFailed(ref failure) => failure.error.description(),
         }
     }
 }
 
 impl fmt::Display for RunMigrationsError {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        self.description().fmt(f)
+        match *self {
+            RunMigrationsError::MigrationFailed(ref failure) => failure.fmt(f),
+            _ => self.description().fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for MigrationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        try!(write!(f, "Migration {} failed", self.version));
+        if let Some(ref path) = self.path {
+            try!(write!(f, " in {}", path.display()));
+        }
+        if let Some(line) = self.line {
+            try!(write!(f, " at line {}", line));
+        }
+        try!(write!(f, ": {}", self.error));
+        if let Some(ref statement) = self.statement {
+            try!(write!(f, "\n\n{}", statement));
+        }
+        if self.partially_applied {
+            try!(write!(f, "\n\nThe migration was not run in a transaction which could roll back its \
+                             changes, so it may have been partially applied."));
+        }
+        Ok(())
     }
 }
 
@@ -0,0 +1,70 @@
+use super::RunMigrationsError;
+
+use std::io::Write;
+use std::time::Duration;
+
+/// Callbacks which are invoked around each migration that is run or reverted,
+/// for things such as audit logging or notifying a monitoring system. Pass an
+/// implementation to [`Migrator::with_hooks`](struct.Migrator.html#method.with_hooks),
+/// [`run_pending_migrations_with_hooks`](fn.run_pending_migrations_with_hooks.html) or
+/// [`revert_latest_migration_with_hooks`](fn.revert_latest_migration_with_hooks.html).
+///
+/// `before_run` and `before_revert` are called inside the migration's
+/// transaction, so returning an `Err` will cancel the migration.
+/// `after_run` and `after_revert` are called once the transaction has been
+/// committed, with the time the migration took. Returning an `Err` from them
+/// stops any further migrations from being run.
+///
+/// All methods do nothing by default. Nothing is written to stdout when hooks
+/// are given.
+pub trait MigrationHooks {
+    fn before_run(&mut self, _version: &str) -> Result<(), RunMigrationsError> {
+        Ok(())
+    }
+
+    fn after_run(&mut self, _version: &str, _elapsed: Duration) -> Result<(), RunMigrationsError> {
+        Ok(())
+    }
+
+    fn before_revert(&mut self, _version: &str) -> Result<(), RunMigrationsError> {
+        Ok(())
+    }
+
+    fn after_revert(&mut self, _version: &str, _elapsed: Duration) -> Result<(), RunMigrationsError> {
+        Ok(())
+    }
+}
+
+impl<'a, H: MigrationHooks + ?Sized> MigrationHooks for &'a mut H {
+    fn before_run(&mut self, version: &str) -> Result<(), RunMigrationsError> {
+        (**self).before_run(version)
+    }
+
+    fn after_run(&mut self, version: &str, elapsed: Duration) -> Result<(), RunMigrationsError> {
+        (**self).after_run(version, elapsed)
+    }
+
+    fn before_revert(&mut self, version: &str) -> Result<(), RunMigrationsError> {
+        (**self).before_revert(version)
+    }
+
+    fn after_revert(&mut self, version: &str, elapsed: Duration) -> Result<(), RunMigrationsError> {
+        (**self).after_revert(version, elapsed)
+    }
+}
+
+/// The hooks used by the functions which write their progress to stdout, or a
+/// given `Write`.
+pub struct ProgressOutput<W>(pub W);
+
+impl<W: Write> MigrationHooks for ProgressOutput<W> {
+    fn before_run(&mut self, version: &str) -> Result<(), RunMigrationsError> {
+        try!(writeln!(self.0, "Running migration {}", version));
+        Ok(())
+    }
+
+    fn before_revert(&mut self, version: &str) -> Result<(), RunMigrationsError> {
+        try!(writeln!(self.0, "Rolling back migration {}", version));
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use super::{migration_paths_in_directory, MigrationError};
+use super::migration::{migration_from, Migration};
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Somewhere migrations can be loaded from. This is implemented for a path to
+/// a migrations directory, for a list of migrations directories, and for lists
+/// of migrations which are already in memory, such as the migrations compiled
+/// in by `embed_migrations!`. Use it with
+/// [`Migrator::with_source`](struct.Migrator.html#method.with_source), or
+/// [`run_pending_migrations_from_source`](fn.run_pending_migrations_from_source.html).
+///
+/// Implementing this trait allows migrations to be stored elsewhere, such as in
+/// an archive, or to test code which runs migrations without touching the
+/// filesystem.
+pub trait MigrationSource {
+    /// Returns every migration in this source. They do not need to be in any
+    /// particular order.
+    fn migrations(&self) -> Result<Vec<Box<Migration>>, MigrationError>;
+}
+
+impl<'a, S: MigrationSource + ?Sized> MigrationSource for &'a S {
+    fn migrations(&self) -> Result<Vec<Box<Migration>>, MigrationError> {
+        (**self).migrations()
+    }
+}
+
+impl MigrationSource for Path {
+    fn migrations(&self) -> Result<Vec<Box<Migration>>, MigrationError> {
+        try!(migration_paths_in_directory(self)).into_iter()
+            .map(|(_, path)| migration_from(path))
+            .collect()
+    }
+}
+
+impl MigrationSource for PathBuf {
+    fn migrations(&self) -> Result<Vec<Box<Migration>>, MigrationError> {
+        self.as_path().migrations()
+    }
+}
+
+/// Merges the migrations from each directory. If more than one directory has a
+/// migration with the same version, the one in the directory listed first is
+/// used.
+impl<'a> MigrationSource for [&'a Path] {
+    fn migrations(&self) -> Result<Vec<Box<Migration>>, MigrationError> {
+        let mut versions = HashSet::new();
+        let mut migrations = Vec::new();
+        for dir in self {
+            for migration in try!(dir.migrations()) {
+                if versions.insert(migration.version().to_string()) {
+                    migrations.push(migration);
+                }
+            }
+        }
+        Ok(migrations)
+    }
+}
+
+impl<T: Migration + Clone + 'static> MigrationSource for [T] {
+    fn migrations(&self) -> Result<Vec<Box<Migration>>, MigrationError> {
+        Ok(self.iter().map(|m| Box::new(m.clone()) as Box<Migration>).collect())
+    }
+}
+
+impl<T: Migration + Clone + 'static> MigrationSource for Vec<T> {
+    fn migrations(&self) -> Result<Vec<Box<Migration>>, MigrationError> {
+        (&**self).migrations()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate tempdir;
+
+    use super::MigrationSource;
+
+    use self::tempdir::TempDir;
+    use std::fs;
+    use std::io::Write;
+    use std::path::Path;
+
+    fn create_migration(dir: &Path, name: &str, up_sql: &str) {
+        let folder = dir.join(name);
+        fs::create_dir(&folder).unwrap();
+        fs::File::create(folder.join("up.sql")).unwrap().write_all(up_sql.as_bytes()).unwrap();
+        fs::File::create(folder.join("down.sql")).unwrap();
+    }
+
+    #[test]
+    fn migrations_from_several_directories_are_merged_by_version() {
+        let library_dir = TempDir::new("diesel").unwrap();
+        let app_dir = TempDir::new("diesel").unwrap();
+        create_migration(library_dir.path(), "1_create_jobs", "CREATE TABLE jobs ();");
+        create_migration(library_dir.path(), "3_add_jobs_index", "");
+        create_migration(app_dir.path(), "1_create_jobs", "CREATE TABLE other_jobs ();");
+        create_migration(app_dir.path(), "2_create_users", "");
+
+        let dirs = [library_dir.path(), app_dir.path()];
+        let migrations = dirs[..].migrations().unwrap();
+        let mut versions = migrations.iter().map(|m| m.version()).collect::<Vec<_>>();
+        versions.sort();
+
+        assert_eq!(vec!["1", "2", "3"], versions);
+        let first = migrations.iter().find(|m| m.version() == "1").unwrap();
+        assert_eq!("CREATE TABLE jobs ();", first.up_sql().unwrap());
+    }
+}
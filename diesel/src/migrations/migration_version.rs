@@ -0,0 +1,113 @@
+use super::MigrationError;
+
+use std::cmp::Ordering;
+
+/// The format which migration versions must follow. Every format requires
+/// versions to only contain digits. Whatever the format, versions are ordered
+/// by their numeric value, so `2` is run before `10`. Versions are recorded
+/// exactly as they are written, so `0002` and `2` are different migrations,
+/// and renaming one to the other will cause it to be run again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionFormat {
+    /// Any version made up of digits. This is the default.
+    Any,
+    /// A timestamp of the form `YYYYMMDDHHMMSS`, such as `20160107090901`.
+    /// This is the format used by `diesel migration generate`.
+    Timestamp,
+    /// A sequential integer, such as `0001` or `2`. Versions with more than 8
+    /// digits are rejected, as they are almost certainly timestamps which have
+    /// been mixed in by mistake, and would be run after every other migration.
+    Sequential,
+}
+
+impl Default for VersionFormat {
+    fn default() -> Self {
+        VersionFormat::Any
+    }
+}
+
+impl VersionFormat {
+    /// Returns `MigrationError::InvalidMigrationVersion` if the version does
+    /// not follow this format.
+    pub fn validate(&self, version: &str) -> Result<(), MigrationError> {
+        let valid = is_numeric(version) && match *self {
+            VersionFormat::Any => true,
+            VersionFormat::Timestamp => is_timestamp(version),
+            VersionFormat::Sequential => version.len() <= 8,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(MigrationError::InvalidMigrationVersion(version.into()))
+        }
+    }
+}
+
+/// Compares two migration versions by their numeric value, rather than
+/// lexicographically. Versions which only differ by leading zeros are ordered
+/// lexicographically, so that the order is still total.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let trimmed_a = a.trim_left_matches('0');
+    let trimmed_b = b.trim_left_matches('0');
+    match (trimmed_a.len(), trimmed_a).cmp(&(trimmed_b.len(), trimmed_b)) {
+        Ordering::Equal => a.cmp(b),
+        ordering => ordering,
+    }
+}
+
+fn is_numeric(version: &str) -> bool {
+    !version.is_empty() && version.chars().all(|c| c.is_digit(10))
+}
+
+fn is_timestamp(version: &str) -> bool {
+    if version.len() != 14 {
+        return false;
+    }
+    let part = |start: usize, end: usize| version[start..end].parse::<u32>().unwrap_or(0);
+    let (month, day) = (part(4, 6), part(6, 8));
+    let (hour, minute, second) = (part(8, 10), part(10, 12), part(12, 14));
+    month >= 1 && month <= 12 && day >= 1 && day <= 31 &&
+        hour < 24 && minute < 60 && second < 60
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare_versions, VersionFormat};
+    use super::super::MigrationError;
+
+    use std::cmp::Ordering;
+
+    #[test]
+    fn versions_are_compared_numerically() {
+        assert_eq!(Ordering::Less, compare_versions("2", "10"));
+        assert_eq!(Ordering::Less, compare_versions("0002", "0010"));
+        assert_eq!(Ordering::Greater, compare_versions("20160107090901", "20151219180527"));
+        assert_eq!(Ordering::Equal, compare_versions("0002", "0002"));
+        assert_eq!(Ordering::Less, compare_versions("002", "2"));
+    }
+
+    #[test]
+    fn versions_must_be_numeric() {
+        let error = MigrationError::InvalidMigrationVersion("abc".into());
+        assert_eq!(Err(error), VersionFormat::Any.validate("abc"));
+        assert!(VersionFormat::Any.validate("").is_err());
+        assert!(VersionFormat::Any.validate("12a").is_err());
+        assert_eq!(Ok(()), VersionFormat::Any.validate("0001"));
+        assert_eq!(Ok(()), VersionFormat::Any.validate("20160107090901"));
+    }
+
+    #[test]
+    fn timestamp_versions_must_be_valid_timestamps() {
+        assert_eq!(Ok(()), VersionFormat::Timestamp.validate("20160107090901"));
+        assert!(VersionFormat::Timestamp.validate("0001").is_err());
+        assert!(VersionFormat::Timestamp.validate("20161307090901").is_err());
+        assert!(VersionFormat::Timestamp.validate("20160107250901").is_err());
+    }
+
+    #[test]
+    fn sequential_versions_cannot_be_timestamps() {
+        assert_eq!(Ok(()), VersionFormat::Sequential.validate("0001"));
+        assert_eq!(Ok(()), VersionFormat::Sequential.validate("12"));
+        assert!(VersionFormat::Sequential.validate("20160107090901").is_err());
+    }
+}
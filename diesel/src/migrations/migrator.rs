@@ -0,0 +1,386 @@
+use super::{compare_versions, find_migrations_directory, MigrationConfig, MigrationError};
+use super::{MigrationHooks, MigrationInfo, MigrationSource, MigrationStatus, RunMigrationsError};
+use super::{AppliedMigrationRow, AppliedMigrationSqlType};
+use super::{column_exists, create_migrations_table_if_needed, latest_run_migration_version};
+use super::{migration_with_version, migrations_table_exists, missing_columns};
+use super::{previously_run_migration_versions, previously_run_migration_versions_if_any};
+use super::{revert_latest, revert_migration, run_migration, run_pending, run_pending_atomically};
+use super::{run_pending_in_schema, verify_unique_versions, with_migrations_lock};
+use super::migration::{self, Migration};
+use super::migration_error::MigrationError::*;
+use super::migration_hooks::ProgressOutput;
+use super::squash;
+use ::expression::dsl::sql;
+use ::query_dsl::*;
+use {Connection, QueryResult, Queryable};
+
+use std::cmp::Ordering;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Runs, reverts and inspects migrations with a given
+/// [`MigrationConfig`](struct.MigrationConfig.html), source of migrations, and
+/// [`MigrationHooks`](trait.MigrationHooks.html). The functions in this module are shorthands for
+/// a `Migrator` which uses the default configuration, finds the migrations directory as described
+/// in the [module level documentation](index.html), and writes its progress to stdout. Each of
+/// these can be changed independently.
+///
+/// # Example
+///
+/// ```no_run
+/// # use diesel::migrations::{MigrationConfig, Migrator};
+/// # use diesel::prelude::*;
+/// # use std::io;
+/// # fn main() {
+/// # let connection = PgConnection::establish("postgres://localhost").unwrap();
+/// let config = MigrationConfig {
+///     table_name: "__migrations".into(),
+///     schema: Some("deploy".into()),
+///     ..MigrationConfig::default()
+/// };
+/// let mut migrator = Migrator::new(&connection)
+///     .with_config(config)
+///     .with_directory("db/migrations")
+///     .with_output(io::stderr());
+/// migrator.run_pending_migrations().unwrap();
+/// println!("The schema is at version {:?}", migrator.database_schema_version().unwrap());
+/// # }
+/// ```
+pub struct Migrator<'a, Conn: 'a> {
+    conn: &'a Conn,
+    config: MigrationConfig,
+    directory: Option<PathBuf>,
+    source: Option<Box<MigrationSource + 'a>>,
+    hooks: Box<MigrationHooks + 'a>,
+}
+
+impl<'a, Conn: Connection> Migrator<'a, Conn> {
+    /// Creates a `Migrator` which uses the default `MigrationConfig`, finds the migrations
+    /// directory automatically, and writes its progress to stdout.
+    pub fn new(conn: &'a Conn) -> Self {
+        Migrator {
+            conn: conn,
+            config: MigrationConfig::default(),
+            directory: None,
+            source: None,
+            hooks: Box::new(ProgressOutput(io::stdout())),
+        }
+    }
+
+    /// Keeps track of migrations in the table given by `config`, instead of
+    /// `__diesel_schema_migrations`.
+    pub fn with_config(mut self, config: MigrationConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Uses the migrations in the given directory, instead of searching for the migrations
+    /// directory.
+    pub fn with_directory<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Loads migrations from the given source, such as a list of migrations directories, or the
+    /// migrations compiled in by `embed_migrations!`. Squashing migrations rewrites the files in
+    /// the migrations directory, so it ignores the source.
+    pub fn with_source<S: MigrationSource + 'a>(mut self, source: S) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    /// Writes progress to `output` instead of stdout. Pass `std::io::sink()` to silence the
+    /// output entirely.
+    pub fn with_output<W: Write + 'a>(mut self, output: W) -> Self {
+        self.hooks = Box::new(ProgressOutput(output));
+        self
+    }
+
+    /// Calls the given hooks around each migration which is run or reverted, instead of writing
+    /// progress to stdout.
+    pub fn with_hooks<H: MigrationHooks + 'a>(mut self, hooks: H) -> Self {
+        self.hooks = Box::new(hooks);
+        self
+    }
+
+    /// Runs all migrations that have not yet been run. See
+    /// [`run_pending_migrations`](fn.run_pending_migrations.html).
+    pub fn run_pending_migrations(&mut self) -> Result<(), RunMigrationsError> {
+        let all_migrations = try!(self.migrations());
+        run_pending(self.conn, &self.config, all_migrations, &mut *self.hooks)
+    }
+
+    /// Runs all migrations that have not yet been run, in a single transaction. See
+    /// [`run_pending_migrations_atomically`](fn.run_pending_migrations_atomically.html).
+    pub fn run_pending_migrations_atomically(&mut self) -> Result<(), RunMigrationsError> {
+        let all_migrations = try!(self.migrations());
+        run_pending_atomically(self.conn, &self.config, all_migrations, &mut *self.hooks)
+    }
+
+    /// Runs all migrations that have not yet been run, from both the source and the given list.
+    /// See [`run_migrations`](fn.run_migrations.html).
+    pub fn run_migrations(&mut self, migrations: Vec<Box<Migration>>)
+        -> Result<(), RunMigrationsError>
+    {
+        let mut all_migrations = try!(self.migrations());
+        all_migrations.extend(migrations);
+        try!(verify_unique_versions(&all_migrations));
+        all_migrations.sort_by(|a, b| compare_versions(a.version(), b.version()));
+        run_pending(self.conn, &self.config, all_migrations, &mut *self.hooks)
+    }
+
+    /// Runs all migrations that have not yet been run, up to and including `target_version`. See
+    /// [`run_migrations_until`](fn.run_migrations_until.html).
+    pub fn run_migrations_until(&mut self, target_version: &str) -> Result<(), RunMigrationsError> {
+        let all_migrations = try!(self.migrations());
+        if !all_migrations.iter().any(|m| m.version() == target_version) {
+            return Err(UnknownMigrationVersion(target_version.into()).into());
+        }
+        let migrations = all_migrations.into_iter()
+            .filter(|m| compare_versions(m.version(), target_version) != Ordering::Greater)
+            .collect();
+        run_pending(self.conn, &self.config, migrations, &mut *self.hooks)
+    }
+
+    /// Runs all migrations that have not yet been run in the given schema, keeping track of them
+    /// in a table in that schema. See
+    /// [`run_pending_migrations_for_schemas`](fn.run_pending_migrations_for_schemas.html).
+    pub fn run_pending_migrations_in_schema(&mut self, schema: &str)
+        -> Result<(), RunMigrationsError>
+    {
+        let all_migrations = try!(self.migrations());
+        let config = MigrationConfig {
+            schema: Some(schema.to_string()),
+            ..self.config.clone()
+        };
+        let conn = self.conn;
+        let hooks = &mut *self.hooks;
+        conn.transaction(|| run_pending_in_schema(conn, &config, all_migrations, hooks))
+            .map_err(|e| e.into())
+    }
+
+    /// Reverts the last migration that was run. Returns the version that was reverted. See
+    /// [`revert_latest_migration`](fn.revert_latest_migration.html).
+    pub fn revert_latest_migration(&mut self) -> Result<String, RunMigrationsError> {
+        let all_migrations = try!(self.migrations());
+        revert_latest(self.conn, &self.config, all_migrations, &mut *self.hooks)
+    }
+
+    /// Reverts the last migration that was run, and then runs it again, in a single transaction.
+    /// Returns the version that was redone. See
+    /// [`redo_latest_migration`](fn.redo_latest_migration.html).
+    pub fn redo_latest_migration(&mut self) -> Result<String, RunMigrationsError> {
+        let all_migrations = try!(self.migrations());
+        let conn = self.conn;
+        let config = &self.config;
+        let hooks = &mut *self.hooks;
+        conn.transaction::<_, RunMigrationsError, _>(|| {
+            with_migrations_lock(conn, config, || {
+                try!(create_migrations_table_if_needed(conn, config));
+                let version = try!(latest_run_migration_version(conn, config)
+                    .and_then(|version| version.ok_or(::result::Error::NotFound)));
+                let migration = try!(migration_with_version(all_migrations, &version));
+                if !migration.run_in_transaction() {
+                    return Err(NotRunInTransaction(version).into());
+                }
+                try!(revert_migration(conn, config, &*migration, hooks));
+                try!(run_migration(conn, config, &*migration, hooks));
+                Ok(version)
+            })
+        }).map_err(|e| e.into())
+    }
+
+    #[doc(hidden)]
+    pub fn run_migration_with_version(&mut self, ver: &str) -> Result<(), RunMigrationsError> {
+        let migration = try!(migration_with_version(try!(self.migrations()), ver));
+        run_migration(self.conn, &self.config, &*migration, &mut *self.hooks)
+    }
+
+    #[doc(hidden)]
+    pub fn revert_migration_with_version(&mut self, ver: &str) -> Result<(), RunMigrationsError> {
+        let migration = try!(migration_with_version(try!(self.migrations()), ver));
+        revert_migration(self.conn, &self.config, &*migration, &mut *self.hooks)
+    }
+
+    /// Records the migration with the given version as having been run, without running it. See
+    /// [`mark_migration_as_applied`](fn.mark_migration_as_applied.html).
+    pub fn mark_migration_as_applied(&self, ver: &str) -> Result<(), RunMigrationsError> {
+        let migration = try!(migration_with_version(try!(self.migrations()), ver));
+        let conn = self.conn;
+        let config = &self.config;
+        with_migrations_lock(conn, config, || {
+            try!(create_migrations_table_if_needed(conn, config));
+            let already_run = try!(previously_run_migration_versions(conn, config));
+            if !already_run.contains(migration.version()) {
+                let checksum = migration::checksum(&*migration);
+                let insert_version = config.insert_version_sql(migration.version(), checksum.as_ref().map(|c| &**c), None);
+                try!(conn.execute(&insert_version));
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns the migrations which have not yet been run, in order of version. See
+    /// [`pending_migrations`](fn.pending_migrations.html).
+    pub fn pending_migrations(&self) -> Result<Vec<MigrationInfo>, RunMigrationsError> {
+        let already_run = try!(previously_run_migration_versions_if_any(self.conn, &self.config));
+        let all_migrations = try!(self.migrations());
+        Ok(all_migrations.into_iter()
+            .filter(|m| !already_run.contains(m.version()))
+            .map(|m| MigrationInfo {
+                version: m.version().into(),
+                name: m.name(),
+                run_on: None,
+                duration: None,
+                run_by: None,
+            }).collect())
+    }
+
+    /// Returns `true` if there are any migrations which have not yet been run. See
+    /// [`any_pending_migrations`](fn.any_pending_migrations.html).
+    pub fn any_pending_migrations(&self) -> Result<bool, RunMigrationsError> {
+        self.pending_migrations().map(|migrations| !migrations.is_empty())
+    }
+
+    /// Returns the migrations which have been run, in order of version. See
+    /// [`applied_migrations`](fn.applied_migrations.html).
+    pub fn applied_migrations(&self) -> Result<Vec<MigrationInfo>, RunMigrationsError> where
+        AppliedMigrationRow: Queryable<AppliedMigrationSqlType, Conn::Backend>,
+    {
+        let conn = self.conn;
+        let config = &self.config;
+        if !try!(migrations_table_exists(conn, config)) {
+            return Ok(Vec::new());
+        }
+        let known_migrations = match self.migrations() {
+            Ok(migrations) => migrations,
+            Err(MigrationDirectoryNotFound) => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let extra_columns = if try!(column_exists(conn, config, "duration_ms")) {
+            "duration_ms, run_by"
+        } else {
+            "NULL::BIGINT, NULL::VARCHAR"
+        };
+        let query = format!("SELECT version, run_on, {} FROM {}",
+                            extra_columns, config.qualified_table_name());
+        let mut applied = try!(sql::<AppliedMigrationSqlType>(&query)
+            .load::<AppliedMigrationRow>(conn))
+            .collect::<Vec<_>>();
+        applied.sort_by(|a, b| compare_versions(&a.0, &b.0));
+        Ok(applied.into_iter().map(|(ver, ran_at, duration_ms, run_by)| {
+            let name = known_migrations.iter()
+                .find(|m| m.version() == ver)
+                .and_then(|m| m.name());
+            MigrationInfo {
+                version: ver,
+                name: name,
+                run_on: Some(ran_at),
+                duration: duration_ms.map(|ms| Duration::from_millis(ms as u64)),
+                run_by: run_by,
+            }
+        }).collect())
+    }
+
+    /// Returns the status of every migration which is either in the source, or has been run, in
+    /// order of version. See [`migration_status`](fn.migration_status.html).
+    pub fn migration_status(&self) -> Result<Vec<MigrationStatus>, RunMigrationsError> where
+        AppliedMigrationRow: Queryable<AppliedMigrationSqlType, Conn::Backend>,
+    {
+        let applied = try!(self.applied_migrations());
+        let all_migrations = try!(self.migrations());
+        let mut statuses = all_migrations.iter().map(|m| {
+            MigrationStatus {
+                version: m.version().into(),
+                name: m.name(),
+                applied: applied.iter().find(|a| a.version == m.version()).and_then(|a| a.run_on.clone()),
+                source_present: true,
+            }
+        }).collect::<Vec<_>>();
+        statuses.extend(applied.into_iter()
+            .filter(|a| !all_migrations.iter().any(|m| m.version() == a.version))
+            .map(|a| MigrationStatus {
+                version: a.version,
+                name: None,
+                applied: a.run_on,
+                source_present: false,
+            }));
+        statuses.sort_by(|a, b| compare_versions(&a.version, &b.version));
+        Ok(statuses)
+    }
+
+    /// Returns the version of the latest migration which has been run, or `None` if no migrations
+    /// have been run. See [`database_schema_version`](fn.database_schema_version.html).
+    pub fn database_schema_version(&self) -> QueryResult<Option<String>> {
+        latest_run_migration_version(self.conn, &self.config)
+    }
+
+    /// Writes a SQL script which runs all migrations that have not yet been run, instead of
+    /// running them. See
+    /// [`write_pending_migrations_script`](fn.write_pending_migrations_script.html).
+    pub fn write_pending_migrations_script<W: Write>(&self, out: &mut W)
+        -> Result<(), RunMigrationsError>
+    {
+        let conn = self.conn;
+        let config = &self.config;
+        let already_run = try!(previously_run_migration_versions_if_any(conn, config));
+        let pending_migrations = try!(self.migrations()).into_iter().filter(|m| {
+            !already_run.contains(m.version())
+        });
+
+        try!(writeln!(out, "{};", config.create_table_sql()));
+        if try!(migrations_table_exists(conn, config)) {
+            for &(column, sql_type) in try!(missing_columns(conn, config)).iter() {
+                try!(writeln!(out, "{};", config.add_column_sql(column, sql_type)));
+            }
+        }
+        for migration in pending_migrations {
+            try!(writeln!(out, "\n-- Migration {}", migration.version()));
+            if migration.run_in_transaction() {
+                try!(writeln!(out, "BEGIN;"));
+            }
+            let up_sql = try!(migration.up_sql());
+            try!(writeln!(out, "{}", up_sql.trim_right()));
+            let checksum = migration::checksum(&*migration);
+            let insert_version = config.insert_version_sql(migration.version(), checksum.as_ref().map(|c| &**c), None);
+            try!(writeln!(out, "{};", insert_version));
+            if migration.run_in_transaction() {
+                try!(writeln!(out, "COMMIT;"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces every migration which has been run with a single baseline migration. Returns the
+    /// directory of the baseline migration. See
+    /// [`squash_applied_migrations`](fn.squash_applied_migrations.html).
+    pub fn squash_applied_migrations(&self) -> Result<Option<PathBuf>, RunMigrationsError> {
+        let migrations_dir = try!(self.migrations_directory());
+        squash::squash(self.conn, &self.config, &migrations_dir)
+    }
+
+    /// Records the baseline in place of the squashed migrations which have been run. Returns the
+    /// version of the baseline. See
+    /// [`record_squashed_baseline`](fn.record_squashed_baseline.html).
+    pub fn record_squashed_baseline(&self) -> Result<Option<String>, RunMigrationsError> {
+        let migrations_dir = try!(self.migrations_directory());
+        squash::record_baseline(self.conn, &self.config, &migrations_dir)
+    }
+
+    fn migrations(&self) -> Result<Vec<Box<Migration>>, MigrationError> {
+        let mut migrations = try!(match self.source {
+            Some(ref source) => source.migrations(),
+            None => try!(self.migrations_directory()).migrations(),
+        });
+        migrations.sort_by(|a, b| compare_versions(a.version(), b.version()));
+        Ok(migrations)
+    }
+
+    fn migrations_directory(&self) -> Result<PathBuf, MigrationError> {
+        match self.directory {
+            Some(ref directory) => Ok(directory.clone()),
+            None => find_migrations_directory(),
+        }
+    }
+}
@@ -10,11 +10,39 @@
 //! migrations directory in the current directory and its parents, stopping when it finds the
 //! directory containing `Cargo.toml`.
 //!
+//! The location can also be given explicitly, which is useful when several crates share a
+//! repository. The `MIGRATION_DIRECTORY` environment variable takes precedence over everything
+//! else. Otherwise, a `diesel.toml` file in the current directory or any of its parents can give
+//! the directory, relative to the file itself:
+//!
+//! ```toml
+//! [migrations_directory]
+//! dir = "db/migrations"
+//! ```
+//!
+//! The functions in this module use the default [`MigrationConfig`](struct.MigrationConfig.html)
+//! and write their progress to stdout. To change the table migrations are tracked in, where they
+//! are loaded from, or what happens around each one, use a [`Migrator`](struct.Migrator.html).
+//!
+//! Data which isn't part of the schema, such as the data used by tests or a staging environment,
+//! can be kept in a `seeds` directory next to the `migrations` directory, in the same format. Seeds
+//! are run with [`run_pending_seeds`](fn.run_pending_seeds.html), and are tracked separately from
+//! migrations.
+//!
 //! Individual migrations should be a folder containing exactly two files, `up.sql` and `down.sql`.
 //! `up.sql` will be used to run the migration, while `down.sql` will be used for reverting it. The
 //! folder itself should have the structure `{version}_{migration_name}`. It is recommended that
 //! you use the timestamp of creation for the version.
 //!
+//! Each migration is run in a transaction. Some statements, such as PostgreSQL's `CREATE INDEX
+//! CONCURRENTLY`, can't be run in a transaction. A migration containing them can opt out by adding
+//! a `metadata.toml` file to its folder, containing `run_in_transaction = false`.
+//!
+//! A migration which can't be undone, such as one which deletes data, can be marked as
+//! irreversible by making its `down.sql` contain only `-- IRREVERSIBLE`, or by adding
+//! `reversible = false` to its `metadata.toml` and leaving out `down.sql`. Reverting it will return
+//! `MigrationError::Irreversible`.
+//!
 //! ## Example
 //!
 //! ```text
@@ -55,23 +83,58 @@
 //! -- 20160107082941_create_posts/down.sql
 //! DROP TABLE posts;
 //! ```
+mod drift;
+mod generate;
 mod migration;
+mod migration_config;
 mod migration_error;
-mod schema;
+mod migration_hooks;
+mod migration_source;
+mod migration_version;
+mod migrator;
+mod schema_dump;
+mod seeds;
+mod sql_statements;
+mod squash;
 
+pub use self::drift::{schema_drift, schema_drift_in_directory, SchemaDrift};
+#[doc(hidden)]
+pub use self::drift::{ColumnRow, ColumnSqlType};
+pub use self::generate::{generate_migration, generate_migration_with_version};
+pub use self::migration_config::MigrationConfig;
 pub use self::migration_error::*;
+pub use self::migration_hooks::MigrationHooks;
+pub use self::migration_source::MigrationSource;
+pub use self::migration_version::{compare_versions, VersionFormat};
+pub use self::migrator::Migrator;
+pub use self::schema_dump::{dump_schema, dump_schema_to_file, run_pending_migrations_and_then};
+#[doc(hidden)]
+pub use self::schema_dump::{DumpColumnRow, DumpColumnSqlType};
+pub use self::seeds::{find_seeds_directory, run_pending_seeds, run_seeds};
+pub use self::sql_statements::split_statements;
+pub use self::squash::{squash_applied_migrations, squash_applied_migrations_in_directory};
+pub use self::squash::{record_squashed_baseline, record_squashed_baseline_in_directory};
+pub use self::migration::{Migration, MigrationInfo, MigrationStatus};
+#[doc(hidden)]
+pub use self::migration::EmbeddedMigration;
 
-use ::expression::expression_methods::*;
+use ::data_types::PgTimestamp;
+use ::expression::dsl::sql;
+use ::query_builder::functions::select;
 use ::query_dsl::*;
 use self::migration::*;
+use self::migration_config::set_config;
 use self::migration_error::MigrationError::*;
-use self::schema::NewMigration;
-use self::schema::__diesel_schema_migrations::dsl::*;
-use {Connection, QueryResult};
+use backend::Backend;
+use {Connection, QueryResult, Queryable, types};
 
-use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::fs::File;
+use std::io::{self, Read, Write};
 use std::path::{PathBuf, Path};
+use std::time::Instant;
 
 /// Runs all migrations that have not yet been run. This function will print all progress to
 /// stdout. This function will return an `Err` if some error occurs reading the migrations, or if
@@ -87,14 +150,266 @@ use std::path::{PathBuf, Path};
 /// See the [module level documentation](index.html) for information on how migrations should be
 /// structured, and where Diesel will look for them by default.
 pub fn run_pending_migrations<Conn: Connection>(conn: &Conn) -> Result<(), RunMigrationsError> {
-    try!(create_schema_migrations_table_if_needed(conn));
-    let already_run = try!(previously_run_migration_versions(conn));
-    let migrations_dir = try!(find_migrations_directory());
-    let all_migrations = try!(migrations_in_directory(&migrations_dir));
+    run_pending_migrations_with_output(conn, &mut io::stdout())
+}
+
+/// Runs all migrations that have not yet been run, writing progress to `output` instead of
+/// stdout. Pass `&mut std::io::sink()` to silence the output entirely. See
+/// [`run_pending_migrations`](fn.run_pending_migrations.html).
+pub fn run_pending_migrations_with_output<Conn: Connection>(conn: &Conn, output: &mut Write)
+    -> Result<(), RunMigrationsError>
+{
+    Migrator::new(conn).with_output(output).run_pending_migrations()
+}
+
+/// Runs all migrations that have not yet been run, calling the given hooks around each one
+/// instead of writing progress to stdout. See
+/// [`run_pending_migrations`](fn.run_pending_migrations.html).
+pub fn run_pending_migrations_with_hooks<Conn: Connection>(conn: &Conn, hooks: &mut MigrationHooks)
+    -> Result<(), RunMigrationsError>
+{
+    Migrator::new(conn).with_hooks(hooks).run_pending_migrations()
+}
+
+/// Runs all migrations that have not yet been run, keeping track of them in the table given by
+/// `config` instead of `__diesel_schema_migrations`. See
+/// [`run_pending_migrations`](fn.run_pending_migrations.html).
+pub fn run_pending_migrations_with_config<Conn: Connection>(conn: &Conn, config: &MigrationConfig)
+    -> Result<(), RunMigrationsError>
+{
+    Migrator::new(conn).with_config(config.clone()).run_pending_migrations()
+}
+
+/// Runs all migrations that have not yet been run, in a single transaction, writing progress to
+/// `output`. Unlike [`run_pending_migrations`](fn.run_pending_migrations.html), if any migration
+/// fails to run, none of the migrations will be committed, and the database is left as it was.
+///
+/// This relies on the backend supporting DDL inside of transactions, which PostgreSQL does. On
+/// other backends, `MigrationError::TransactionalDdlNotSupported` is returned, and nothing is run.
+/// Statements which cannot be run in a transaction, such as `CREATE INDEX CONCURRENTLY`, cannot
+/// be used in migrations run this way. If any pending migration opts out of running in a
+/// transaction, `MigrationError::NotRunInTransaction` is returned, and nothing is run.
+pub fn run_pending_migrations_atomically<Conn: Connection>(conn: &Conn, output: &mut Write)
+    -> Result<(), RunMigrationsError>
+{
+    Migrator::new(conn).with_output(output).run_pending_migrations_atomically()
+}
+
+/// Runs all migrations that have not yet been run once for each of the given schemas, writing
+/// progress to `output`. This is useful when each tenant of an application has its own schema.
+///
+/// While a schema's migrations are run, the search path is set to only that schema, so tables
+/// which aren't qualified with a schema are created in it. Each schema keeps track of its own
+/// migrations in its own `__diesel_schema_migrations` table. The schemas must already exist.
+///
+/// Each schema is migrated in a single transaction, which the change to the search path is local
+/// to, so the search path is restored afterwards even if a migration fails. If a migration fails,
+/// none of the migrations for that schema are committed, and the schemas after it are not
+/// migrated. If a pending migration opts out of running in a transaction,
+/// `MigrationError::NotRunInTransaction` is returned for that schema without running anything.
+///
+/// # Example
+///
+/// ```no_run
+/// # use diesel::migrations;
+/// # use diesel::prelude::*;
+/// # use std::io;
+/// # fn main() {
+/// # let connection = PgConnection::establish("postgres://localhost").unwrap();
+/// migrations::run_pending_migrations_for_schemas(&connection, &["tenant_a", "tenant_b"],
+///     &mut io::stdout()).unwrap();
+/// # }
+/// ```
+pub fn run_pending_migrations_for_schemas<Conn: Connection>(
+    conn: &Conn,
+    schemas: &[&str],
+    output: &mut Write,
+) -> Result<(), RunMigrationsError> {
+    for schema in schemas {
+        try!(writeln!(output, "Migrating schema {}", schema));
+        try!(Migrator::new(conn).with_output(&mut *output).run_pending_migrations_in_schema(schema));
+    }
+    Ok(())
+}
+
+/// Runs the given migrations which have not yet been run, with the search path set to the schema
+/// given by `config`. Must be called inside of a transaction.
+fn run_pending_in_schema<Conn: Connection>(
+    conn: &Conn,
+    config: &MigrationConfig,
+    all_migrations: Vec<Box<Migration>>,
+    hooks: &mut MigrationHooks,
+) -> Result<(), RunMigrationsError> {
+    with_migrations_lock(conn, config, || {
+        let pending_migrations = try!(pending_migrations_under_lock(conn, config, all_migrations));
+        try!(verify_run_in_transaction(&pending_migrations));
+
+        // The search path is set back explicitly as well, since when the caller is already in a
+        // transaction, a change which is local to ours would last until theirs ends.
+        let original_search_path = try!(sql::<types::VarChar>("SELECT current_setting('search_path')")
+            .get_result::<String>(conn));
+        if let Some(search_path) = config.search_path() {
+            try!(select(set_config("search_path", search_path, true)).get_result::<String>(conn));
+        }
+        try!(run_each_migration(conn, config, pending_migrations.into_iter(), hooks));
+        try!(select(set_config("search_path", original_search_path, true)).get_result::<String>(conn));
+        Ok(())
+    })
+}
+
+/// Runs all migrations in the given directory that have not yet been run, writing progress to
+/// `output`. This behaves the same as
+/// [`run_pending_migrations`](fn.run_pending_migrations.html), but is useful when the migrations
+/// directory can't be found automatically, such as when several crates share one migrations
+/// directory.
+pub fn run_pending_migrations_in_directory<Conn: Connection>(
+    conn: &Conn,
+    migrations_dir: &Path,
+    output: &mut Write,
+) -> Result<(), RunMigrationsError> {
+    Migrator::new(conn).with_directory(migrations_dir).with_output(output).run_pending_migrations()
+}
+
+/// Runs all migrations in the given directories that have not yet been run, writing progress to
+/// `output`. The migrations from every directory are merged and run in order of version. This is
+/// useful when a library ships its own migrations, which should be run alongside the
+/// application's. If more than one directory contains a migration with the same version, the one
+/// in the directory listed first is used.
+pub fn run_pending_migrations_in_directories<Conn: Connection>(
+    conn: &Conn,
+    migrations_dirs: &[&Path],
+    output: &mut Write,
+) -> Result<(), RunMigrationsError> {
+    run_pending_migrations_from_source(conn, migrations_dirs, output)
+}
+
+/// Runs all migrations that have not yet been run, from both the migrations directory and the
+/// given list. This allows migrations which are written in Rust, such as a data backfill, to be
+/// run alongside the SQL migrations. The two sets of migrations are merged and run in order of
+/// version. If a migration in the list has the same version as another migration, nothing is run,
+/// and `MigrationError::DuplicateMigrationVersion` is returned.
+///
+/// Migrations which are not in the migrations directory cannot be reverted with
+/// [`revert_latest_migration`](fn.revert_latest_migration.html).
+///
+/// # Example
+///
+/// ```no_run
+/// # use diesel::connection::SimpleConnection;
+/// # use diesel::migrations::{self, Migration, RunMigrationsError};
+/// # use diesel::prelude::*;
+/// struct BackfillHairColor;
+///
+/// impl Migration for BackfillHairColor {
+///     fn version(&self) -> &str {
+///         "20160214000000"
+///     }
+///
+///     fn run(&self, conn: &SimpleConnection) -> Result<(), RunMigrationsError> {
+///         try!(conn.batch_execute("UPDATE users SET hair_color = 'Unknown' WHERE hair_color IS NULL"));
+///         Ok(())
+///     }
+///
+///     fn revert(&self, _conn: &SimpleConnection) -> Result<(), RunMigrationsError> {
+///         Ok(())
+///     }
+/// }
+///
+/// # fn main() {
+/// # let connection = PgConnection::establish("postgres://localhost").unwrap();
+/// migrations::run_migrations(&connection, vec![Box::new(BackfillHairColor) as Box<Migration>])
+///     .unwrap();
+/// # }
+/// ```
+pub fn run_migrations<Conn: Connection>(conn: &Conn, migrations: Vec<Box<Migration>>)
+    -> Result<(), RunMigrationsError>
+{
+    Migrator::new(conn).run_migrations(migrations)
+}
+
+/// Runs all migrations that have not yet been run, whose version is less than or equal to
+/// `target_version`. Later migrations are left pending. This is useful for rolling out schema
+/// changes in stages, or for testing against a partially migrated schema. Returns
+/// `MigrationError::UnknownMigrationVersion` if there is no migration with the given version.
+pub fn run_migrations_until<Conn: Connection>(conn: &Conn, target_version: &str)
+    -> Result<(), RunMigrationsError>
+{
+    Migrator::new(conn).run_migrations_until(target_version)
+}
+
+/// Runs all of the given embedded migrations which have not yet been run. This is called by the
+/// `run` and `run_with_output` functions generated by `embed_migrations!`, which should be used
+/// instead of calling this directly.
+#[doc(hidden)]
+pub fn run_pending_embedded_migrations<Conn: Connection>(
+    conn: &Conn,
+    migrations: &[EmbeddedMigration],
+    output: &mut Write,
+) -> Result<(), RunMigrationsError> {
+    run_pending_migrations_from_source(conn, migrations, output)
+}
+
+/// Runs all migrations from the given source which have not yet been run, in order of version,
+/// writing progress to `output`. See [`MigrationSource`](trait.MigrationSource.html).
+pub fn run_pending_migrations_from_source<Conn, S: ?Sized>(conn: &Conn, source: &S, output: &mut Write)
+    -> Result<(), RunMigrationsError> where
+        Conn: Connection,
+        S: MigrationSource,
+{
+    Migrator::new(conn).with_source(source).with_output(output).run_pending_migrations()
+}
+
+fn run_pending<Conn: Connection>(
+    conn: &Conn,
+    config: &MigrationConfig,
+    all_migrations: Vec<Box<Migration>>,
+    hooks: &mut MigrationHooks,
+) -> Result<(), RunMigrationsError> {
+    with_migrations_lock(conn, config, || {
+        let pending_migrations = try!(pending_migrations_under_lock(conn, config, all_migrations));
+        run_each_migration(conn, config, pending_migrations.into_iter(), hooks)
+    })
+}
+
+fn run_pending_atomically<Conn: Connection>(
+    conn: &Conn,
+    config: &MigrationConfig,
+    all_migrations: Vec<Box<Migration>>,
+    hooks: &mut MigrationHooks,
+) -> Result<(), RunMigrationsError> {
+    if !Conn::Backend::supports_transactional_ddl() {
+        return Err(TransactionalDdlNotSupported.into());
+    }
+    conn.transaction(|| {
+        with_migrations_lock(conn, config, || {
+            let pending_migrations = try!(pending_migrations_under_lock(conn, config, all_migrations));
+            try!(verify_run_in_transaction(&pending_migrations));
+            run_each_migration(conn, config, pending_migrations.into_iter(), hooks)
+        })
+    }).map_err(|e| e.into())
+}
+
+/// Returns the given migrations which have not yet been run, after checking that none of the
+/// migrations which have been run have changed. Must be called while holding the migrations lock,
+/// so that another process can't run the same migrations concurrently.
+fn pending_migrations_under_lock<Conn: Connection>(
+    conn: &Conn,
+    config: &MigrationConfig,
+    all_migrations: Vec<Box<Migration>>,
+) -> Result<Vec<Box<Migration>>, RunMigrationsError> {
+    for migration in &all_migrations {
+        try!(config.version_format.validate(migration.version()));
+    }
+    try!(create_migrations_table_if_needed(conn, config));
+    try!(verify_checksums(conn, config, &all_migrations));
+    let already_run = try!(previously_run_migration_versions(conn, config));
     let pending_migrations = all_migrations.into_iter().filter(|m| {
         !already_run.contains(m.version())
-    });
-    run_migrations(conn, pending_migrations)
+    }).collect::<Vec<_>>();
+    if !config.allow_out_of_order {
+        try!(verify_in_order(&already_run, &pending_migrations));
+    }
+    Ok(pending_migrations)
 }
 
 /// Reverts the last migration that was run. Returns the version that was reverted. Returns an
@@ -103,29 +418,130 @@ pub fn run_pending_migrations<Conn: Connection>(conn: &Conn) -> Result<(), RunMi
 /// See the [module level documentation](index.html) for information on how migrations should be
 /// structured, and where Diesel will look for them by default.
 pub fn revert_latest_migration<Conn: Connection>(conn: &Conn) -> Result<String, RunMigrationsError> {
-    try!(create_schema_migrations_table_if_needed(conn));
-    let latest_migration_version = try!(latest_run_migration_version(conn));
-    revert_migration_with_version(conn, &latest_migration_version)
-        .map(|_| latest_migration_version)
+    revert_latest_migration_with_output(conn, &mut io::stdout())
+}
+
+/// Reverts the last migration that was run, writing progress to `output` instead of stdout.
+/// Returns the version that was reverted. See
+/// [`revert_latest_migration`](fn.revert_latest_migration.html).
+pub fn revert_latest_migration_with_output<Conn: Connection>(conn: &Conn, output: &mut Write)
+    -> Result<String, RunMigrationsError>
+{
+    Migrator::new(conn).with_output(output).revert_latest_migration()
+}
+
+/// Reverts the last migration that was run, calling the given hooks around it instead of writing
+/// progress to stdout. Returns the version that was reverted. See
+/// [`revert_latest_migration`](fn.revert_latest_migration.html).
+pub fn revert_latest_migration_with_hooks<Conn: Connection>(conn: &Conn, hooks: &mut MigrationHooks)
+    -> Result<String, RunMigrationsError>
+{
+    Migrator::new(conn).with_hooks(hooks).revert_latest_migration()
+}
+
+/// Reverts the last migration that was run, using the table given by `config` to find it,
+/// instead of `__diesel_schema_migrations`. Returns the version that was reverted. See
+/// [`revert_latest_migration`](fn.revert_latest_migration.html).
+pub fn revert_latest_migration_with_config<Conn: Connection>(conn: &Conn, config: &MigrationConfig)
+    -> Result<String, RunMigrationsError>
+{
+    Migrator::new(conn).with_config(config.clone()).revert_latest_migration()
+}
+
+/// Reverts the last migration that was run, using the migrations in the given directory and
+/// writing progress to `output`. Returns the version that was reverted. See
+/// [`run_pending_migrations_in_directory`](fn.run_pending_migrations_in_directory.html).
+pub fn revert_latest_migration_in_directory<Conn: Connection>(
+    conn: &Conn,
+    migrations_dir: &Path,
+    output: &mut Write,
+) -> Result<String, RunMigrationsError> {
+    Migrator::new(conn).with_directory(migrations_dir).with_output(output).revert_latest_migration()
+}
+
+/// Reverts the last migration that was run, using the migrations in the given directories and
+/// writing progress to `output`. Returns the version that was reverted. See
+/// [`run_pending_migrations_in_directories`](fn.run_pending_migrations_in_directories.html).
+pub fn revert_latest_migration_in_directories<Conn: Connection>(
+    conn: &Conn,
+    migrations_dirs: &[&Path],
+    output: &mut Write,
+) -> Result<String, RunMigrationsError> {
+    Migrator::new(conn).with_source(migrations_dirs).with_output(output).revert_latest_migration()
+}
+
+fn revert_latest<Conn: Connection>(
+    conn: &Conn,
+    config: &MigrationConfig,
+    all_migrations: Vec<Box<Migration>>,
+    hooks: &mut MigrationHooks,
+) -> Result<String, RunMigrationsError> {
+    with_migrations_lock(conn, config, || {
+        try!(create_migrations_table_if_needed(conn, config));
+        let latest_migration_version = try!(latest_run_migration_version(conn, config)
+            .and_then(|version| version.ok_or(::result::Error::NotFound)));
+        let migration = try!(migration_with_version(all_migrations, &latest_migration_version));
+        revert_migration(conn, config, &*migration, hooks)
+            .map(|_| latest_migration_version)
+    })
+}
+
+/// Runs the given function while holding an advisory lock, so that only one process can run or
+/// revert migrations at a time. Any other process will wait for the lock before checking which
+/// migrations have been run. When called inside of a transaction, the lock is held until that
+/// transaction ends.
+fn with_migrations_lock<Conn, T, F>(conn: &Conn, config: &MigrationConfig, f: F)
+    -> Result<T, RunMigrationsError> where
+        Conn: Connection,
+        F: FnOnce() -> Result<T, RunMigrationsError>,
+{
+    if conn.get_transaction_depth() > 0 {
+        try!(conn.execute(&config.transaction_lock_sql()));
+        f()
+    } else {
+        try!(conn.execute(&config.lock_sql()));
+        let result = f();
+        let unlock_result = conn.execute(&config.unlock_sql());
+        let value = try!(result);
+        try!(unlock_result);
+        Ok(value)
+    }
+}
+
+/// Reverts the last migration that was run, and then runs it again. Both steps happen in a single
+/// transaction, so the migration is left applied if either of them fails. Returns the version that
+/// was redone. This is useful when writing a new migration, to check that `down.sql` cleanly
+/// reverts `up.sql`.
+///
+/// A migration which opts out of running in a transaction can't be redone this way, and
+/// `MigrationError::NotRunInTransaction` is returned without reverting it. Revert it and run it
+/// again separately instead.
+pub fn redo_latest_migration<Conn: Connection>(conn: &Conn) -> Result<String, RunMigrationsError> {
+    Migrator::new(conn).redo_latest_migration()
 }
 
 #[doc(hidden)]
 pub fn revert_migration_with_version<Conn: Connection>(conn: &Conn, ver: &str) -> Result<(), RunMigrationsError> {
-    migration_with_version(ver)
-        .map_err(|e| e.into())
-        .and_then(|m| revert_migration(conn, m))
+    Migrator::new(conn).revert_migration_with_version(ver)
 }
 
 #[doc(hidden)]
 pub fn run_migration_with_version<Conn: Connection>(conn: &Conn, ver: &str) -> Result<(), RunMigrationsError> {
-    migration_with_version(ver)
-        .map_err(|e| e.into())
-        .and_then(|m| run_migration(conn, m))
+    Migrator::new(conn).run_migration_with_version(ver)
+}
+
+/// Records the migration with the given version as having been run, without running it. This is
+/// useful when adopting Diesel on an existing database, whose schema already matches the first few
+/// migrations. Does nothing if the migration has already been run. Returns
+/// `MigrationError::UnknownMigrationVersion` if there is no migration with the given version in
+/// the migrations directory.
+pub fn mark_migration_as_applied<Conn: Connection>(conn: &Conn, ver: &str) -> Result<(), RunMigrationsError> {
+    Migrator::new(conn).mark_migration_as_applied(ver)
 }
 
-fn migration_with_version(ver: &str) -> Result<Box<Migration>, MigrationError> {
-    let migrations_dir = try!(find_migrations_directory());
-    let all_migrations = try!(migrations_in_directory(&migrations_dir));
+fn migration_with_version(all_migrations: Vec<Box<Migration>>, ver: &str)
+    -> Result<Box<Migration>, MigrationError>
+{
     let migration = all_migrations.into_iter().find(|m| {
         m.version() == ver
     });
@@ -135,108 +551,439 @@ fn migration_with_version(ver: &str) -> Result<Box<Migration>, MigrationError> {
     }
 }
 
+/// Returns the migrations in the migrations directory which have not yet been run, in order of
+/// version. Unlike [`run_pending_migrations`](fn.run_pending_migrations.html), this does not
+/// write anything to the database, so it can be used to check that the schema is up to date
+/// before starting an application.
+pub fn pending_migrations<Conn: Connection>(conn: &Conn) -> Result<Vec<MigrationInfo>, RunMigrationsError> {
+    Migrator::new(conn).pending_migrations()
+}
+
+/// Returns the migrations which have been run, in order of version, along with when they were run,
+/// how long they took, and who ran them. Migrations which were run by older versions of Diesel
+/// won't have a duration, or who ran them.
+///
+/// The migrations directory is only used to find the name of each migration. If there is no
+/// migrations directory, such as when an application is deployed without one, the migrations are
+/// still returned, without their names.
+pub fn applied_migrations<Conn>(conn: &Conn) -> Result<Vec<MigrationInfo>, RunMigrationsError> where
+    Conn: Connection,
+    AppliedMigrationRow: Queryable<AppliedMigrationSqlType, Conn::Backend>,
+{
+    Migrator::new(conn).applied_migrations()
+}
+
+/// Returns the status of every migration which is either in the migrations directory, or has been
+/// run, in order of version. This shows both ways in which the database and the migrations
+/// directory can disagree: migrations which have been run but are no longer in the directory, and
+/// migrations in the directory which have not been run.
+pub fn migration_status<Conn>(conn: &Conn) -> Result<Vec<MigrationStatus>, RunMigrationsError> where
+    Conn: Connection,
+    AppliedMigrationRow: Queryable<AppliedMigrationSqlType, Conn::Backend>,
+{
+    Migrator::new(conn).migration_status()
+}
+
+#[doc(hidden)]
+pub type AppliedMigrationSqlType = (types::VarChar, types::Timestamp,
+    types::Nullable<types::BigInt>, types::Nullable<types::VarChar>);
+#[doc(hidden)]
+pub type AppliedMigrationRow = (String, PgTimestamp, Option<i64>, Option<String>);
+
+/// Returns `true` if there are any migrations in the migrations directory which have not yet
+/// been run. See [`pending_migrations`](fn.pending_migrations.html).
+pub fn any_pending_migrations<Conn: Connection>(conn: &Conn) -> Result<bool, RunMigrationsError> {
+    Migrator::new(conn).any_pending_migrations()
+}
+
+/// Returns the version of the latest migration which has been run, or `None` if no migrations
+/// have been run. This can be used to log the version of the schema when an application starts,
+/// or to check that the database is compatible with the code.
+pub fn database_schema_version<Conn: Connection>(conn: &Conn) -> QueryResult<Option<String>> {
+    Migrator::new(conn).database_schema_version()
+}
+
+/// Writes a SQL script which runs all migrations that have not yet been run, instead of running
+/// them. The script is written in order of version. It creates the schema migrations table if
+/// needed, and records each migration in it, so running the script has the same effect as calling
+/// [`run_pending_migrations`](fn.run_pending_migrations.html). Each migration is wrapped in its own
+/// transaction, unless it opts out of running in a transaction.
+///
+/// This is intended for environments where schema changes must be reviewed and applied by someone
+/// else. The connection is only used to find which migrations have already been run, and nothing
+/// is written to the database.
+pub fn write_pending_migrations_script<Conn, W>(conn: &Conn, out: &mut W)
+    -> Result<(), RunMigrationsError> where
+        Conn: Connection,
+        W: Write,
+{
+    Migrator::new(conn).write_pending_migrations_script(out)
+}
+
+/// Writes a SQL script which runs all migrations from the given source which have not yet been
+/// run. See [`write_pending_migrations_script`](fn.write_pending_migrations_script.html).
+pub fn write_pending_migrations_script_from_source<Conn, S: ?Sized, W>(conn: &Conn, source: &S, out: &mut W)
+    -> Result<(), RunMigrationsError> where
+        Conn: Connection,
+        S: MigrationSource,
+        W: Write,
+{
+    Migrator::new(conn).with_source(source).write_pending_migrations_script(out)
+}
+
 #[doc(hidden)]
 pub fn create_schema_migrations_table_if_needed<Conn: Connection>(conn: &Conn) -> QueryResult<usize> {
+    create_migrations_table_if_needed(conn, &MigrationConfig::default())
+}
+
+fn create_migrations_table_if_needed<Conn: Connection>(conn: &Conn, config: &MigrationConfig)
+    -> QueryResult<usize>
+{
     conn.silence_notices(|| {
-        conn.execute("CREATE TABLE IF NOT EXISTS __diesel_schema_migrations (
-            version VARCHAR PRIMARY KEY NOT NULL,
-            run_on TIMESTAMP NOT NULL DEFAULT NOW()
-        )")
+        conn.execute(&config.create_table_sql())
+    }).and_then(|_| {
+        let mut rows = 0;
+        for &(column, sql_type) in try!(missing_columns(conn, config)).iter() {
+            rows += try!(conn.execute(&config.add_column_sql(column, sql_type)));
+        }
+        Ok(rows)
     })
 }
 
-fn previously_run_migration_versions<Conn: Connection>(conn: &Conn) -> QueryResult<HashSet<String>> {
-    __diesel_schema_migrations.select(version)
+fn migrations_table_exists<Conn: Connection>(conn: &Conn, config: &MigrationConfig) -> QueryResult<bool> {
+    conn.execute(&config.table_exists_sql())
+        .map(|rows| rows != 0)
+}
+
+fn column_exists<Conn: Connection>(conn: &Conn, config: &MigrationConfig, column: &str) -> QueryResult<bool> {
+    conn.execute(&config.column_exists_sql(column))
+        .map(|rows| rows != 0)
+}
+
+/// Tables created by older versions of Diesel won't have the columns which have been added since.
+fn missing_columns<Conn: Connection>(conn: &Conn, config: &MigrationConfig)
+    -> QueryResult<Vec<(&'static str, &'static str)>>
+{
+    let mut missing = Vec::new();
+    for &(column, sql_type) in MigrationConfig::added_columns() {
+        if !try!(column_exists(conn, config, column)) {
+            missing.push((column, sql_type));
+        }
+    }
+    Ok(missing)
+}
+
+fn previously_run_migration_versions<Conn: Connection>(conn: &Conn, config: &MigrationConfig)
+    -> QueryResult<HashSet<String>>
+{
+    let query = format!("SELECT version FROM {}", config.qualified_table_name());
+    sql::<types::VarChar>(&query)
         .load(conn)
         .map(|r| r.collect())
 }
 
-fn latest_run_migration_version<Conn: Connection>(conn: &Conn) -> QueryResult<String> {
-    use ::expression::dsl::max;
-    __diesel_schema_migrations.select(max(version))
-        .first(conn)
+fn previously_run_migration_versions_if_any<Conn: Connection>(conn: &Conn, config: &MigrationConfig)
+    -> QueryResult<HashSet<String>>
+{
+    if try!(migrations_table_exists(conn, config)) {
+        previously_run_migration_versions(conn, config)
+    } else {
+        Ok(HashSet::new())
+    }
+}
+
+/// Returns `MigrationError::OutOfOrderMigrations`, listing the pending migrations which are older
+/// than the latest migration that has been run, if there are any.
+fn verify_in_order(already_run: &HashSet<String>, pending_migrations: &[Box<Migration>])
+    -> Result<(), MigrationError>
+{
+    let latest_run = match already_run.iter().max_by(|a, b| compare_versions(a, b)) {
+        Some(version) => version,
+        None => return Ok(()),
+    };
+    let out_of_order = pending_migrations.iter()
+        .map(|m| m.version())
+        .filter(|version| compare_versions(version, latest_run) == Ordering::Less)
+        .map(String::from)
+        .collect::<Vec<_>>();
+    if out_of_order.is_empty() {
+        Ok(())
+    } else {
+        Err(OutOfOrderMigrations(out_of_order))
+    }
+}
+
+/// Returns `MigrationError::DuplicateMigrationVersion` if more than one of the given migrations has
+/// the same version.
+fn verify_unique_versions(migrations: &[Box<Migration>]) -> Result<(), MigrationError> {
+    let mut versions = HashSet::new();
+    match migrations.iter().find(|m| !versions.insert(m.version())) {
+        Some(m) => Err(DuplicateMigrationVersion(m.version().into())),
+        None => Ok(()),
+    }
 }
 
-fn migrations_in_directory(path: &Path) -> Result<Vec<Box<Migration>>, MigrationError> {
-    use self::migration::migration_from;
+/// Returns `MigrationError::NotRunInTransaction` for the first of the given migrations which opts
+/// out of running in a transaction, if there is one. Used before running migrations inside of a
+/// single transaction which the migration would otherwise silently be part of.
+fn verify_run_in_transaction(migrations: &[Box<Migration>]) -> Result<(), MigrationError> {
+    match migrations.iter().find(|m| !m.run_in_transaction()) {
+        Some(m) => Err(NotRunInTransaction(m.version().into())),
+        None => Ok(()),
+    }
+}
 
-    try!(path.read_dir())
-        .filter_map(|entry| {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(e) => return Some(Err(e.into())),
-            };
-            if !entry.file_name().to_string_lossy().starts_with(".") {
-                Some(migration_from(entry.path()))
-            } else {
-                None
+/// Returns `MigrationError::ChecksumMismatch` if the `up.sql` of any migration which has already
+/// been run has changed since it was run. Migrations which were run before checksums were
+/// recorded, or which aren't written in SQL, are not checked.
+fn verify_checksums<Conn: Connection>(
+    conn: &Conn,
+    config: &MigrationConfig,
+    migrations: &[Box<Migration>],
+) -> Result<(), RunMigrationsError> {
+    let query = format!("SELECT version, checksum FROM {} WHERE checksum IS NOT NULL",
+                        config.qualified_table_name());
+    let recorded_checksums = try!(sql::<(types::VarChar, types::VarChar)>(&query)
+        .load::<(String, String)>(conn))
+        .collect::<HashMap<_, _>>();
+    for migration in migrations {
+        let recorded_checksum = recorded_checksums.get(migration.version());
+        let checksum = migration::checksum(&**migration);
+        if let (Some(recorded), Some(current)) = (recorded_checksum, checksum) {
+            if *recorded != current {
+                return Err(ChecksumMismatch(migration.version().into()).into());
             }
-        }).collect()
+        }
+    }
+    Ok(())
 }
 
-fn run_migrations<T, Conn: Connection>(conn: &Conn, migrations: T)
-    -> Result<(), RunMigrationsError> where
-        T: Iterator<Item=Box<Migration>>
+fn latest_run_migration_version<Conn: Connection>(conn: &Conn, config: &MigrationConfig)
+    -> QueryResult<Option<String>>
 {
-    for migration in migrations {
-        try!(run_migration(conn, migration));
+    let mut versions = try!(previously_run_migration_versions_if_any(conn, config))
+        .into_iter().collect::<Vec<_>>();
+    versions.sort_by(|a, b| compare_versions(a, b));
+    Ok(versions.pop())
+}
+
+/// Returns the version and path of each migration in the given directory, sorted by version. Used
+/// by `embed_migrations!` to find the migrations to embed. Migrations can be grouped into
+/// subdirectories, such as `migrations/2016/20160107082941_create_posts`. Any directory which
+/// doesn't contain `up.sql` or `down.sql` is treated as a group, and searched for migrations.
+#[doc(hidden)]
+pub fn migration_paths_in_directory(path: &Path) -> Result<Vec<(String, PathBuf)>, MigrationError> {
+    let mut migrations = Vec::new();
+    try!(collect_migration_paths(path, &mut migrations));
+    migrations.sort_by(|a, b| compare_versions(&a.0, &b.0));
+    Ok(migrations)
+}
+
+fn collect_migration_paths(path: &Path, migrations: &mut Vec<(String, PathBuf)>) -> Result<(), MigrationError> {
+    use self::migration::migration_from;
+
+    for entry in try!(path.read_dir()) {
+        let entry = try!(entry);
+        if entry.file_name().to_string_lossy().starts_with(".") {
+            continue;
+        }
+        let entry_path = entry.path();
+        if is_migration_group(&entry_path) {
+            try!(collect_migration_paths(&entry_path, migrations));
+        } else {
+            let migration = try!(migration_from(entry_path.clone()));
+            migrations.push((migration.version().to_string(), entry_path));
+        }
     }
     Ok(())
 }
 
-fn run_migration<Conn: Connection>(conn: &Conn, migration: Box<Migration>)
-    -> Result<(), RunMigrationsError>
+fn is_migration_group(path: &Path) -> bool {
+    path.is_dir() && !path.join("up.sql").exists() && !path.join("down.sql").exists()
+}
+
+/// Returns whether the migration in the given directory should be run in a transaction, based on
+/// its `metadata.toml`. Used by `embed_migrations!`.
+#[doc(hidden)]
+pub fn migration_runs_in_transaction(path: &Path) -> Result<bool, MigrationError> {
+    migration::run_in_transaction_from_path(path)
+}
+
+/// Returns whether the migration in the given directory can be reverted. Used by
+/// `embed_migrations!`.
+#[doc(hidden)]
+pub fn migration_is_reversible(path: &Path) -> Result<bool, MigrationError> {
+    migration::migration_from(path.to_path_buf()).map(|m| m.reversible())
+}
+
+fn run_each_migration<T, Conn: Connection>(
+    conn: &Conn,
+    config: &MigrationConfig,
+    migrations: T,
+    hooks: &mut MigrationHooks,
+) -> Result<(), RunMigrationsError> where
+    T: Iterator<Item=Box<Migration>>,
 {
-    conn.transaction(|| {
-        println!("Running migration {}", migration.version());
-        try!(migration.run(conn));
-        try!(::insert(&NewMigration(migration.version()))
-             .into(__diesel_schema_migrations)
-             .execute(conn));
+    for migration in migrations {
+        try!(run_migration(conn, config, &*migration, hooks));
+    }
+    Ok(())
+}
+
+fn run_migration<Conn: Connection>(
+    conn: &Conn,
+    config: &MigrationConfig,
+    migration: &Migration,
+    hooks: &mut MigrationHooks,
+) -> Result<(), RunMigrationsError> {
+    let start = Instant::now();
+    try!(transaction_if_needed(conn, migration, || {
+        try!(hooks.before_run(migration.version()));
+        let run_start = Instant::now();
+        try!(migration.run(conn).map_err(|e| e.for_migration(migration.version())));
+        let duration = run_start.elapsed();
+        let checksum = migration::checksum(migration);
+        let insert_version = config.insert_version_sql(migration.version(), checksum.as_ref().map(|c| &**c), Some(duration));
+        try!(conn.execute(&insert_version));
         Ok(())
-    }).map_err(|e| e.into())
+    }).map_err(|e| mark_partially_applied::<Conn>(e, migration)));
+    hooks.after_run(migration.version(), start.elapsed())
 }
 
-fn revert_migration<Conn: Connection>(conn: &Conn, migration: Box<Migration>)
-    -> Result<(), RunMigrationsError>
-{
-    try!(conn.transaction(|| {
-        println!("Rolling back migration {}", migration.version());
-        try!(migration.revert(conn));
-        let target = __diesel_schema_migrations.filter(version.eq(migration.version()));
-        try!(::delete(target).execute(conn));
+fn revert_migration<Conn: Connection>(
+    conn: &Conn,
+    config: &MigrationConfig,
+    migration: &Migration,
+    hooks: &mut MigrationHooks,
+) -> Result<(), RunMigrationsError> {
+    if !migration.reversible() {
+        return Err(Irreversible(migration.version().into()).into());
+    }
+    let start = Instant::now();
+    try!(transaction_if_needed(conn, migration, || {
+        try!(hooks.before_revert(migration.version()));
+        try!(migration.revert(conn).map_err(|e| e.for_migration(migration.version())));
+        try!(conn.execute(&config.delete_version_sql(migration.version())));
         Ok(())
-    }));
-    Ok(())
+    }).map_err(|e| mark_partially_applied::<Conn>(e, migration)));
+    hooks.after_revert(migration.version(), start.elapsed())
 }
 
-/// Returns the directory containing migrations. Will look at for
-/// $PWD/migrations. If it is not found, it will search the parents of the
-/// current directory, until it reaches the root directory.  Returns
+/// A migration which fails can only be assumed to have been rolled back if it was run in a
+/// transaction, on a backend which rolls back schema changes.
+fn mark_partially_applied<Conn: Connection>(error: RunMigrationsError, migration: &Migration)
+    -> RunMigrationsError
+{
+    if migration.run_in_transaction() && Conn::Backend::supports_transactional_ddl() {
+        error
+    } else {
+        error.partially_applied()
+    }
+}
+
+fn transaction_if_needed<Conn, F>(conn: &Conn, migration: &Migration, f: F)
+    -> Result<(), RunMigrationsError> where
+        Conn: Connection,
+        F: FnOnce() -> Result<(), RunMigrationsError>,
+{
+    if migration.run_in_transaction() {
+        conn.transaction(f).map_err(|e| e.into())
+    } else {
+        f()
+    }
+}
+
+/// Returns the directory containing migrations. If the `MIGRATION_DIRECTORY` environment variable
+/// is set, that directory is used. Otherwise, if there is a `diesel.toml` in the current directory
+/// or any of its parents which gives a migrations directory, that is used. Otherwise, this will
+/// look for $PWD/migrations. If it is not found, it will search the parents of the current
+/// directory, until it reaches the root directory. Returns
 /// `MigrationError::MigrationDirectoryNotFound` if no directory is found.
 pub fn find_migrations_directory() -> Result<PathBuf, MigrationError> {
-    search_for_migrations_directory(&try!(env::current_dir()))
+    let current_dir = try!(env::current_dir());
+    match env::var_os("MIGRATION_DIRECTORY") {
+        Some(dir) => existing_directory(current_dir.join(dir)),
+        None => search_for_migrations_directory(&current_dir),
+    }
+}
+
+#[doc(hidden)]
+pub fn search_for_migrations_directory(path: &Path) -> Result<PathBuf, MigrationError> {
+    match try!(configured_migrations_directory(path)) {
+        Some(dir) => existing_directory(dir),
+        None => search_for_directory(path, "migrations"),
+    }
 }
 
-fn search_for_migrations_directory(path: &Path) -> Result<PathBuf, MigrationError> {
-    let migration_path = path.join("migrations");
-    if migration_path.is_dir() {
-        Ok(migration_path)
+fn search_for_directory(path: &Path, name: &str) -> Result<PathBuf, MigrationError> {
+    let directory_path = path.join(name);
+    if directory_path.is_dir() {
+        Ok(directory_path)
     } else {
-        path.parent().map(search_for_migrations_directory)
+        path.parent().map(|parent| search_for_directory(parent, name))
             .unwrap_or(Err(MigrationError::MigrationDirectoryNotFound))
     }
 }
 
+/// Returns the migrations directory given by the nearest `diesel.toml` in `path` or its parents,
+/// if there is one and it gives a directory.
+fn configured_migrations_directory(path: &Path) -> Result<Option<PathBuf>, MigrationError> {
+    let config_path = path.join("diesel.toml");
+    if config_path.is_file() {
+        let mut config = String::new();
+        try!(try!(File::open(&config_path)).read_to_string(&mut config));
+        let dir = try!(parse_migrations_directory(&config_path, &config));
+        Ok(dir.map(|dir| path.join(dir)))
+    } else {
+        path.parent().map(configured_migrations_directory).unwrap_or(Ok(None))
+    }
+}
+
+/// Finds `dir` in the `[migrations_directory]` section. Anything else in the file is ignored, so
+/// that it can be used to configure other things.
+fn parse_migrations_directory(config_path: &Path, config: &str) -> Result<Option<String>, MigrationError> {
+    let mut section = "";
+    for line in config.lines() {
+        let line = line.split("#").next().unwrap_or("").trim();
+        if line.starts_with("[") && line.ends_with("]") {
+            section = line[1..line.len() - 1].trim();
+            continue;
+        }
+        let mut parts = line.splitn(2, "=").map(|part| part.trim());
+        if let (Some("dir"), Some(value)) = (parts.next(), parts.next()) {
+            if section != "migrations_directory" {
+                continue;
+            }
+            if value.len() >= 2 && value.starts_with("\"") && value.ends_with("\"") {
+                return Ok(Some(value[1..value.len() - 1].into()));
+            } else {
+                return Err(MigrationError::InvalidConfigFile(config_path.to_path_buf()));
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn existing_directory(path: PathBuf) -> Result<PathBuf, MigrationError> {
+    if path.is_dir() {
+        Ok(path)
+    } else {
+        Err(MigrationError::MigrationDirectoryNotFound)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate tempdir;
 
     use super::*;
-    use super::search_for_migrations_directory;
+    use super::{parse_migrations_directory, search_for_migrations_directory};
+    use super::{verify_run_in_transaction, verify_unique_versions};
 
     use self::tempdir::TempDir;
     use std::fs;
+    use std::io::Write;
+    use std::path::Path;
 
     #[test]
     fn migration_directory_not_found_if_no_migration_dir_exists() {
@@ -269,4 +1016,88 @@ mod tests {
 
         assert_eq!(Ok(migrations_path), search_for_migrations_directory(&child_path));
     }
+    #[test]
+    fn migration_directory_can_be_given_by_diesel_toml() {
+        let dir = TempDir::new("diesel").unwrap();
+        let temp_path = dir.path().canonicalize().unwrap();
+        let child_path = temp_path.join("child");
+        let configured_path = temp_path.join("db").join("migrations");
+
+        fs::create_dir(&child_path).unwrap();
+        fs::create_dir(child_path.join("migrations")).unwrap();
+        fs::create_dir_all(&configured_path).unwrap();
+        let mut config = fs::File::create(temp_path.join("diesel.toml")).unwrap();
+        config.write_all(b"[migrations_directory]\ndir = \"db/migrations\"\n").unwrap();
+
+        assert_eq!(Ok(temp_path.join("db/migrations")), search_for_migrations_directory(&child_path));
+    }
+
+    #[test]
+    fn diesel_toml_without_a_migrations_directory_is_ignored() {
+        let dir = TempDir::new("diesel").unwrap();
+        let temp_path = dir.path().canonicalize().unwrap();
+        let migrations_path = temp_path.join("migrations");
+
+        fs::create_dir(&migrations_path).unwrap();
+        fs::File::create(temp_path.join("diesel.toml")).unwrap();
+
+        assert_eq!(Ok(migrations_path), search_for_migrations_directory(&temp_path));
+    }
+
+    #[test]
+    fn parsing_diesel_toml() {
+        let path = Path::new("diesel.toml");
+        assert_eq!(Ok(Some("db/migrations".into())),
+            parse_migrations_directory(path, "[migrations_directory]\ndir = \"db/migrations\" # comment"));
+        assert_eq!(Ok(None), parse_migrations_directory(path, "[print_schema]\ndir = \"src\""));
+        assert_eq!(Ok(None), parse_migrations_directory(path, ""));
+        assert_eq!(Err(MigrationError::InvalidConfigFile(path.to_path_buf())),
+            parse_migrations_directory(path, "[migrations_directory]\ndir = db"));
+    }
+    #[test]
+    fn migrations_can_be_grouped_into_subdirectories() {
+        let dir = TempDir::new("diesel").unwrap();
+        let group_path = dir.path().join("2016");
+        for folder in &[dir.path().join("20151219180527_create_users"),
+                        group_path.join("20160107082941_create_posts")] {
+            fs::create_dir_all(folder).unwrap();
+            fs::File::create(folder.join("up.sql")).unwrap();
+            fs::File::create(folder.join("down.sql")).unwrap();
+        }
+
+        let migrations = migration_paths_in_directory(dir.path()).unwrap();
+
+        assert_eq!(vec![
+            ("20151219180527".to_string(), dir.path().join("20151219180527_create_users")),
+            ("20160107082941".to_string(), group_path.join("20160107082941_create_posts")),
+        ], migrations);
+    }
+
+    #[test]
+    fn migrations_which_opt_out_of_transactions_cant_be_run_in_one() {
+        let migration = |version, run_in_transaction| Box::new(EmbeddedMigration {
+            version: version,
+            up_sql: "",
+            down_sql: "",
+            run_in_transaction: run_in_transaction,
+        }) as Box<Migration>;
+
+        assert_eq!(Ok(()), verify_run_in_transaction(&[migration("1", true), migration("2", true)]));
+        assert_eq!(Err(MigrationError::NotRunInTransaction("2".into())),
+            verify_run_in_transaction(&[migration("1", true), migration("2", false), migration("3", false)]));
+    }
+
+    #[test]
+    fn migrations_must_have_unique_versions() {
+        let migration = |version| Box::new(EmbeddedMigration {
+            version: version,
+            up_sql: "",
+            down_sql: "",
+            run_in_transaction: true,
+        }) as Box<Migration>;
+
+        assert_eq!(Ok(()), verify_unique_versions(&[migration("1"), migration("2")]));
+        assert_eq!(Err(MigrationError::DuplicateMigrationVersion("2".into())),
+            verify_unique_versions(&[migration("2"), migration("1"), migration("2")]));
+    }
 }
@@ -0,0 +1,140 @@
+use ::expression::dsl::sql;
+use ::query_dsl::*;
+use super::{run_pending_migrations_with_output, MigrationConfig, RunMigrationsError};
+use {Connection, Queryable, types};
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+#[doc(hidden)]
+pub type DumpColumnSqlType = (types::VarChar, types::VarChar, types::VarChar, types::Bool,
+    types::Nullable<types::VarChar>);
+#[doc(hidden)]
+pub type DumpColumnRow = (String, String, String, bool, Option<String>);
+
+/// Runs all migrations that have not yet been run, writing progress to `output`, and then calls
+/// `after` with the connection once they have all been run successfully. `after` is not called if
+/// any migration fails.
+///
+/// This is intended for keeping a snapshot of the schema in sync with the migrations, so that
+/// changes to the schema can be seen in code review. See
+/// [`dump_schema_to_file`](fn.dump_schema_to_file.html).
+///
+/// # Example
+///
+/// ```no_run
+/// # use diesel::migrations;
+/// # use diesel::prelude::*;
+/// # use std::io;
+/// # fn main() {
+/// # let connection = PgConnection::establish("postgres://localhost").unwrap();
+/// migrations::run_pending_migrations_and_then(&connection, &mut io::stdout(), |conn| {
+///     migrations::dump_schema_to_file(conn, "schema.sql")
+/// }).unwrap();
+/// # }
+/// ```
+pub fn run_pending_migrations_and_then<Conn, F>(conn: &Conn, output: &mut Write, after: F)
+    -> Result<(), RunMigrationsError> where
+        Conn: Connection,
+        F: FnOnce(&Conn) -> Result<(), RunMigrationsError>,
+{
+    try!(run_pending_migrations_with_output(conn, output));
+    after(conn)
+}
+
+/// Writes the tables, constraints and indexes in the current schema of the database to the file
+/// at `path` as SQL, replacing it if it exists. See
+/// [`dump_schema`](fn.dump_schema.html).
+pub fn dump_schema_to_file<Conn, P>(conn: &Conn, path: P) -> Result<(), RunMigrationsError> where
+    Conn: Connection,
+    P: AsRef<Path>,
+    DumpColumnRow: Queryable<DumpColumnSqlType, Conn::Backend>,
+{
+    let mut file = try!(File::create(path));
+    dump_schema(conn, &mut file)
+}
+
+/// Writes the tables, constraints and indexes in the current schema of the database to `out` as
+/// SQL. Everything is written in order of name, so the output only changes when the schema does.
+/// The tables which keep track of migrations and seeds are left out.
+///
+/// This is meant to be read by people reviewing changes to the schema, rather than to recreate
+/// the database. Views, functions, triggers and types are not included.
+pub fn dump_schema<Conn>(conn: &Conn, out: &mut Write) -> Result<(), RunMigrationsError> where
+    Conn: Connection,
+    DumpColumnRow: Queryable<DumpColumnSqlType, Conn::Backend>,
+{
+    let ignored_tables = [MigrationConfig::default().table_name, MigrationConfig::seeds().table_name];
+    let is_dumped = |table: &str| !ignored_tables.iter().any(|t| t == table);
+
+    try!(writeln!(out, "-- This file is generated by Diesel after migrations are run. Do not edit it by hand."));
+    let columns = try!(sql::<DumpColumnSqlType>(COLUMNS_QUERY).load::<DumpColumnRow>(conn))
+        .filter(|column| is_dumped(&column.0))
+        .collect::<Vec<_>>();
+    let mut current_table = None;
+    for &(ref table, ref name, ref sql_type, not_null, ref default) in &columns {
+        if current_table == Some(table) {
+            try!(write!(out, ",\n"));
+        } else {
+            if current_table.is_some() {
+                try!(writeln!(out, "\n);"));
+            }
+            try!(write!(out, "\nCREATE TABLE {} (\n", table));
+            current_table = Some(table);
+        }
+        try!(write!(out, "    {} {}", name, sql_type));
+        if not_null {
+            try!(write!(out, " NOT NULL"));
+        }
+        if let Some(ref default) = *default {
+            try!(write!(out, " DEFAULT {}", default));
+        }
+    }
+    if current_table.is_some() {
+        try!(writeln!(out, "\n);"));
+    }
+
+    let constraints = try!(sql::<(types::VarChar, types::VarChar, types::VarChar)>(CONSTRAINTS_QUERY)
+        .load::<(String, String, String)>(conn));
+    for (table, name, definition) in constraints.filter(|c| is_dumped(&c.0)) {
+        try!(writeln!(out, "\nALTER TABLE {} ADD CONSTRAINT {} {};", table, name, definition));
+    }
+
+    let indexes = try!(sql::<(types::VarChar, types::VarChar)>(INDEXES_QUERY)
+        .load::<(String, String)>(conn));
+    for (_, definition) in indexes.filter(|i| is_dumped(&i.0)) {
+        try!(writeln!(out, "\n{};", definition));
+    }
+    Ok(())
+}
+
+const COLUMNS_QUERY: &'static str = "\
+SELECT c.relname::varchar, a.attname::varchar, format_type(a.atttypid, a.atttypmod)::varchar,
+    a.attnotnull, pg_get_expr(d.adbin, d.adrelid)::varchar
+FROM pg_attribute a
+INNER JOIN pg_class c ON c.oid = a.attrelid
+INNER JOIN pg_namespace n ON n.oid = c.relnamespace
+LEFT OUTER JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+WHERE n.nspname = current_schema() AND c.relkind = 'r' AND a.attnum > 0 AND NOT a.attisdropped
+ORDER BY c.relname, a.attnum";
+
+/// `NOT NULL` constraints are left out, as they are already part of the column.
+const CONSTRAINTS_QUERY: &'static str = "\
+SELECT c.relname::varchar, con.conname::varchar, pg_get_constraintdef(con.oid)::varchar
+FROM pg_constraint con
+INNER JOIN pg_class c ON c.oid = con.conrelid
+INNER JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE n.nspname = current_schema() AND con.contype <> 'n'
+ORDER BY c.relname, con.conname";
+
+/// Indexes which were created for a constraint are left out, as they are created along with it.
+const INDEXES_QUERY: &'static str = "\
+SELECT t.relname::varchar, pg_get_indexdef(i.indexrelid)::varchar
+FROM pg_index i
+INNER JOIN pg_class c ON c.oid = i.indexrelid
+INNER JOIN pg_class t ON t.oid = i.indrelid
+INNER JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE n.nspname = current_schema()
+    AND NOT EXISTS (SELECT 1 FROM pg_constraint con WHERE con.conindid = i.indexrelid)
+ORDER BY t.relname, c.relname";
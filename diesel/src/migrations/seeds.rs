@@ -0,0 +1,50 @@
+use super::{run_pending, search_for_directory, MigrationConfig, MigrationHooks, MigrationSource};
+use super::{compare_versions, Migration, MigrationError, RunMigrationsError};
+use Connection;
+
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Runs all seeds that have not yet been run, printing progress to stdout. Seeds load data, such
+/// as the data used by tests or a staging environment, rather than changing the schema. They are
+/// kept in a `seeds` directory next to the `migrations` directory, in the same format as
+/// migrations, and are tracked in their own `__diesel_seeds` table, so they never appear in the
+/// schema's migration history.
+///
+/// Seeds should be run after all migrations have been run, as they will usually depend on the
+/// latest schema.
+pub fn run_pending_seeds<Conn: Connection>(conn: &Conn) -> Result<(), RunMigrationsError> {
+    run_seeds(conn, Vec::new())
+}
+
+/// Runs all seeds that have not yet been run, from both the seeds directory and the given list.
+/// This allows seeds which are written in Rust, such as ones which generate data, to be run
+/// alongside the SQL seeds. See [`run_pending_seeds`](fn.run_pending_seeds.html) and
+/// [`run_migrations`](fn.run_migrations.html).
+pub fn run_seeds<Conn: Connection>(conn: &Conn, seeds: Vec<Box<Migration>>)
+    -> Result<(), RunMigrationsError>
+{
+    let seeds_dir = try!(find_seeds_directory());
+    let mut all_seeds = try!(seeds_dir.migrations());
+    all_seeds.extend(seeds);
+    all_seeds.sort_by(|a, b| compare_versions(a.version(), b.version()));
+    let mut stdout = io::stdout();
+    run_pending(conn, &MigrationConfig::seeds(), all_seeds, &mut SeedProgressOutput(&mut stdout))
+}
+
+/// Returns the directory containing seeds. Will look for $PWD/seeds. If it is not found, it will
+/// search the parents of the current directory, until it reaches the root directory. Returns
+/// `MigrationError::MigrationDirectoryNotFound` if no directory is found.
+pub fn find_seeds_directory() -> Result<PathBuf, MigrationError> {
+    search_for_directory(&try!(env::current_dir()), "seeds")
+}
+
+struct SeedProgressOutput<'a>(&'a mut Write);
+
+impl<'a> MigrationHooks for SeedProgressOutput<'a> {
+    fn before_run(&mut self, version: &str) -> Result<(), RunMigrationsError> {
+        try!(writeln!(self.0, "Running seed {}", version));
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+/// Splits SQL into the statements it contains, along with the line each one
+/// starts on. Semicolons inside of string literals (including `E'...'` strings
+/// with backslash escapes), quoted identifiers, dollar quoted strings, and
+/// comments (including nested block comments) don't end a statement.
+/// Statements which are empty, or only contain comments, are left out.
+///
+/// Migrations are run one statement at a time using this, so that each file
+/// can contain many statements, even on backends which only accept one
+/// statement per query. It can also be used by `Migration` implementations
+/// which run SQL themselves.
+///
+/// # Example
+///
+/// ```rust
+/// # use diesel::migrations::split_statements;
+/// let sql = "CREATE TABLE users (name VARCHAR);\nINSERT INTO users VALUES ('Sean;');";
+/// assert_eq!(vec![(1, "CREATE TABLE users (name VARCHAR)"), (2, "INSERT INTO users VALUES ('Sean;')")],
+///            split_statements(sql));
+/// ```
+pub fn split_statements(sql: &str) -> Vec<(usize, &str)> {
+    let mut statements = Vec::new();
+    let mut statement_start = None;
+    let mut line = 1;
+    let mut i = 0;
+    while i < sql.len() {
+        let rest = &sql[i..];
+        let c = rest.chars().next().unwrap();
+        let length = if rest.starts_with("--") {
+            rest.find('\n').unwrap_or(rest.len())
+        } else if rest.starts_with("/*") {
+            block_comment_length(rest)
+        } else if c == ';' {
+            if let Some((start, start_line)) = statement_start.take() {
+                statements.push((start_line, sql[start..i].trim_right()));
+            }
+            1
+        } else if c.is_whitespace() {
+            c.len_utf8()
+        } else {
+            if statement_start.is_none() {
+                statement_start = Some((i, line));
+            }
+            let follows_identifier = sql[..i].chars().next_back()
+                .map_or(false, |p| p.is_alphanumeric() || p == '_');
+            let escape_string = if follows_identifier { None } else { escape_string_length(rest) };
+            escape_string.or_else(|| quoted_length(rest)).unwrap_or(c.len_utf8())
+        };
+        line += rest[..length].matches('\n').count();
+        i += length;
+    }
+    if let Some((start, start_line)) = statement_start {
+        statements.push((start_line, sql[start..].trim_right()));
+    }
+    statements
+}
+
+/// Returns the length of the block comment at the start of `sql`. Unlike most
+/// databases, PostgreSQL allows block comments to be nested.
+fn block_comment_length(sql: &str) -> usize {
+    let mut depth = 0;
+    let mut i = 0;
+    while i < sql.len() {
+        if sql[i..].starts_with("/*") {
+            depth += 1;
+            i += 2;
+        } else if sql[i..].starts_with("*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += sql[i..].chars().next().unwrap().len_utf8();
+        }
+    }
+    sql.len()
+}
+
+/// Returns the length of the `E'...'` string at the start of `sql`, if there
+/// is one. In these strings, a backslash escapes the character after it.
+fn escape_string_length(sql: &str) -> Option<usize> {
+    if !(sql.starts_with("E'") || sql.starts_with("e'")) {
+        return None;
+    }
+    let mut chars = sql.char_indices().skip(2);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => { chars.next(); }
+            '\'' if sql[i + 1..].starts_with('\'') => { chars.next(); }
+            '\'' => return Some(i + 1),
+            _ => {}
+        }
+    }
+    Some(sql.len())
+}
+
+/// Returns the length of the string literal, quoted identifier, or dollar
+/// quoted string at the start of `sql`, if there is one.
+fn quoted_length(sql: &str) -> Option<usize> {
+    let quote = match sql.chars().next() {
+        Some('\'') => "'",
+        Some('"') => "\"",
+        Some('$') => match dollar_quote_tag(sql) {
+            Some(tag) => tag,
+            None => return None,
+        },
+        _ => return None,
+    };
+    let rest = &sql[quote.len()..];
+    Some(rest.find(quote).map(|end| end + 2 * quote.len()).unwrap_or(sql.len()))
+}
+
+/// Returns the opening `$tag$` of a dollar quoted string. `$1` is a parameter,
+/// rather than the start of a string, so tags can't start with a digit.
+fn dollar_quote_tag(sql: &str) -> Option<&str> {
+    sql[1..].find('$').and_then(|end| {
+        let tag = &sql[1..end + 1];
+        let valid_tag = !tag.starts_with(|c: char| c.is_digit(10)) &&
+            tag.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if valid_tag {
+            Some(&sql[..end + 2])
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_statements;
+
+    #[test]
+    fn statements_are_split_on_semicolons() {
+        let sql = "CREATE TABLE users (id SERIAL);\n\nCREATE TABLE posts (id SERIAL);\n";
+        assert_eq!(vec![(1, "CREATE TABLE users (id SERIAL)"), (3, "CREATE TABLE posts (id SERIAL)")],
+                   split_statements(sql));
+    }
+
+    #[test]
+    fn the_last_statement_does_not_need_a_semicolon() {
+        assert_eq!(vec![(1, "SELECT 1"), (1, "SELECT 2")], split_statements("SELECT 1; SELECT 2\n"));
+    }
+
+    #[test]
+    fn semicolons_in_strings_and_comments_are_ignored() {
+        let sql = "-- a comment; with a semicolon\nINSERT INTO users (name) VALUES ('Sean;''s');\n\
+                   /* another;\ncomment */ SELECT \"weird;column\" FROM users;\n";
+        assert_eq!(vec![
+            (2, "INSERT INTO users (name) VALUES ('Sean;''s')"),
+            (4, "SELECT \"weird;column\" FROM users"),
+        ], split_statements(sql));
+    }
+
+    #[test]
+    fn dollar_quoted_strings_are_not_split() {
+        let sql = "CREATE FUNCTION f() RETURNS INTEGER AS $body$ SELECT 1; $body$ LANGUAGE SQL;\nSELECT $1;";
+        assert_eq!(vec![
+            (1, "CREATE FUNCTION f() RETURNS INTEGER AS $body$ SELECT 1; $body$ LANGUAGE SQL"),
+            (2, "SELECT $1"),
+        ], split_statements(sql));
+        assert_eq!(vec![(1, "SELECT $$a;b$$")], split_statements("SELECT $$a;b$$;"));
+    }
+
+    #[test]
+    fn nested_block_comments_are_ignored() {
+        let sql = "/* outer /* inner; */ still a comment; */ SELECT 1;";
+        assert_eq!(vec![(1, "SELECT 1")], split_statements(sql));
+    }
+
+    #[test]
+    fn backslashes_escape_quotes_in_escape_strings() {
+        let sql = r"INSERT INTO users (name) VALUES (E'Sean\';'), (e'a''b;\\'); SELECT 1;";
+        assert_eq!(vec![
+            (1, r"INSERT INTO users (name) VALUES (E'Sean\';'), (e'a''b;\\')"),
+            (1, "SELECT 1"),
+        ], split_statements(sql));
+        assert_eq!(vec![(1, r"SELECT name'\'"), (1, "x'")], split_statements(r"SELECT name'\';x'"));
+    }
+
+    #[test]
+    fn empty_statements_are_left_out() {
+        assert_eq!(Vec::<(usize, &str)>::new(), split_statements("\n;; -- nothing here\n"));
+        assert_eq!(vec![(1, "SELECT 'ü'")], split_statements("SELECT 'ü';"));
+    }
+}
@@ -0,0 +1,309 @@
+use super::{create_migrations_table_if_needed, migration_paths_in_directory};
+use super::{previously_run_migration_versions, with_migrations_lock, MigrationConfig};
+use super::{compare_versions, MigrationError, Migrator, RunMigrationsError};
+use super::migration::{self, fnv1a, migration_from, name_from_path, read_sql_file, run_in_transaction_from_path};
+use super::migration::IRREVERSIBLE_MARKER;
+use Connection;
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Replaces every migration which has been run with a single baseline migration, and records it
+/// as having been run in place of them. This keeps setting up a new database fast, for projects
+/// which have built up a long history of migrations. Returns the directory of the baseline
+/// migration, or `None` if fewer than two migrations have been run, so there is nothing to squash.
+///
+/// The baseline's `up.sql` contains each migration's `up.sql` in order, and its `down.sql`
+/// contains each `down.sql` in reverse order. If any of the squashed migrations are irreversible,
+/// so is the baseline. It takes the version of the latest migration which was run, so any pending
+/// migrations are still run after it. The directories of the squashed migrations are deleted.
+///
+/// Returns `MigrationError::PendingMigrationBeforeBaseline` if a migration which comes before the
+/// latest migration that was run has not been run.
+///
+/// Only the database this is called against has its tracking table rewritten. Every other
+/// database which the migrations are deployed to must have run all of the squashed migrations
+/// before the baseline is deployed to it, and must then have its tracking table rewritten with
+/// [`record_squashed_baseline`](fn.record_squashed_baseline.html). Until then, running migrations
+/// against it returns `MigrationError::ChecksumMismatch`, since the baseline's version was
+/// recorded with the checksum of the migration it replaced.
+pub fn squash_applied_migrations<Conn: Connection>(conn: &Conn)
+    -> Result<Option<PathBuf>, RunMigrationsError>
+{
+    Migrator::new(conn).squash_applied_migrations()
+}
+
+/// Squashes the migrations in the given directory. See
+/// [`squash_applied_migrations`](fn.squash_applied_migrations.html).
+pub fn squash_applied_migrations_in_directory<Conn: Connection>(conn: &Conn, migrations_dir: &Path)
+    -> Result<Option<PathBuf>, RunMigrationsError>
+{
+    Migrator::new(conn).with_directory(migrations_dir).squash_applied_migrations()
+}
+
+pub fn squash<Conn: Connection>(conn: &Conn, config: &MigrationConfig, migrations_dir: &Path)
+    -> Result<Option<PathBuf>, RunMigrationsError>
+{
+    with_migrations_lock(conn, config, || {
+        try!(create_migrations_table_if_needed(conn, config));
+        let already_run = try!(previously_run_migration_versions(conn, config));
+        let all_migrations = try!(migration_paths_in_directory(migrations_dir));
+        let squashed_count = match all_migrations.iter().rposition(|&(ref v, _)| already_run.contains(v)) {
+            Some(latest) => latest + 1,
+            None => 0,
+        };
+        if squashed_count < 2 {
+            return Ok(None);
+        }
+        let squashed = &all_migrations[..squashed_count];
+        if let Some(&(ref version, _)) = squashed.iter().find(|&&(ref v, _)| !already_run.contains(v)) {
+            return Err(MigrationError::PendingMigrationBeforeBaseline(version.clone()).into());
+        }
+
+        // The baseline is written to a hidden directory, which is ignored when looking for
+        // migrations, until the squashed migrations have been removed.
+        let baseline_version = &squashed[squashed_count - 1].0;
+        let baseline_name = format!("{}_baseline", baseline_version);
+        let temporary_dir = migrations_dir.join(format!(".{}", baseline_name));
+        try!(write_baseline(&temporary_dir, squashed));
+        let up_sql = try!(read_sql_file(&temporary_dir.join("up.sql")));
+        let checksum = format!("{:016x}", fnv1a(up_sql.as_bytes()));
+
+        let rewrite_result = conn.transaction::<_, RunMigrationsError, _>(|| {
+            for &(ref version, _) in squashed {
+                try!(conn.execute(&config.delete_version_sql(version)));
+            }
+            try!(conn.execute(&config.insert_version_sql(baseline_version, Some(&checksum), None)));
+            Ok(())
+        });
+        if let Err(e) = rewrite_result {
+            try!(fs::remove_dir_all(&temporary_dir));
+            return Err(e.into());
+        }
+
+        for &(_, ref path) in squashed {
+            try!(fs::remove_dir_all(path));
+        }
+        let baseline_dir = migrations_dir.join(baseline_name);
+        try!(fs::rename(&temporary_dir, &baseline_dir));
+        Ok(Some(baseline_dir))
+    })
+}
+
+/// Rewrites the tracking table of a database which ran the migrations that were squashed by
+/// [`squash_applied_migrations`](fn.squash_applied_migrations.html) against another database. The
+/// squashed migrations are removed from the table, and the baseline is recorded as having been run
+/// in their place, with its own checksum. Nothing is run. Returns the version of the baseline, or
+/// `None` if there was nothing to rewrite, either because it has already been done, or because
+/// the database hasn't run any of the squashed migrations, in which case the baseline will be run
+/// like any other migration.
+///
+/// The baseline is the earliest migration in the migrations directory whose name is `baseline`.
+/// Migrations which have been run, but which come before it and are no longer in the migrations
+/// directory, are treated as having been squashed. Returns
+/// `MigrationError::PendingMigrationBeforeBaseline` if the database ran some of the squashed
+/// migrations, but not the last of them.
+///
+/// # Example
+///
+/// ```no_run
+/// # use diesel::migrations;
+/// # use diesel::prelude::*;
+/// # fn main() {
+/// # let connection = PgConnection::establish("postgres://localhost").unwrap();
+/// // After deploying a squashed migrations directory, before running migrations
+/// migrations::record_squashed_baseline(&connection).unwrap();
+/// migrations::run_pending_migrations(&connection).unwrap();
+/// # }
+/// ```
+pub fn record_squashed_baseline<Conn: Connection>(conn: &Conn)
+    -> Result<Option<String>, RunMigrationsError>
+{
+    Migrator::new(conn).record_squashed_baseline()
+}
+
+/// Records the baseline in the given directory. See
+/// [`record_squashed_baseline`](fn.record_squashed_baseline.html).
+pub fn record_squashed_baseline_in_directory<Conn: Connection>(conn: &Conn, migrations_dir: &Path)
+    -> Result<Option<String>, RunMigrationsError>
+{
+    Migrator::new(conn).with_directory(migrations_dir).record_squashed_baseline()
+}
+
+pub fn record_baseline<Conn: Connection>(conn: &Conn, config: &MigrationConfig, migrations_dir: &Path)
+    -> Result<Option<String>, RunMigrationsError>
+{
+    with_migrations_lock(conn, config, || {
+        try!(create_migrations_table_if_needed(conn, config));
+        let already_run = try!(previously_run_migration_versions(conn, config));
+        let all_migrations = try!(migration_paths_in_directory(migrations_dir));
+        let (baseline_version, baseline_path) = match all_migrations.iter()
+            .find(|&&(_, ref path)| name_from_path(path).as_ref().map(|n| &**n) == Some("baseline"))
+        {
+            Some(&(ref version, ref path)) => (version.clone(), path.clone()),
+            None => return Ok(None),
+        };
+        let in_directory = all_migrations.iter().map(|&(ref v, _)| v).collect::<HashSet<_>>();
+        let squashed = already_run.iter()
+            .filter(|v| !in_directory.contains(v))
+            .filter(|v| compare_versions(v, &baseline_version) == Ordering::Less)
+            .collect::<Vec<_>>();
+        if squashed.is_empty() {
+            return Ok(None);
+        }
+        if !already_run.contains(&baseline_version) {
+            return Err(MigrationError::PendingMigrationBeforeBaseline(baseline_version).into());
+        }
+
+        let baseline = try!(migration_from(baseline_path));
+        let checksum = migration::checksum(&*baseline);
+        try!(conn.transaction::<_, RunMigrationsError, _>(|| {
+            for version in &squashed {
+                try!(conn.execute(&config.delete_version_sql(version)));
+            }
+            try!(conn.execute(&config.delete_version_sql(&baseline_version)));
+            try!(conn.execute(&config.insert_version_sql(&baseline_version, checksum.as_ref().map(|c| &**c), None)));
+            Ok(())
+        }));
+        Ok(Some(baseline_version))
+    })
+}
+
+fn write_baseline(baseline_dir: &Path, squashed: &[(String, PathBuf)]) -> Result<(), MigrationError> {
+    try!(fs::create_dir(baseline_dir));
+    let mut up_sql = try!(File::create(baseline_dir.join("up.sql")));
+    for &(ref version, ref path) in squashed {
+        let sql = try!(read_sql_file(&path.join("up.sql")));
+        try!(write!(up_sql, "-- Migration {}\n{}\n\n", version, sql.trim_right()));
+    }
+    let mut down_sql = try!(File::create(baseline_dir.join("down.sql")));
+    let mut reversible = true;
+    for &(_, ref path) in squashed {
+        reversible = reversible && try!(migration_from(path.clone())).reversible();
+    }
+    if reversible {
+        for &(ref version, ref path) in squashed.iter().rev() {
+            let sql = try!(read_sql_file(&path.join("down.sql")));
+            try!(write!(down_sql, "-- Migration {}\n{}\n\n", version, sql.trim_right()));
+        }
+    } else {
+        try!(writeln!(down_sql, "{}", IRREVERSIBLE_MARKER));
+    }
+    let mut run_in_transaction = true;
+    for &(_, ref path) in squashed {
+        run_in_transaction = run_in_transaction && try!(run_in_transaction_from_path(path));
+    }
+    if !run_in_transaction {
+        let mut metadata = try!(File::create(baseline_dir.join("metadata.toml")));
+        try!(writeln!(metadata, "run_in_transaction = false"));
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    extern crate tempdir;
+
+    use self::tempdir::TempDir;
+
+    use migrations::*;
+    use prelude::*;
+
+    use std::fs;
+    use std::io::Write;
+    use std::path::Path;
+    use test_helpers;
+
+    fn connection() -> PgConnection {
+        let connection = test_helpers::connection();
+        connection.begin_test_transaction().unwrap();
+        // Only the migrations created by each test should be in the tracking table
+        create_schema_migrations_table_if_needed(&connection).unwrap();
+        connection.execute("DELETE FROM __diesel_schema_migrations").unwrap();
+        connection
+    }
+
+    fn create_migration(dir: &Path, name: &str, table: &str) {
+        let folder = dir.join(name);
+        fs::create_dir(&folder).unwrap();
+        fs::File::create(folder.join("up.sql")).unwrap()
+            .write_all(format!("CREATE TABLE {} (id SERIAL PRIMARY KEY);", table).as_bytes()).unwrap();
+        fs::File::create(folder.join("down.sql")).unwrap()
+            .write_all(format!("DROP TABLE {};", table).as_bytes()).unwrap();
+    }
+
+    fn version_is_recorded(connection: &PgConnection, version: &str) -> bool {
+        let query = format!("SELECT 1 FROM __diesel_schema_migrations WHERE version = '{}'", version);
+        connection.execute(&query).unwrap() == 1
+    }
+
+    #[test]
+    fn applied_migrations_are_replaced_by_a_baseline() {
+        let connection = connection();
+        let dir = TempDir::new("diesel").unwrap();
+        create_migration(dir.path(), "1_create_squash_a", "squash_a");
+        create_migration(dir.path(), "2_create_squash_b", "squash_b");
+        run_pending_migrations_in_directory(&connection, dir.path(), &mut Vec::new()).unwrap();
+        create_migration(dir.path(), "3_create_squash_c", "squash_c");
+
+        let baseline_dir = squash_applied_migrations_in_directory(&connection, dir.path()).unwrap();
+
+        assert_eq!(Some(dir.path().join("2_baseline")), baseline_dir);
+        let mut names = fs::read_dir(dir.path()).unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(vec!["2_baseline", "3_create_squash_c"], names);
+        assert!(!version_is_recorded(&connection, "1"));
+        assert!(version_is_recorded(&connection, "2"));
+
+        run_pending_migrations_in_directory(&connection, dir.path(), &mut Vec::new()).unwrap();
+        revert_latest_migration_in_directory(&connection, dir.path(), &mut Vec::new()).unwrap();
+        revert_latest_migration_in_directory(&connection, dir.path(), &mut Vec::new()).unwrap();
+        assert_eq!(Ok(0), connection.execute("SELECT 1 FROM pg_tables WHERE tablename LIKE 'squash_%'"));
+    }
+
+    #[test]
+    fn migrations_cannot_be_squashed_past_a_pending_migration() {
+        let connection = connection();
+        let dir = TempDir::new("diesel").unwrap();
+        create_migration(dir.path(), "1_create_squash_a", "squash_a");
+        create_migration(dir.path(), "3_create_squash_c", "squash_c");
+        run_pending_migrations_in_directory(&connection, dir.path(), &mut Vec::new()).unwrap();
+        create_migration(dir.path(), "2_create_squash_b", "squash_b");
+
+        let result = squash_applied_migrations_in_directory(&connection, dir.path());
+
+        let expected_error = MigrationError::PendingMigrationBeforeBaseline("2".into());
+        assert_eq!(Err(RunMigrationsError::MigrationError(expected_error)), result);
+    }
+
+    #[test]
+    fn other_databases_record_the_baseline_in_place_of_the_squashed_migrations() {
+        let connection = connection();
+        let dir = TempDir::new("diesel").unwrap();
+        create_migration(dir.path(), "1_create_squash_a", "squash_a");
+        create_migration(dir.path(), "2_create_squash_b", "squash_b");
+        run_pending_migrations_in_directory(&connection, dir.path(), &mut Vec::new()).unwrap();
+        connection.execute("CREATE TEMPORARY TABLE original_migrations AS \
+            SELECT * FROM __diesel_schema_migrations").unwrap();
+        squash_applied_migrations_in_directory(&connection, dir.path()).unwrap();
+        // Put the tracking table back the way it is on a database which wasn't squashed
+        connection.execute("DELETE FROM __diesel_schema_migrations").unwrap();
+        connection.execute("INSERT INTO __diesel_schema_migrations SELECT * FROM original_migrations")
+            .unwrap();
+        let result = run_pending_migrations_in_directory(&connection, dir.path(), &mut Vec::new());
+        let expected_error = MigrationError::ChecksumMismatch("2".into());
+        assert_eq!(Err(RunMigrationsError::MigrationError(expected_error)), result);
+
+        assert_eq!(Ok(Some("2".into())), record_squashed_baseline_in_directory(&connection, dir.path()));
+
+        assert!(!version_is_recorded(&connection, "1"));
+        assert!(version_is_recorded(&connection, "2"));
+        run_pending_migrations_in_directory(&connection, dir.path(), &mut Vec::new()).unwrap();
+        assert_eq!(Ok(None), record_squashed_baseline_in_directory(&connection, dir.path()));
+    }
+}
@@ -1,3 +1,9 @@
+/// Whether a clause was given. This lets a backend render one clause
+/// differently when another is missing, such as `OFFSET` without `LIMIT`.
+pub trait IsPresent {
+    fn is_present(&self) -> bool;
+}
+
 macro_rules! simple_clause {
     ($no_clause:ident, $clause:ident, $sql:expr) => {
         use backend::Backend;
@@ -12,6 +18,12 @@ macro_rules! simple_clause {
             }
         }
 
+        impl super::clause_macro::IsPresent for $no_clause {
+            fn is_present(&self) -> bool {
+                false
+            }
+        }
+
         #[derive(Debug, Clone, Copy)]
         pub struct $clause<Expr>(pub Expr);
 
@@ -24,5 +36,11 @@ macro_rules! simple_clause {
                 self.0.to_sql(out)
             }
         }
+
+        impl<Expr> super::clause_macro::IsPresent for $clause<Expr> {
+            fn is_present(&self) -> bool {
+                true
+            }
+        }
     }
 }
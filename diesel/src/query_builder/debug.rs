@@ -1,10 +1,12 @@
 use backend::Debug;
-use super::{QueryBuilder, BuildQueryResult, Context};
+use super::{QueryBuilder, BuildQueryResult, Binds, Context};
 use types::HasSqlType;
 
 #[doc(hidden)]
+#[derive(Default)]
 pub struct DebugQueryBuilder {
     pub sql: String,
+    pub binds: Binds,
     pub bind_types: Vec<u32>,
     context_stack: Vec<Context>,
 }
@@ -13,6 +15,7 @@ impl DebugQueryBuilder {
     pub fn new() -> Self {
         DebugQueryBuilder {
             sql: String::new(),
+            binds: Vec::new(),
             bind_types: Vec::new(),
             context_stack: Vec::new(),
         }
@@ -36,7 +39,10 @@ impl QueryBuilder<Debug> for DebugQueryBuilder {
     {
         match (self.context_stack.first(), bind) {
             (Some(&Context::Insert), None) => self.push_sql("DEFAULT"),
-            _ => self.push_sql("?"),
+            (_, bind) => {
+                self.push_sql("?");
+                self.binds.push(bind);
+            }
         }
     }
 
@@ -0,0 +1,74 @@
+use backend::{Backend, Pg};
+use expression::predicates::{Asc, Desc};
+use super::{QueryFragment, QueryBuilder, BuildQueryResult};
+use super::order_clause::{NoOrderClause, OrderClause};
+
+#[derive(Debug, Clone, Copy)]
+pub struct NoDistinctClause;
+
+impl<DB: Backend> QueryFragment<DB> for NoDistinctClause {
+    fn to_sql(&self, _out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        Ok(())
+    }
+}
+
+/// `DISTINCT ON` is PostgreSQL specific, so a query using it will only compile
+/// for `Pg`.
+#[derive(Debug, Clone, Copy)]
+pub struct DistinctOnClause<Expr>(pub Expr);
+
+impl<Expr> QueryFragment<Pg> for DistinctOnClause<Expr> where
+    Expr: QueryFragment<Pg>,
+{
+    fn to_sql(&self, out: &mut <Pg as Backend>::QueryBuilder) -> BuildQueryResult {
+        out.push_sql("DISTINCT ON (");
+        try!(self.0.to_sql(out));
+        out.push_sql(") ");
+        Ok(())
+    }
+}
+
+/// Indicates that an order clause is valid alongside the given distinct clause.
+/// PostgreSQL requires that the leftmost expressions in `ORDER BY` match the
+/// expression given to `DISTINCT ON`, so a query which orders by anything else
+/// will fail to compile rather than fail at runtime.
+pub trait ValidOrderingForDistinct<Distinct> {
+}
+
+impl<T> ValidOrderingForDistinct<NoDistinctClause> for T {
+}
+
+impl<Expr> ValidOrderingForDistinct<DistinctOnClause<Expr>> for NoOrderClause {
+}
+
+impl<Expr> ValidOrderingForDistinct<DistinctOnClause<Expr>> for OrderClause<Expr> {
+}
+
+impl<Expr> ValidOrderingForDistinct<DistinctOnClause<Expr>> for OrderClause<Asc<Expr>> {
+}
+
+impl<Expr> ValidOrderingForDistinct<DistinctOnClause<Expr>> for OrderClause<Desc<Expr>> {
+}
+
+macro_rules! valid_ordering_for_tuples {
+    ($($T:ident),+) => {
+        impl<Expr, $($T),+> ValidOrderingForDistinct<DistinctOnClause<Expr>>
+            for OrderClause<(Expr, $($T),+)>
+        {
+        }
+
+        impl<Expr, $($T),+> ValidOrderingForDistinct<DistinctOnClause<Expr>>
+            for OrderClause<(Asc<Expr>, $($T),+)>
+        {
+        }
+
+        impl<Expr, $($T),+> ValidOrderingForDistinct<DistinctOnClause<Expr>>
+            for OrderClause<(Desc<Expr>, $($T),+)>
+        {
+        }
+    }
+}
+
+valid_ordering_for_tuples!(A);
+valid_ordering_for_tuples!(A, B);
+valid_ordering_for_tuples!(A, B, C);
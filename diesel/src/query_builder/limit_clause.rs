@@ -1 +1,22 @@
+use expression::bound::Bound;
+use types::BigInt;
+
 simple_clause!(NoLimitClause, LimitClause, " LIMIT ");
+
+/// The value of a `LIMIT` or `OFFSET` clause. It is sent as a bind parameter,
+/// so paginated queries produce the same SQL for every page, unless the
+/// backend doesn't allow them to be parameterized, in which case it is written
+/// into the SQL instead.
+#[derive(Debug, Clone, Copy)]
+pub struct LimitOffsetValue(pub i64);
+
+impl<DB: Backend> QueryFragment<DB> for LimitOffsetValue {
+    fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        if DB::supports_parameterized_limit() {
+            Bound::<BigInt, i64>::new(self.0).to_sql(out)
+        } else {
+            out.push_sql(&self.0.to_string());
+            Ok(())
+        }
+    }
+}
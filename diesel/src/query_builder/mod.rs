@@ -10,6 +10,8 @@ pub mod functions;
 pub mod nodes;
 #[macro_use]
 mod clause_macro;
+#[doc(hidden)]
+pub mod distinct_clause;
 mod limit_clause;
 mod offset_clause;
 mod order_clause;
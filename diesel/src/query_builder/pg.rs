@@ -1,12 +1,9 @@
-use std::rc::Rc;
-
 use backend::Pg;
-use connection::pg::raw::RawConnection;
 use super::{QueryBuilder, Binds, BuildQueryResult, Context};
 use types::HasSqlType;
 
+#[derive(Default)]
 pub struct PgQueryBuilder {
-    conn: Rc<RawConnection>,
     pub sql: String,
     pub binds: Binds,
     pub bind_types: Vec<u32>,
@@ -15,9 +12,8 @@ pub struct PgQueryBuilder {
 }
 
 impl PgQueryBuilder {
-    pub fn new(conn: &Rc<RawConnection>) -> Self {
+    pub fn new() -> Self {
         PgQueryBuilder {
-            conn: conn.clone(),
             sql: String::new(),
             binds: Vec::new(),
             bind_types: Vec::new(),
@@ -33,8 +29,12 @@ impl QueryBuilder<Pg> for PgQueryBuilder {
     }
 
     fn push_identifier(&mut self, identifier: &str) -> BuildQueryResult {
-        let escaped_identifier = try!(self.conn.escape_identifier(identifier));
-        Ok(self.push_sql(&escaped_identifier))
+        // This is the same escaping done by `PQescapeIdentifier`, which we
+        // don't use so that queries can be built without a connection.
+        self.push_sql("\"");
+        self.push_sql(&identifier.replace("\"", "\"\""));
+        self.push_sql("\"");
+        Ok(())
     }
 
     fn push_bound_value<T>(&mut self, bind: Option<Vec<u8>>) where
@@ -60,3 +60,12 @@ impl QueryBuilder<Pg> for PgQueryBuilder {
         self.context_stack.pop();
     }
 }
+
+#[test]
+fn identifiers_are_quoted_and_escaped() {
+    let mut query_builder = PgQueryBuilder::new();
+    query_builder.push_identifier("users").unwrap();
+    query_builder.push_sql(".");
+    query_builder.push_identifier("a \"quoted\" name").unwrap();
+    assert_eq!(r#""users"."a ""quoted"" name""#, query_builder.sql);
+}
@@ -1,93 +1,110 @@
 use expression::*;
 use expression::aliased::Aliased;
 use query_builder::{Query, SelectStatement};
+use query_builder::distinct_clause::*;
 use query_builder::limit_clause::*;
 use query_builder::offset_clause::*;
 use query_builder::order_clause::*;
 use query_builder::where_clause::*;
 use query_dsl::*;
-use types::{self, Bool};
+use types::Bool;
 
-impl<ST, S, F, W, O, L, Of, Selection, Type> SelectDsl<Selection, Type>
-    for SelectStatement<ST, S, F, W, O, L, Of> where
+impl<ST, S, F, W, O, L, Of, D, Selection, Type> SelectDsl<Selection, Type>
+    for SelectStatement<ST, S, F, W, O, L, Of, D> where
     Selection: Expression,
-    SelectStatement<Type, Selection, F, W, O, L, Of>: Query<SqlType=Type>,
+    SelectStatement<Type, Selection, F, W, O, L, Of, D>: Query<SqlType=Type>,
 {
-    type Output = SelectStatement<Type, Selection, F, W, O, L, Of>;
+    type Output = SelectStatement<Type, Selection, F, W, O, L, Of, D>;
 
     fn select(self, selection: Selection) -> Self::Output {
         SelectStatement::new(selection, self.from, self.where_clause, self.order,
-            self.limit, self.offset)
+            self.limit, self.offset, self.distinct)
     }
 }
 
-impl<ST, S, F, W, O, L, Of, Predicate> FilterDsl<Predicate>
-    for SelectStatement<ST, S, F, W, O, L, Of> where
+impl<ST, S, F, W, O, L, Of, D, Predicate> FilterDsl<Predicate>
+    for SelectStatement<ST, S, F, W, O, L, Of, D> where
     Predicate: SelectableExpression<F, SqlType=Bool> + NonAggregate,
     W: WhereAnd<Predicate>,
-    SelectStatement<ST, S, F, W::Output, O, L, Of>: Query,
+    SelectStatement<ST, S, F, W::Output, O, L, Of, D>: Query,
 {
-    type Output = SelectStatement<ST, S, F, W::Output, O, L, Of>;
+    type Output = SelectStatement<ST, S, F, W::Output, O, L, Of, D>;
 
     fn filter(self, predicate: Predicate) -> Self::Output {
         SelectStatement::new(self.select, self.from, self.where_clause.and(predicate),
-            self.order, self.limit, self.offset)
+            self.order, self.limit, self.offset, self.distinct)
     }
 }
 
-impl<ST, S, F, W, O, L, Of, Expr> OrderDsl<Expr>
-    for SelectStatement<ST, S, F, W, O, L, Of> where
+impl<ST, S, F, W, O, L, Of, D, Expr> OrderDsl<Expr>
+    for SelectStatement<ST, S, F, W, O, L, Of, D> where
     Expr: SelectableExpression<F>,
-    SelectStatement<ST, S, F, W, OrderClause<Expr>, L, Of>: Query<SqlType=ST>,
+    OrderClause<Expr>: ValidOrderingForDistinct<D>,
+    SelectStatement<ST, S, F, W, OrderClause<Expr>, L, Of, D>: Query<SqlType=ST>,
 {
-    type Output = SelectStatement<ST, S, F, W, OrderClause<Expr>, L, Of>;
+    type Output = SelectStatement<ST, S, F, W, OrderClause<Expr>, L, Of, D>;
 
     fn order(self, expr: Expr) -> Self::Output {
         let order = OrderClause(expr);
         SelectStatement::new(self.select, self.from, self.where_clause, order,
-            self.limit, self.offset)
+            self.limit, self.offset, self.distinct)
+    }
+}
+
+impl<ST, S, F, W, O, L, Of, D, Expr> DistinctOnDsl<Expr>
+    for SelectStatement<ST, S, F, W, O, L, Of, D> where
+    Expr: SelectableExpression<F> + NonAggregate,
+    O: ValidOrderingForDistinct<DistinctOnClause<Expr>>,
+    SelectStatement<ST, S, F, W, O, L, Of, DistinctOnClause<Expr>>: Query<SqlType=ST>,
+{
+    type Output = SelectStatement<ST, S, F, W, O, L, Of, DistinctOnClause<Expr>>;
+
+    fn distinct_on(self, expr: Expr) -> Self::Output {
+        let distinct = DistinctOnClause(expr);
+        SelectStatement::new(self.select, self.from, self.where_clause,
+            self.order, self.limit, self.offset, distinct)
     }
 }
 
 #[doc(hidden)]
-pub type Limit = <i64 as AsExpression<types::BigInt>>::Expression;
+pub type Limit = LimitOffsetValue;
 
-impl<ST, S, F, W, O, L, Of> LimitDsl for SelectStatement<ST, S, F, W, O, L, Of> where
-    SelectStatement<ST, S, F, W, O, LimitClause<Limit>, Of>: Query<SqlType=ST>,
+impl<ST, S, F, W, O, L, Of, D> LimitDsl for SelectStatement<ST, S, F, W, O, L, Of, D> where
+    SelectStatement<ST, S, F, W, O, LimitClause<Limit>, Of, D>: Query<SqlType=ST>,
 {
-    type Output = SelectStatement<ST, S, F, W, O, LimitClause<Limit>, Of>;
+    type Output = SelectStatement<ST, S, F, W, O, LimitClause<Limit>, Of, D>;
 
     fn limit(self, limit: i64) -> Self::Output {
-        let limit_clause = LimitClause(AsExpression::<types::BigInt>::as_expression(limit));
+        let limit_clause = LimitClause(LimitOffsetValue(limit));
         SelectStatement::new(self.select, self.from, self.where_clause,
-            self.order, limit_clause, self.offset)
+            self.order, limit_clause, self.offset, self.distinct)
     }
 }
 
 #[doc(hidden)]
 pub type Offset = Limit;
 
-impl<ST, S, F, W, O, L, Of> OffsetDsl for SelectStatement<ST, S, F, W, O, L, Of> where
-    SelectStatement<ST, S, F, W, O, L, OffsetClause<Offset>>: Query<SqlType=ST>,
+impl<ST, S, F, W, O, L, Of, D> OffsetDsl for SelectStatement<ST, S, F, W, O, L, Of, D> where
+    SelectStatement<ST, S, F, W, O, L, OffsetClause<Offset>, D>: Query<SqlType=ST>,
 {
-    type Output = SelectStatement<ST, S, F, W, O, L, OffsetClause<Offset>>;
+    type Output = SelectStatement<ST, S, F, W, O, L, OffsetClause<Offset>, D>;
 
     fn offset(self, offset: i64) -> Self::Output {
-        let offset_clause = OffsetClause(AsExpression::<types::BigInt>::as_expression(offset));
+        let offset_clause = OffsetClause(LimitOffsetValue(offset));
         SelectStatement::new(self.select, self.from, self.where_clause,
-            self.order, self.limit, offset_clause)
+            self.order, self.limit, offset_clause, self.distinct)
     }
 }
 
-impl<'a, ST, S, F, W, O, L, Of, Expr> WithDsl<'a, Expr>
-for SelectStatement<ST, S, F, W, O, L, Of> where
-    SelectStatement<ST, S, WithQuerySource<'a, F, Expr>, W, O, L, Of>: Query,
+impl<'a, ST, S, F, W, O, L, Of, D, Expr> WithDsl<'a, Expr>
+for SelectStatement<ST, S, F, W, O, L, Of, D> where
+    SelectStatement<ST, S, WithQuerySource<'a, F, Expr>, W, O, L, Of, D>: Query,
 {
-    type Output = SelectStatement<ST, S, WithQuerySource<'a, F, Expr>, W, O, L, Of>;
+    type Output = SelectStatement<ST, S, WithQuerySource<'a, F, Expr>, W, O, L, Of, D>;
 
     fn with(self, expr: Aliased<'a, Expr>) -> Self::Output {
         let source = WithQuerySource::new(self.from, expr);
         SelectStatement::new(self.select, source, self.where_clause,
-            self.order, self.limit, self.offset)
+            self.order, self.limit, self.offset, self.distinct)
     }
 }
@@ -5,6 +5,8 @@ use expression::*;
 use query_source::*;
 use std::marker::PhantomData;
 use super::{Query, QueryBuilder, QueryFragment, BuildQueryResult, Context};
+use super::clause_macro::IsPresent;
+use super::distinct_clause::NoDistinctClause;
 use super::limit_clause::NoLimitClause;
 use super::offset_clause::NoOffsetClause;
 use super::order_clause::NoOrderClause;
@@ -21,6 +23,7 @@ pub struct SelectStatement<
     Order = NoOrderClause,
     Limit = NoLimitClause,
     Offset = NoOffsetClause,
+    Distinct = NoDistinctClause,
 > {
     select: Select,
     from: From,
@@ -28,11 +31,12 @@ pub struct SelectStatement<
     order: Order,
     limit: Limit,
     offset: Offset,
+    distinct: Distinct,
     _marker: PhantomData<SqlType>,
 }
 
-impl<ST, S, F, W, O, L, Of> SelectStatement<ST, S, F, W, O, L, Of> {
-    pub fn new(select: S, from: F, where_clause: W, order: O, limit: L, offset: Of) -> Self {
+impl<ST, S, F, W, O, L, Of, D> SelectStatement<ST, S, F, W, O, L, Of, D> {
+    pub fn new(select: S, from: F, where_clause: W, order: O, limit: L, offset: Of, distinct: D) -> Self {
         SelectStatement {
             select: select,
             from: from,
@@ -40,99 +44,119 @@ impl<ST, S, F, W, O, L, Of> SelectStatement<ST, S, F, W, O, L, Of> {
             order: order,
             limit: limit,
             offset: offset,
+            distinct: distinct,
             _marker: PhantomData,
         }
     }
 
     pub fn inner_join<T>(self, other: T)
-        -> SelectStatement<ST, S, InnerJoinSource<F, T>, W, O, L, Of> where
-            T: Table,
-            F: Table + JoinTo<T, joins::Inner>,
+        -> SelectStatement<ST, S, InnerJoinSource<F, T::Table, T::On>, W, O, L, Of, D> where
+            T: JoinTarget,
+            F: Table,
+            InnerJoinSource<F, T::Table, T::On>: QuerySource,
     {
         SelectStatement::new(self.select, self.from.inner_join(other),
-            self.where_clause, self.order, self.limit, self.offset)
+            self.where_clause, self.order, self.limit, self.offset, self.distinct)
     }
 
     pub fn left_outer_join<T>(self, other: T)
-        -> SelectStatement<ST, S, LeftOuterJoinSource<F, T>, W, O, L, Of> where
-            T: Table,
-            F: Table + JoinTo<T, joins::LeftOuter>,
+        -> SelectStatement<ST, S, LeftOuterJoinSource<F, T::Table, T::On>, W, O, L, Of, D> where
+            T: JoinTarget,
+            F: Table,
+            LeftOuterJoinSource<F, T::Table, T::On>: QuerySource,
     {
         SelectStatement::new(self.select, self.from.left_outer_join(other),
-            self.where_clause, self.order, self.limit, self.offset)
+            self.where_clause, self.order, self.limit, self.offset, self.distinct)
     }
 }
 
 impl<ST, S, F> SelectStatement<ST, S, F> {
     pub fn simple(select: S, from: F) -> Self {
-        SelectStatement::new(select, from, NoWhereClause, NoOrderClause, NoLimitClause, NoOffsetClause)
+        SelectStatement::new(select, from, NoWhereClause, NoOrderClause,
+            NoLimitClause, NoOffsetClause, NoDistinctClause)
     }
 }
 
-impl<ST, S, F, W, O, L, Of> Query for SelectStatement<ST, S, F, W, O, L, Of> where
+impl<ST, S, F, W, O, L, Of, D> Query for SelectStatement<ST, S, F, W, O, L, Of, D> where
     S: SelectableExpression<F, ST>,
 {
     type SqlType = ST;
 }
 
-impl<ST, S, F, W, O, L, Of> Expression for SelectStatement<ST, S, F, W, O, L, Of> where
+impl<ST, S, F, W, O, L, Of, D> Expression for SelectStatement<ST, S, F, W, O, L, Of, D> where
     S: SelectableExpression<F, ST>,
 {
     type SqlType = types::Array<ST>;
 }
 
-impl<ST, S, F, W, O, L, Of, DB> QueryFragment<DB> for SelectStatement<ST, S, F, W, O, L, Of> where
+impl<ST, S, F, W, O, L, Of, D, DB> QueryFragment<DB> for SelectStatement<ST, S, F, W, O, L, Of, D> where
     DB: Backend,
     S: QueryFragment<DB>,
     F: QuerySource,
     F::FromClause: QueryFragment<DB>,
+    D: QueryFragment<DB>,
     W: QueryFragment<DB>,
     O: QueryFragment<DB>,
-    L: QueryFragment<DB>,
-    Of: QueryFragment<DB>,
+    L: QueryFragment<DB> + IsPresent,
+    Of: QueryFragment<DB> + IsPresent,
 {
     fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
         out.push_context(Context::Select);
         out.push_sql("SELECT ");
+        try!(self.distinct.to_sql(out));
         try!(self.select.to_sql(out));
         out.push_sql(" FROM ");
         try!(self.from.from_clause().to_sql(out));
         try!(self.where_clause.to_sql(out));
         try!(self.order.to_sql(out));
         try!(self.limit.to_sql(out));
+        if !self.limit.is_present() && self.offset.is_present() {
+            if let Some(limit) = DB::limit_for_offset_without_limit() {
+                out.push_sql(" LIMIT ");
+                out.push_sql(limit);
+            }
+        }
         try!(self.offset.to_sql(out));
         out.pop_context();
         Ok(())
     }
 }
 
-impl<ST, S, W, O, L, Of, DB> QueryFragment<DB> for SelectStatement<ST, S, (), W, O, L, Of> where
+impl<ST, S, W, O, L, Of, D, DB> QueryFragment<DB> for SelectStatement<ST, S, (), W, O, L, Of, D> where
     DB: Backend,
     S: QueryFragment<DB>,
+    D: QueryFragment<DB>,
     W: QueryFragment<DB>,
     O: QueryFragment<DB>,
-    L: QueryFragment<DB>,
-    Of: QueryFragment<DB>,
+    L: QueryFragment<DB> + IsPresent,
+    Of: QueryFragment<DB> + IsPresent,
 {
     fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
         out.push_context(Context::Select);
         out.push_sql("SELECT ");
+        try!(self.distinct.to_sql(out));
         try!(self.select.to_sql(out));
         try!(self.where_clause.to_sql(out));
         try!(self.order.to_sql(out));
         try!(self.limit.to_sql(out));
+        if !self.limit.is_present() && self.offset.is_present() {
+            if let Some(limit) = DB::limit_for_offset_without_limit() {
+                out.push_sql(" LIMIT ");
+                out.push_sql(limit);
+            }
+        }
         try!(self.offset.to_sql(out));
         out.pop_context();
         Ok(())
     }
 }
 
-impl<ST, S, F, W, O, L, Of, QS> SelectableExpression<QS> for SelectStatement<ST, S, F, W, O, L, Of> where
-    SelectStatement<ST, S, F, W, O, L, Of>: Expression,
+impl<ST, S, F, W, O, L, Of, D, QS> SelectableExpression<QS> for SelectStatement<ST, S, F, W, O, L, Of, D> where
+    SelectStatement<ST, S, F, W, O, L, Of, D>: Expression,
 {
 }
 
-impl<ST, S, F, W, O, L, Of> NonAggregate for SelectStatement<ST, S, F, W, O, L, Of> where
-    SelectStatement<ST, S, F, W, O, L, Of>: Expression,
+impl<ST, S, F, W, O, L, Of, D> NonAggregate for SelectStatement<ST, S, F, W, O, L, Of, D> where
+    SelectStatement<ST, S, F, W, O, L, Of, D>: Expression,
 {
 }
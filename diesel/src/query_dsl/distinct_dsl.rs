@@ -0,0 +1,36 @@
+use expression::Expression;
+use query_builder::{Query, AsQuery};
+use query_source::QuerySource;
+
+/// Adds a `DISTINCT ON` clause to a query, keeping only the first row for each
+/// distinct value of the given expression. If there was already a distinct
+/// clause, it will be overridden.
+///
+/// PostgreSQL requires that the order clause of the query starts with the
+/// same expression. This is checked when the query is built: calling
+/// `.distinct_on(users::name)` on a query ordered by `users::id` (or calling
+/// `.order(users::id)` after `.distinct_on(users::name)`) will fail to compile.
+/// Ordering by the expression itself, its `.asc()` or `.desc()`, or a tuple
+/// beginning with any of those is allowed.
+///
+/// `DISTINCT ON` is PostgreSQL specific, so queries using it can only be run
+/// on a `PgConnection`.
+///
+/// This is automatically implemented for the various query builder types.
+pub trait DistinctOnDsl<Expr: Expression> {
+    type Output: Query;
+
+    fn distinct_on(self, expr: Expr) -> Self::Output;
+}
+
+impl<T, Expr> DistinctOnDsl<Expr> for T where
+    Expr: Expression,
+    T: QuerySource + AsQuery,
+    T::Query: DistinctOnDsl<Expr>,
+{
+    type Output = <T::Query as DistinctOnDsl<Expr>>::Output;
+
+    fn distinct_on(self, expr: Expr) -> Self::Output {
+        self.as_query().distinct_on(expr)
+    }
+}
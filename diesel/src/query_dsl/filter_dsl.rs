@@ -54,8 +54,8 @@ impl<T, Predicate> FilterDsl<Predicate> for T where
 }
 
 impl<T: Table> NotFiltered for T {}
-impl<Left, Right> NotFiltered for InnerJoinSource<Left, Right> {}
-impl<Left, Right> NotFiltered for LeftOuterJoinSource<Left, Right> {}
+impl<Left, Right, On> NotFiltered for InnerJoinSource<Left, Right, On> {}
+impl<Left, Right, On> NotFiltered for LeftOuterJoinSource<Left, Right, On> {}
 
 use expression::{AsExpression, SelectableExpression};
 use expression::expression_methods::*;
@@ -4,6 +4,11 @@ use query_source::QuerySource;
 /// Sets the limit clause of a query. If there was already a limit clause, it
 /// will be overridden. This is automatically implemented for the various query
 /// builder types.
+///
+/// The limit is sent as a bind parameter rather than being written into the
+/// SQL, so paginated queries produce the same SQL regardless of page size.
+/// Backends which don't allow a parameterized limit have it written into the
+/// SQL instead.
 pub trait LimitDsl {
     type Output: Query;
 
@@ -1,5 +1,6 @@
 mod belonging_to_dsl;
 mod count_dsl;
+mod distinct_dsl;
 #[doc(hidden)]
 pub mod limit_dsl;
 #[doc(hidden)]
@@ -14,6 +15,7 @@ mod with_dsl;
 
 pub use self::belonging_to_dsl::BelongingToDsl;
 pub use self::count_dsl::CountDsl;
+pub use self::distinct_dsl::DistinctOnDsl;
 pub use self::filter_dsl::{FilterDsl, FindDsl};
 pub use self::limit_dsl::LimitDsl;
 pub use self::load_dsl::{LoadDsl, ExecuteDsl};
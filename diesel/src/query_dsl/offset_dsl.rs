@@ -4,6 +4,12 @@ use query_source::QuerySource;
 /// Sets the offset clause of a query. If there was already a offset clause, it
 /// will be overridden. This is automatically implemented for the various query
 /// builder types.
+///
+/// An offset can be used without calling
+/// [`limit`](trait.LimitDsl.html#tymethod.limit). PostgreSQL accepts `OFFSET`
+/// on its own, so no `LIMIT` clause is generated in that case. Backends which
+/// require a `LIMIT` alongside `OFFSET` render the largest limit they support
+/// instead.
 pub trait OffsetDsl {
     type Output: Query;
 
@@ -1,20 +1,23 @@
 use super::{QuerySource, Table};
 use query_builder::*;
-use expression::SelectableExpression;
-use types::IntoNullable;
+use query_builder::nodes::Join;
+use expression::{NonAggregate, SelectableExpression};
+use types::{Bool, IntoNullable};
 
 #[derive(Debug, Clone, Copy)]
 #[doc(hidden)]
-pub struct InnerJoinSource<Left, Right> {
+pub struct InnerJoinSource<Left, Right, On=ImplicitOn> {
     left: Left,
     right: Right,
+    on: On,
 }
 
-impl<Left, Right> InnerJoinSource<Left, Right> {
-    pub fn new(left: Left, right: Right) -> Self {
+impl<Left, Right, On> InnerJoinSource<Left, Right, On> {
+    pub fn new(left: Left, right: Right, on: On) -> Self {
         InnerJoinSource {
             left: left,
             right: right,
+            on: on,
         }
     }
 }
@@ -30,11 +33,24 @@ impl<Left, Right> QuerySource for InnerJoinSource<Left, Right> where
     }
 }
 
-impl<Left, Right> AsQuery for InnerJoinSource<Left, Right> where
-    Left: Table + JoinTo<Right, Inner>,
+impl<Left, Right, On> QuerySource for InnerJoinSource<Left, Right, ExplicitOn<On>> where
+    Left: Table,
     Right: Table,
+    On: SelectableExpression<InnerJoinSource<Left, Right>, SqlType=Bool> + NonAggregate + Clone,
+{
+    type FromClause = Join<Left::FromClause, Right::FromClause, On, Inner>;
+
+    fn from_clause(&self) -> Self::FromClause {
+        Join::new(self.left.from_clause(), self.right.from_clause(), self.on.0.clone(), Inner)
+    }
+}
+
+impl<Left, Right, On> AsQuery for InnerJoinSource<Left, Right, On> where
+    Left: Table,
+    Right: Table,
+    InnerJoinSource<Left, Right, On>: QuerySource,
     (Left::AllColumns, Right::AllColumns): SelectableExpression<
-                                   InnerJoinSource<Left, Right>,
+                                   InnerJoinSource<Left, Right, On>,
                                    (Left::SqlType, Right::SqlType),
                                >,
 {
@@ -52,16 +68,18 @@ impl<Left, Right> AsQuery for InnerJoinSource<Left, Right> where
 
 #[derive(Debug, Clone, Copy)]
 #[doc(hidden)]
-pub struct LeftOuterJoinSource<Left, Right> {
+pub struct LeftOuterJoinSource<Left, Right, On=ImplicitOn> {
     left: Left,
     right: Right,
+    on: On,
 }
 
-impl<Left, Right> LeftOuterJoinSource<Left, Right> {
-    pub fn new(left: Left, right: Right) -> Self {
+impl<Left, Right, On> LeftOuterJoinSource<Left, Right, On> {
+    pub fn new(left: Left, right: Right, on: On) -> Self {
         LeftOuterJoinSource {
             left: left,
             right: right,
+            on: on,
         }
     }
 }
@@ -77,12 +95,25 @@ impl<Left, Right> QuerySource for LeftOuterJoinSource<Left, Right> where
     }
 }
 
-impl<Left, Right> AsQuery for LeftOuterJoinSource<Left, Right> where
-    Left: Table + JoinTo<Right, LeftOuter>,
+impl<Left, Right, On> QuerySource for LeftOuterJoinSource<Left, Right, ExplicitOn<On>> where
+    Left: Table,
+    Right: Table,
+    On: SelectableExpression<InnerJoinSource<Left, Right>, SqlType=Bool> + NonAggregate + Clone,
+{
+    type FromClause = Join<Left::FromClause, Right::FromClause, On, LeftOuter>;
+
+    fn from_clause(&self) -> Self::FromClause {
+        Join::new(self.left.from_clause(), self.right.from_clause(), self.on.0.clone(), LeftOuter)
+    }
+}
+
+impl<Left, Right, On> AsQuery for LeftOuterJoinSource<Left, Right, On> where
+    Left: Table,
     Right: Table,
     Right::SqlType: IntoNullable,
+    LeftOuterJoinSource<Left, Right, On>: QuerySource,
     (Left::AllColumns, Right::AllColumns): SelectableExpression<
-                                   LeftOuterJoinSource<Left, Right>,
+                                   LeftOuterJoinSource<Left, Right, On>,
                                    (Left::SqlType, <Right::SqlType as IntoNullable>::Nullable),
                                >,
 {
@@ -98,6 +129,63 @@ impl<Left, Right> AsQuery for LeftOuterJoinSource<Left, Right> where
     }
 }
 
+/// A table paired with the condition to join it on, rather than the one from
+/// its association. This is the return type of
+/// [`Table::on`](trait.Table.html#method.on), and can be passed to
+/// `inner_join` or `left_outer_join`.
+#[derive(Debug, Clone, Copy)]
+pub struct OnClauseWrapper<Source, On> {
+    source: Source,
+    on: On,
+}
+
+impl<Source, On> OnClauseWrapper<Source, On> {
+    pub fn new(source: Source, on: On) -> Self {
+        OnClauseWrapper {
+            source: source,
+            on: on,
+        }
+    }
+}
+
+/// The right side of a join. This is either a table, which is joined using its
+/// association, or a table with an explicit condition from
+/// [`Table::on`](trait.Table.html#method.on).
+pub trait JoinTarget {
+    #[doc(hidden)]
+    type Table: Table;
+    #[doc(hidden)]
+    type On;
+    #[doc(hidden)]
+    fn into_table_and_on(self) -> (Self::Table, Self::On);
+}
+
+impl<T: Table> JoinTarget for T {
+    type Table = T;
+    type On = ImplicitOn;
+
+    fn into_table_and_on(self) -> (T, ImplicitOn) {
+        (self, ImplicitOn)
+    }
+}
+
+impl<T: Table, On> JoinTarget for OnClauseWrapper<T, On> {
+    type Table = T;
+    type On = ExplicitOn<On>;
+
+    fn into_table_and_on(self) -> (T, ExplicitOn<On>) {
+        (self.source, ExplicitOn(self.on))
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct ImplicitOn;
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct ExplicitOn<On>(On);
+
 /// Indicates that two tables can be used together in a JOIN clause.
 /// Implementations of this trait will be generated for you automatically by
 /// the [association annotations](FIXME: Add link) from codegen.
@@ -6,14 +6,16 @@ pub mod filter;
 #[doc(hidden)]
 pub mod joins;
 
+use std::error::Error;
+
 use backend::Backend;
 use expression::{Expression, SelectableExpression, NonAggregate};
 use query_builder::*;
 #[doc(hidden)]
 pub use self::joins::{InnerJoinSource, LeftOuterJoinSource};
-use types::{FromSqlRow, HasSqlType};
+use types::{Bool, FromSqlRow, HasSqlType};
 
-pub use self::joins::JoinTo;
+pub use self::joins::{JoinTarget, JoinTo, OnClauseWrapper};
 
 /// Trait indicating that a record can be queried from the database. This trait
 /// can be derived automatically. See the [codegen
@@ -25,6 +27,14 @@ pub trait Queryable<ST, DB> where
     type Row: FromSqlRow<ST, DB>;
 
     fn build(row: Self::Row) -> Self;
+
+    /// Adds context to an error returned while deserializing `Self::Row`. The
+    /// implementation generated by `#[derive(Queryable)]` uses this to name the
+    /// field which could not be deserialized.
+    #[doc(hidden)]
+    fn describe_error(error: Box<Error>) -> Box<Error> {
+        error
+    }
 }
 
 #[doc(hidden)]
@@ -51,18 +61,38 @@ pub trait Table: QuerySource + AsQuery + Sized {
     fn primary_key(&self) -> Self::PrimaryKey;
     fn all_columns() -> Self::AllColumns;
 
-    fn inner_join<T>(self, other: T) -> InnerJoinSource<Self, T> where
-        T: Table,
-        Self: JoinTo<T, joins::Inner>,
+    /// Joins this table to `other` with an `INNER JOIN`. Loading the result
+    /// directly gives a tuple of each side, such as `(User, Post)`. `other`
+    /// can be a table, which is joined using its association, or
+    /// `table.on(condition)`.
+    fn inner_join<T>(self, other: T) -> InnerJoinSource<Self, T::Table, T::On> where
+        T: JoinTarget,
+        InnerJoinSource<Self, T::Table, T::On>: QuerySource,
+    {
+        let (other, on) = other.into_table_and_on();
+        InnerJoinSource::new(self, other, on)
+    }
+
+    /// Joins this table to `other` with a `LEFT OUTER JOIN`. Every column of
+    /// `other` is nullable in the result, so loading it directly gives a tuple
+    /// such as `(User, Option<Post>)`. `other` can be a table, which is joined
+    /// using its association, or `table.on(condition)`.
+    fn left_outer_join<T>(self, other: T) -> LeftOuterJoinSource<Self, T::Table, T::On> where
+        T: JoinTarget,
+        LeftOuterJoinSource<Self, T::Table, T::On>: QuerySource,
     {
-        InnerJoinSource::new(self, other)
+        let (other, on) = other.into_table_and_on();
+        LeftOuterJoinSource::new(self, other, on)
     }
 
-    fn left_outer_join<T>(self, other: T) -> LeftOuterJoinSource<Self, T> where
-        T: Table,
-        Self: JoinTo<T, joins::LeftOuter>,
+    /// Pairs this table with the condition to join it on, for use with
+    /// `inner_join` or `left_outer_join`. The tables don't need to have an
+    /// association, but their columns must be selectable from the join (see
+    /// `select_column_workaround!`).
+    fn on<On>(self, on: On) -> OnClauseWrapper<Self, On> where
+        On: Expression<SqlType=Bool> + NonAggregate,
     {
-        LeftOuterJoinSource::new(self, other)
+        OnClauseWrapper::new(self, on)
     }
 }
 
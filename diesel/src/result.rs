@@ -11,6 +11,9 @@ pub enum Error {
     InvalidCString(NulError),
     DatabaseError(String),
     NotFound,
+    /// A query could not be built, for example because a bind parameter could
+    /// not be serialized.
+    QueryBuilderError(String),
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -78,6 +81,7 @@ impl Display for Error {
             &Error::InvalidCString(ref nul_err) => nul_err.fmt(f),
             &Error::DatabaseError(ref s) => write!(f, "{}", &s),
             &Error::NotFound => f.write_str("NotFound"),
+            &Error::QueryBuilderError(ref s) => write!(f, "{}", &s),
             &Error::__Nonexhaustive => unreachable!(),
         }
     }
@@ -89,6 +93,7 @@ impl StdError for Error {
             &Error::InvalidCString(ref nul_err) => nul_err.description(),
             &Error::DatabaseError(ref s) => &s,
             &Error::NotFound => "Record not found",
+            &Error::QueryBuilderError(ref s) => &s,
             &Error::__Nonexhaustive => unreachable!(),
         }
     }
@@ -129,3 +134,77 @@ impl<E: StdError> StdError for TransactionError<E> {
         }
     }
 }
+
+/// Returned when a single element of a row could not be deserialized. When
+/// loading a struct which derives `Queryable`, the names of the struct and the
+/// field are included, to make mismatches between the schema and the struct
+/// easier to find. The name of the column is included when the backend
+/// provides it.
+#[derive(Debug)]
+pub struct DeserializeFieldError {
+    /// The index of the element within the tuple or struct being loaded
+    pub index: usize,
+    /// The name of the struct being loaded, if known
+    pub struct_name: Option<&'static str>,
+    /// The name of the field which could not be deserialized, if known
+    pub field_name: Option<&'static str>,
+    /// The name of the column in the result set, if known
+    pub column_name: Option<String>,
+    /// The underlying error
+    pub error: Box<StdError>,
+}
+
+impl DeserializeFieldError {
+    #[doc(hidden)]
+    pub fn new(index: usize, error: Box<StdError>) -> Self {
+        DeserializeFieldError {
+            index: index,
+            struct_name: None,
+            field_name: None,
+            column_name: None,
+            error: error,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn with_column_name(mut self, column_name: Option<String>) -> Self {
+        self.column_name = column_name;
+        self
+    }
+
+    /// Sets the struct and field name, based on `self.index`. This is called by
+    /// the implementation of `Queryable` generated by `#[derive(Queryable)]`.
+    #[doc(hidden)]
+    pub fn name_field(mut self, struct_name: &'static str, field_names: &[&'static str]) -> Self {
+        if self.field_name.is_none() {
+            self.struct_name = Some(struct_name);
+            self.field_name = field_names.get(self.index).cloned();
+        }
+        self
+    }
+}
+
+impl Display for DeserializeFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.struct_name, self.field_name) {
+            (Some(struct_name), Some(field_name)) => {
+                try!(write!(f, "Error deserializing field `{}` of `{}`", field_name, struct_name));
+            }
+            _ => try!(write!(f, "Error deserializing element {} of row", self.index)),
+        }
+        if let Some(ref column_name) = self.column_name {
+            try!(write!(f, " (column `{}`)", column_name));
+        }
+        write!(f, ": {}", self.error)
+    }
+}
+
+impl StdError for DeserializeFieldError {
+    fn description(&self) -> &str {
+        "Error deserializing a field of a row"
+    }
+
+    fn cause(&self) -> Option<&StdError> {
+        Some(&*self.error)
+    }
+}
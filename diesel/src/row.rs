@@ -1,16 +1,38 @@
+#[cfg(feature = "postgres")]
 use db_result::PgResult;
 
 pub trait Row {
     fn take(&mut self) -> Option<&[u8]>;
     fn next_is_null(&self, count: usize) -> bool;
+
+    /// Skips the next `count` columns, as if `take` had been called for each
+    /// of them.
+    fn advance(&mut self, count: usize) {
+        for _ in 0..count {
+            self.take();
+        }
+    }
+
+    /// The index of the column which will be returned by the next call to
+    /// `take`, if the backend tracks it. This is used to describe errors.
+    fn column_index(&self) -> Option<usize> {
+        None
+    }
+
+    /// The name of the column at the given index, if the backend provides it.
+    fn column_name_at(&self, _idx: usize) -> Option<&str> {
+        None
+    }
 }
 
+#[cfg(feature = "postgres")]
 pub struct PgRow<'a> {
     db_result: &'a PgResult,
     row_idx: usize,
     col_idx: usize,
 }
 
+#[cfg(feature = "postgres")]
 impl<'a> PgRow<'a> {
     pub fn new(db_result: &'a PgResult, row_idx: usize) -> Self {
         PgRow {
@@ -21,6 +43,7 @@ impl<'a> PgRow<'a> {
     }
 }
 
+#[cfg(feature = "postgres")]
 impl<'a> Row for PgRow<'a> {
     fn take(&mut self) -> Option<&[u8]> {
         let current_idx = self.col_idx;
@@ -33,4 +56,16 @@ impl<'a> Row for PgRow<'a> {
             self.db_result.is_null(self.row_idx, self.col_idx + i)
         })
     }
+
+    fn advance(&mut self, count: usize) {
+        self.col_idx += count;
+    }
+
+    fn column_index(&self) -> Option<usize> {
+        Some(self.col_idx)
+    }
+
+    fn column_name_at(&self, idx: usize) -> Option<&str> {
+        self.db_result.column_name(idx)
+    }
 }
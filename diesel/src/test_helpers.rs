@@ -0,0 +1,22 @@
+extern crate dotenv;
+
+use self::dotenv::dotenv;
+use connection::{Connection, PgConnection};
+
+/// Connects to the database given by `DATABASE_URL`, which can be set in a
+/// `.env` file.
+pub fn connection() -> PgConnection {
+    dotenv().ok();
+
+    let connection_url = ::std::env::var("DATABASE_URL").ok()
+        .expect("DATABASE_URL must be set in order to run tests");
+    PgConnection::establish(&connection_url).unwrap()
+}
+
+/// Connects like [`connection`](fn.connection.html), and creates the given
+/// extension if it doesn't already exist.
+pub fn connection_with_extension(extension: &str) -> PgConnection {
+    let connection = connection();
+    connection.execute(&format!("CREATE EXTENSION IF NOT EXISTS {}", extension)).unwrap();
+    connection
+}
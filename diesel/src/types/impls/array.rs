@@ -5,6 +5,8 @@ use std::error::Error;
 use std::io::Write;
 
 use backend::{Pg, Debug, PgTypeMetadata};
+use expression::SelectableExpression;
+use expression::nullable::SelectableAsNullable;
 use query_source::Queryable;
 use row::Row;
 use super::option::UnexpectedNullError;
@@ -30,6 +32,48 @@ impl<T> HasSqlType<Array<T>> for Debug where
 impl<T> NotNull for Array<T> {
 }
 
+impl<T, Expr, QS> SelectableAsNullable<Expr, QS> for Array<T> where
+    Expr: SelectableExpression<QS>,
+{
+}
+
+/// Whether `ST` is itself an array type. `HasSqlType<Array<T>>` is the only
+/// implementation which has an OID, but no array OID, since PostgreSQL uses the
+/// same type for arrays of any number of dimensions.
+fn is_array_type<ST>() -> bool where
+    Pg: HasSqlType<ST>,
+{
+    let metadata = <Pg as HasSqlType<ST>>::metadata();
+    metadata.oid != 0 && metadata.array_oid == 0
+}
+
+/// Reads the length of an element of an array, and then its bytes. Returns
+/// `None` if the element is null.
+fn read_element<'a>(bytes: &mut &'a [u8]) -> Result<Option<&'a [u8]>, Box<Error>> {
+    let elem_size = try!(bytes.read_i32::<BigEndian>());
+    if elem_size == -1 {
+        return Ok(None);
+    }
+    if elem_size < 0 || elem_size as usize > bytes.len() {
+        let error_message = format!("Invalid length for an element of an array: {}", elem_size);
+        return Err(Box::<Error + Send + Sync>::from(error_message));
+    }
+    let (elem_bytes, new_bytes) = bytes.split_at(elem_size as usize);
+    *bytes = new_bytes;
+    Ok(Some(elem_bytes))
+}
+
+/// The OID of the elements sent by PostgreSQL is not checked, so an array of a
+/// user defined type (such as an enum or a composite type) can be loaded as long
+/// as the element type implements `FromSql` for the binary representation of
+/// that type. Since the OID of a user defined type is not known until runtime, a
+/// `HasSqlType` implementation for one may use an OID of 0. Such a type can be
+/// loaded, but cannot be sent as a bind parameter, either on its own or as the
+/// element of an array.
+///
+/// A multi-dimensional array is loaded as nested `Vec`s, such as
+/// `Array<Array<Integer>>` as `Vec<Vec<i32>>`. Each `Vec` within it is one row
+/// of the array.
 impl<T, ST> FromSql<Array<ST>, Pg> for Vec<T> where
     T: FromSql<ST, Pg>,
     Pg: HasSqlType<ST>,
@@ -37,29 +81,72 @@ impl<T, ST> FromSql<Array<ST>, Pg> for Vec<T> where
     fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
         let mut bytes = not_none!(bytes);
         let num_dimensions = try!(bytes.read_i32::<BigEndian>());
-        let has_null = try!(bytes.read_i32::<BigEndian>()) != 0;
-        let _oid = try!(bytes.read_i32::<BigEndian>());
+        let has_null = try!(bytes.read_i32::<BigEndian>());
+        let oid = try!(bytes.read_u32::<BigEndian>());
 
+        if num_dimensions < 0 {
+            return Err(Box::<Error + Send + Sync>::from("Invalid array: the number of dimensions was negative"));
+        }
         if num_dimensions == 0 {
+            if !bytes.is_empty() {
+                return Err(Box::<Error + Send + Sync>::from("Invalid array: an array with no dimensions had elements"));
+            }
             return Ok(Vec::new())
         }
 
-        let num_elements = try!(bytes.read_i32::<BigEndian>());
-        let lower_bound = try!(bytes.read_i32::<BigEndian>());
-
-        assert!(num_dimensions == 1, "multi-dimensional arrays are not supported");
-        assert!(lower_bound == 1, "lower bound must be 1");
-
-        (0..num_elements).map(|_| {
-            let elem_size = try!(bytes.read_i32::<BigEndian>());
-            if has_null && elem_size == -1 {
-                T::from_sql(None)
-            } else {
-                let (elem_bytes, new_bytes) = bytes.split_at(elem_size as usize);
-                bytes = new_bytes;
-                T::from_sql(Some(&elem_bytes))
+        let mut dimensions = Vec::new();
+        for _ in 0..num_dimensions {
+            let num_elements = try!(bytes.read_i32::<BigEndian>());
+            if num_elements < 0 {
+                return Err(Box::<Error + Send + Sync>::from("Invalid array: the number of elements was negative"));
             }
-        }).collect()
+            let lower_bound = try!(bytes.read_i32::<BigEndian>());
+            if lower_bound != 1 {
+                return Err(Box::<Error + Send + Sync>::from("arrays with a lower bound other than 1 are not supported"));
+            }
+            dimensions.push(num_elements);
+        }
+        let (num_elements, inner_dimensions) = (dimensions[0], &dimensions[1..]);
+
+        if inner_dimensions.is_empty() {
+            (0..num_elements).map(|index| {
+                match try!(read_element(&mut bytes)) {
+                    Some(elem_bytes) => T::from_sql(Some(elem_bytes)),
+                    None => T::from_sql(None).map_err(|_| {
+                        let error_message = format!("Unexpected null at index {} of an array. Arrays which \
+                                                     contain null must be loaded as Array<Nullable<T>>", index);
+                        Box::<Error + Send + Sync>::from(error_message) as Box<Error>
+                    }),
+                }
+            }).collect()
+        } else if !is_array_type::<ST>() {
+            let error_message = format!("Expected a 1-dimensional array, but it had {} dimensions. Load \
+                                         it as nested arrays, such as Array<Array<T>>", num_dimensions);
+            Err(Box::<Error + Send + Sync>::from(error_message))
+        } else {
+            // Each row is sent to `T` as an array with one less dimension.
+            let elements_per_row = inner_dimensions.iter().fold(1, |total, &dimension| total * dimension);
+            (0..num_elements).map(|_| {
+                let mut row = Vec::new();
+                try!(row.write_i32::<BigEndian>(inner_dimensions.len() as i32));
+                try!(row.write_i32::<BigEndian>(has_null));
+                try!(row.write_u32::<BigEndian>(oid));
+                for &dimension in inner_dimensions {
+                    try!(row.write_i32::<BigEndian>(dimension));
+                    try!(row.write_i32::<BigEndian>(1));
+                }
+                for _ in 0..elements_per_row {
+                    match try!(read_element(&mut bytes)) {
+                        Some(elem_bytes) => {
+                            try!(row.write_i32::<BigEndian>(elem_bytes.len() as i32));
+                            try!(row.write_all(elem_bytes));
+                        }
+                        None => try!(row.write_i32::<BigEndian>(-1)),
+                    }
+                }
+                T::from_sql(Some(&row))
+            }).collect()
+        }
     }
 }
 
@@ -120,26 +207,108 @@ impl<'a, ST, T> ToSql<Array<ST>, Pg> for &'a [T] where
     T: ToSql<ST, Pg>,
 {
     fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let element_oid = <Pg as HasSqlType<ST>>::metadata().oid;
+        if element_oid == 0 {
+            return Err(Box::<Error + Send + Sync>::from(
+                "Arrays of types whose OID is not known cannot be sent as bind parameters"));
+        }
+        if is_array_type::<ST>() {
+            return write_nested_array(*self, out);
+        }
+        // The header includes a flag for whether the array contains nulls, so
+        // the elements are serialized before anything is written.
+        let mut elements = Vec::with_capacity(ToSql::<Array<ST>, Pg>::size_hint(self));
+        let mut has_null = false;
+        let mut buffer = Vec::new();
+        for elem in self.iter() {
+            match try!(elem.to_sql(&mut buffer)) {
+                IsNull::Yes => {
+                    has_null = true;
+                    try!(elements.write_i32::<BigEndian>(-1));
+                }
+                IsNull::No => {
+                    try!(elements.write_i32::<BigEndian>(buffer.len() as i32));
+                    try!(elements.write_all(&buffer));
+                }
+            }
+            buffer.clear();
+        }
+
         let num_dimensions = 1;
         try!(out.write_i32::<BigEndian>(num_dimensions));
-        let flags = 0;
+        let flags = if has_null { 1 } else { 0 };
         try!(out.write_i32::<BigEndian>(flags));
-        try!(out.write_u32::<BigEndian>(Pg::metadata().oid));
+        try!(out.write_u32::<BigEndian>(element_oid));
         try!(out.write_i32::<BigEndian>(self.len() as i32));
         let lower_bound = 1;
         try!(out.write_i32::<BigEndian>(lower_bound));
+        try!(out.write_all(&elements));
 
-        let mut buffer = Vec::new();
-        for elem in self.iter() {
-            let is_null = try!(elem.to_sql(&mut buffer));
-            assert!(is_null == IsNull::No, "Arrays containing null are not supported");
-            try!(out.write_i32::<BigEndian>(buffer.len() as i32));
-            try!(out.write_all(&buffer));
-            buffer.clear();
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        let header_size = 20;
+        let element_sizes = self.iter().map(|elem| 4 + elem.size_hint());
+        header_size + element_sizes.fold(0, |total, size| total + size)
+    }
+}
+
+/// PostgreSQL doesn't have arrays of arrays. Instead, each row is sent as an
+/// array, and its elements are combined into a single array with one more
+/// dimension. Every row must have the same dimensions. An empty array of arrays
+/// can't be sent, as the OID of its elements is only known from its rows.
+fn write_nested_array<T, ST, W>(rows: &[T], out: &mut W) -> Result<IsNull, Box<Error>> where
+    T: ToSql<ST, Pg>,
+    W: Write,
+{
+    let mut header = None;
+    let mut has_null = false;
+    let mut elements = Vec::new();
+    let mut buffer = Vec::new();
+    for row in rows {
+        buffer.clear();
+        if let IsNull::Yes = try!(row.to_sql(&mut buffer)) {
+            return Err(Box::<Error + Send + Sync>::from("Multi-dimensional arrays cannot contain null rows"));
         }
+        let mut bytes = &buffer[..];
+        let num_dimensions = try!(bytes.read_i32::<BigEndian>());
+        has_null = has_null || try!(bytes.read_i32::<BigEndian>()) != 0;
+        let oid = try!(bytes.read_u32::<BigEndian>());
+        let mut dimensions = Vec::new();
+        for _ in 0..num_dimensions {
+            dimensions.push(try!(bytes.read_i32::<BigEndian>()));
+            let _lower_bound = try!(bytes.read_i32::<BigEndian>());
+        }
+        match header {
+            Some((ref expected, _)) if *expected != dimensions => {
+                return Err(Box::<Error + Send + Sync>::from(
+                    "Every row of a multi-dimensional array must have the same dimensions"));
+            }
+            Some(_) => {}
+            None => header = Some((dimensions, oid)),
+        }
+        try!(elements.write_all(bytes));
+    }
 
-        Ok(IsNull::No)
+    let (inner_dimensions, element_oid) = match header {
+        Some(header) => header,
+        None => return Err(Box::<Error + Send + Sync>::from(
+            "An empty multi-dimensional array cannot be sent as a bind parameter")),
+    };
+    try!(out.write_i32::<BigEndian>(inner_dimensions.len() as i32 + 1));
+    let flags = if has_null { 1 } else { 0 };
+    try!(out.write_i32::<BigEndian>(flags));
+    try!(out.write_u32::<BigEndian>(element_oid));
+    let lower_bound = 1;
+    try!(out.write_i32::<BigEndian>(rows.len() as i32));
+    try!(out.write_i32::<BigEndian>(lower_bound));
+    for dimension in inner_dimensions {
+        try!(out.write_i32::<BigEndian>(dimension));
+        try!(out.write_i32::<BigEndian>(lower_bound));
     }
+    try!(out.write_all(&elements));
+    Ok(IsNull::No)
 }
 
 impl<ST, T> ToSql<Array<ST>, Pg> for Vec<T> where
@@ -149,4 +318,127 @@ impl<ST, T> ToSql<Array<ST>, Pg> for Vec<T> where
     fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
         (&self as &[T]).to_sql(out)
     }
+
+    fn size_hint(&self) -> usize {
+        ToSql::<Array<ST>, Pg>::size_hint(&(&self as &[T]))
+    }
+}
+
+#[test]
+fn arrays_containing_null_set_the_null_flag() {
+    use types::{Integer, Nullable};
+
+    let mut bytes = Vec::new();
+    let data = vec![Some(1), None];
+    ToSql::<Array<Nullable<Integer>>, Pg>::to_sql(&data, &mut bytes).unwrap();
+    let expected = vec![
+        0, 0, 0, 1, // dimensions
+        0, 0, 0, 1, // has null
+        0, 0, 0, 23, // element oid
+        0, 0, 0, 2, // number of elements
+        0, 0, 0, 1, // lower bound
+        0, 0, 0, 4, 0, 0, 0, 1, // 1
+        255, 255, 255, 255, // null
+    ];
+    assert_eq!(expected, bytes);
+
+    let round_tripped: Vec<Option<i32>> = FromSql::<Array<Nullable<Integer>>, Pg>::from_sql(Some(&bytes)).unwrap();
+    assert_eq!(data, round_tripped);
+}
+
+#[test]
+fn arrays_containing_null_must_have_nullable_elements() {
+    use types::Integer;
+
+    let bytes = vec![
+        0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 23, 0, 0, 0, 2, 0, 0, 0, 1,
+        0, 0, 0, 4, 0, 0, 0, 1,
+        255, 255, 255, 255,
+    ];
+    let error = <Vec<i32> as FromSql<Array<Integer>, Pg>>::from_sql(Some(&bytes)).unwrap_err();
+    assert_eq!("Unexpected null at index 1 of an array. Arrays which contain null must be \
+               loaded as Array<Nullable<T>>", error.to_string());
+}
+
+#[test]
+fn truncated_array_elements_are_an_error() {
+    use types::Integer;
+
+    let bytes = vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0];
+    assert!(<Vec<i32> as FromSql<Array<Integer>, Pg>>::from_sql(Some(&bytes)).is_err());
+}
+
+#[test]
+fn invalid_array_headers_are_an_error() {
+    use types::Integer;
+
+    let negative_dimensions = vec![255, 255, 255, 255, 0, 0, 0, 0, 0, 0, 0, 23];
+    let error = <Vec<i32> as FromSql<Array<Integer>, Pg>>::from_sql(Some(&negative_dimensions)).unwrap_err();
+    assert_eq!("Invalid array: the number of dimensions was negative", error.to_string());
+
+    let no_dimensions_with_elements = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 4, 0, 0, 0, 1];
+    let error = <Vec<i32> as FromSql<Array<Integer>, Pg>>::from_sql(Some(&no_dimensions_with_elements)).unwrap_err();
+    assert_eq!("Invalid array: an array with no dimensions had elements", error.to_string());
+
+    let negative_elements = vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 23, 255, 255, 255, 255, 0, 0, 0, 1];
+    let error = <Vec<i32> as FromSql<Array<Integer>, Pg>>::from_sql(Some(&negative_elements)).unwrap_err();
+    assert_eq!("Invalid array: the number of elements was negative", error.to_string());
+
+    let empty = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 23];
+    assert_eq!(Ok(Vec::new()), <Vec<i32> as FromSql<Array<Integer>, Pg>>::from_sql(Some(&empty))
+        .map_err(|e| e.to_string()));
+}
+
+#[test]
+fn nested_arrays_are_sent_as_one_multi_dimensional_array() {
+    use types::Integer;
+
+    let mut bytes = Vec::new();
+    let data = vec![vec![1, 2], vec![3, 4]];
+    ToSql::<Array<Array<Integer>>, Pg>::to_sql(&data, &mut bytes).unwrap();
+    let expected = vec![
+        0, 0, 0, 2, // dimensions
+        0, 0, 0, 0, // has null
+        0, 0, 0, 23, // element oid
+        0, 0, 0, 2, 0, 0, 0, 1, // number of rows, lower bound
+        0, 0, 0, 2, 0, 0, 0, 1, // number of columns, lower bound
+        0, 0, 0, 4, 0, 0, 0, 1,
+        0, 0, 0, 4, 0, 0, 0, 2,
+        0, 0, 0, 4, 0, 0, 0, 3,
+        0, 0, 0, 4, 0, 0, 0, 4,
+    ];
+    assert_eq!(expected, bytes);
+
+    let round_tripped: Vec<Vec<i32>> = FromSql::<Array<Array<Integer>>, Pg>::from_sql(Some(&bytes)).unwrap();
+    assert_eq!(data, round_tripped);
+}
+
+#[test]
+fn nested_arrays_must_be_rectangular() {
+    use types::Integer;
+
+    let mut bytes = Vec::new();
+    let data = vec![vec![1, 2], vec![3]];
+    assert!(ToSql::<Array<Array<Integer>>, Pg>::to_sql(&data, &mut bytes).is_err());
+}
+
+#[test]
+fn multi_dimensional_arrays_must_be_loaded_as_nested_arrays() {
+    use types::Integer;
+
+    let bytes = vec![
+        0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 23,
+        0, 0, 0, 1, 0, 0, 0, 1,
+        0, 0, 0, 1, 0, 0, 0, 1,
+        0, 0, 0, 4, 0, 0, 0, 1,
+    ];
+    assert!(<Vec<i32> as FromSql<Array<Integer>, Pg>>::from_sql(Some(&bytes)).is_err());
+}
+
+#[test]
+fn array_size_hint_includes_header_and_element_lengths() {
+    use types::Text;
+
+    let data = vec!["Hello", "world!"];
+    assert_eq!(20 + 4 + 5 + 4 + 6, ToSql::<Array<Text>, Pg>::size_hint(&data));
 }
@@ -0,0 +1,110 @@
+//! PostgreSQL sends `bit` and `varbit` values as the number of bits, followed by
+//! the bits themselves, 8 to a byte. The most significant bit of each byte comes
+//! first, and the last byte is padded with zeroes.
+extern crate byteorder;
+
+use self::byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+use std::error::Error;
+use std::io::Write;
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, IsNull, ToSql};
+
+primitive_impls! {
+    Bit -> (Vec<bool>, 1560, 1561),
+    Varbit -> (Vec<bool>, 1562, 1563),
+}
+
+fn bits_from_sql(bytes: Option<&[u8]>) -> Result<Vec<bool>, Box<Error>> {
+    let mut bytes = not_none!(bytes);
+    let num_bits = try!(bytes.read_i32::<BigEndian>());
+    if num_bits < 0 || (num_bits as usize + 7) / 8 != bytes.len() {
+        let error_message = format!("Invalid bit string of {} bits: {:?}", num_bits, bytes);
+        return Err(Box::<Error + Send + Sync>::from(error_message));
+    }
+    Ok((0..num_bits as usize).map(|i| bytes[i / 8] & (0x80 >> (i % 8)) != 0).collect())
+}
+
+fn bits_to_sql<W: Write>(bits: &[bool], out: &mut W) -> Result<IsNull, Box<Error>> {
+    try!(out.write_i32::<BigEndian>(bits.len() as i32));
+    for byte_bits in bits.chunks(8) {
+        let byte = byte_bits.iter().enumerate()
+            .fold(0u8, |byte, (i, &bit)| if bit { byte | 0x80 >> i } else { byte });
+        try!(out.write_all(&[byte]));
+    }
+    Ok(IsNull::No)
+}
+
+impl FromSql<types::Bit, Pg> for Vec<bool> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        bits_from_sql(bytes)
+    }
+}
+
+impl ToSql<types::Bit, Pg> for Vec<bool> {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        bits_to_sql(self, out)
+    }
+
+    fn size_hint(&self) -> usize {
+        4 + (self.len() + 7) / 8
+    }
+}
+
+impl FromSql<types::Varbit, Pg> for Vec<bool> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        bits_from_sql(bytes)
+    }
+}
+
+impl ToSql<types::Varbit, Pg> for Vec<bool> {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        bits_to_sql(self, out)
+    }
+
+    fn size_hint(&self) -> usize {
+        4 + (self.len() + 7) / 8
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use backend::Pg;
+    use expression::dsl::sql;
+    use prelude::*;
+    use test_helpers::connection;
+    use types::{Bit, FromSql, ToSql, Varbit};
+
+    #[test]
+    fn bits_are_sent_most_significant_bit_first() {
+        let bits = vec![true, false, true, true, false, false, false, false, true, true];
+        let mut bytes = Vec::new();
+        ToSql::<Varbit, Pg>::to_sql(&bits, &mut bytes).unwrap();
+        assert_eq!(vec![0, 0, 0, 10, 0b10110000, 0b11000000], bytes);
+        assert_eq!(bits, <Vec<bool> as FromSql<Varbit, Pg>>::from_sql(Some(&bytes)).unwrap());
+    }
+
+    #[test]
+    fn bit_strings_of_the_wrong_length_are_an_error() {
+        assert!(<Vec<bool> as FromSql<Bit, Pg>>::from_sql(Some(&[0, 0, 0, 9, 0])).is_err());
+    }
+
+    #[test]
+    fn bit_strings_round_trip_through_postgres() {
+        let connection = connection();
+        let bits = vec![true, false, true, true];
+        let query = select(sql::<Bit>("B'1011'"));
+        assert_eq!(Ok(bits.clone()), query.get_result(&connection));
+        let query = select(sql::<Bit>("B'1011'").eq(bits.clone()));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+
+        let query = select(sql::<Varbit>("B'1011'::varbit"));
+        assert_eq!(Ok(bits.clone()), query.get_result(&connection));
+        let query = select(sql::<Varbit>("B'1011'::varbit").eq(bits));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+}
@@ -0,0 +1,89 @@
+//! Diesel always asks PostgreSQL for results in the binary format, where a
+//! `bytea` is sent as its raw bytes, so `FromSql<Binary, Pg>` never needs to
+//! decode text. Output obtained some other way, such as from `COPY ... TO` in
+//! text format, uses either the `hex` or the `escape` text format, depending on
+//! the `bytea_output` setting. `bytea_from_text` decodes both.
+use std::error::Error;
+
+fn invalid_bytea(text: &[u8]) -> Box<Error> {
+    let error_message = format!("Invalid bytea text: {:?}", String::from_utf8_lossy(text));
+    Box::<Error + Send + Sync>::from(error_message)
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'...b'9' => Some(byte - b'0'),
+        b'a'...b'f' => Some(byte - b'a' + 10),
+        b'A'...b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn octal_value(bytes: &[u8]) -> Option<u8> {
+    let mut value = 0u16;
+    for &byte in bytes {
+        match byte {
+            b'0'...b'7' => value = value * 8 + (byte - b'0') as u16,
+            _ => return None,
+        }
+    }
+    if value > 0xFF { None } else { Some(value as u8) }
+}
+
+/// Decodes the text representation of a `bytea`, in either the `hex` format
+/// (`\x4869`) or the `escape` format (`Hi\000`).
+pub fn bytea_from_text(text: &[u8]) -> Result<Vec<u8>, Box<Error>> {
+    if text.starts_with(b"\\x") {
+        let hex: Vec<u8> = text[2..].iter().cloned()
+            .filter(|b| !(*b as char).is_whitespace())
+            .collect();
+        if hex.len() % 2 != 0 {
+            return Err(invalid_bytea(text));
+        }
+        hex.chunks(2).map(|pair| {
+            match (hex_value(pair[0]), hex_value(pair[1])) {
+                (Some(high), Some(low)) => Ok(high << 4 | low),
+                _ => Err(invalid_bytea(text)),
+            }
+        }).collect()
+    } else {
+        let mut result = Vec::with_capacity(text.len());
+        let mut i = 0;
+        while i < text.len() {
+            if text[i] != b'\\' {
+                result.push(text[i]);
+                i += 1;
+            } else if text.get(i + 1) == Some(&b'\\') {
+                result.push(b'\\');
+                i += 2;
+            } else {
+                match text.get(i + 1..i + 4).and_then(octal_value) {
+                    Some(byte) => result.push(byte),
+                    None => return Err(invalid_bytea(text)),
+                }
+                i += 4;
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bytea_from_text;
+
+    #[test]
+    fn hex_format_is_decoded() {
+        assert_eq!(vec![0x48, 0x69, 0x00, 0xff], bytea_from_text(b"\\x486900FF").unwrap());
+        assert_eq!(Vec::<u8>::new(), bytea_from_text(b"\\x").unwrap());
+        assert!(bytea_from_text(b"\\x486").is_err());
+        assert!(bytea_from_text(b"\\x4g").is_err());
+    }
+
+    #[test]
+    fn escape_format_is_decoded() {
+        assert_eq!(b"Hi\0\\\xff".to_vec(), bytea_from_text(b"Hi\\000\\\\\\377").unwrap());
+        assert!(bytea_from_text(b"\\9").is_err());
+        assert!(bytea_from_text(b"trailing\\").is_err());
+    }
+}
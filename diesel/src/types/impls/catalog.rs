@@ -0,0 +1,131 @@
+//! Types used by PostgreSQL's system catalogs. `regclass` and `regtype` are
+//! sent as the `oid` of the table or type. To load the name instead, cast the
+//! value to `Text`.
+//!
+//! An `oidvector` is sent like a one dimensional array of `oid`, but its lower
+//! bound is always 0 and it can't contain nulls.
+extern crate byteorder;
+
+use self::byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+use std::error::Error;
+use std::io::Write;
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, IsNull, ToSql};
+
+primitive_impls! {
+    Regclass -> (u32, 2205, 2210),
+    Regtype -> (u32, 2206, 2211),
+    OidVector -> (Vec<u32>, 30, 1013),
+}
+
+const OID_OID: u32 = 26;
+
+impl FromSql<types::Regclass, Pg> for u32 {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        FromSql::<types::Oid, Pg>::from_sql(bytes)
+    }
+}
+
+impl ToSql<types::Regclass, Pg> for u32 {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        ToSql::<types::Oid, Pg>::to_sql(self, out)
+    }
+}
+
+impl FromSql<types::Regtype, Pg> for u32 {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        FromSql::<types::Oid, Pg>::from_sql(bytes)
+    }
+}
+
+impl ToSql<types::Regtype, Pg> for u32 {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        ToSql::<types::Oid, Pg>::to_sql(self, out)
+    }
+}
+
+fn invalid_oidvector(bytes: &[u8]) -> Box<Error> {
+    let error_message = format!("Invalid oidvector: {:?}", bytes);
+    Box::<Error + Send + Sync>::from(error_message)
+}
+
+impl FromSql<types::OidVector, Pg> for Vec<u32> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let original = not_none!(bytes);
+        let mut bytes = original;
+        let num_dimensions = try!(bytes.read_i32::<BigEndian>());
+        let has_null = try!(bytes.read_i32::<BigEndian>());
+        let _element_oid = try!(bytes.read_u32::<BigEndian>());
+        if num_dimensions == 0 {
+            return Ok(Vec::new());
+        }
+        let num_elements = try!(bytes.read_i32::<BigEndian>());
+        let _lower_bound = try!(bytes.read_i32::<BigEndian>());
+        if num_dimensions != 1 || has_null != 0 || num_elements < 0 {
+            return Err(invalid_oidvector(original));
+        }
+
+        let mut oids = Vec::with_capacity(num_elements as usize);
+        for _ in 0..num_elements {
+            if try!(bytes.read_i32::<BigEndian>()) != 4 {
+                return Err(invalid_oidvector(original));
+            }
+            oids.push(try!(bytes.read_u32::<BigEndian>()));
+        }
+        Ok(oids)
+    }
+}
+
+impl ToSql<types::OidVector, Pg> for Vec<u32> {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        try!(out.write_i32::<BigEndian>(1));
+        try!(out.write_i32::<BigEndian>(0));
+        try!(out.write_u32::<BigEndian>(OID_OID));
+        try!(out.write_i32::<BigEndian>(self.len() as i32));
+        try!(out.write_i32::<BigEndian>(0));
+        for oid in self {
+            try!(out.write_i32::<BigEndian>(4));
+            try!(out.write_u32::<BigEndian>(*oid));
+        }
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        20 + 8 * self.len()
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use expression::dsl::sql;
+    use prelude::*;
+    use test_helpers::connection;
+    use types::{OidVector, Regclass, Regtype, Text};
+
+    #[test]
+    fn regclass_and_regtype_are_loaded_as_oids() {
+        let connection = connection();
+        let query = select(sql::<Regtype>("'int4'::regtype"));
+        assert_eq!(Ok(23), query.get_result::<u32>(&connection));
+        let query = select(sql::<Regclass>("'pg_class'::regclass"));
+        assert_eq!(Ok(1259), query.get_result::<u32>(&connection));
+        let query = select(23u32.into_sql::<Regtype>().cast::<Text>());
+        assert_eq!(Ok("integer".to_string()), query.get_result(&connection));
+    }
+
+    #[test]
+    fn oidvectors_round_trip() {
+        let connection = connection();
+        let query = select(sql::<OidVector>("'23 25'::oidvector"));
+        assert_eq!(Ok(vec![23, 25]), query.get_result::<Vec<u32>>(&connection));
+        let query = select(sql::<OidVector>("''::oidvector"));
+        assert_eq!(Ok(Vec::<u32>::new()), query.get_result::<Vec<u32>>(&connection));
+        let query = select(vec![16u32, 20].into_sql::<OidVector>().cast::<Text>());
+        assert_eq!(Ok("16 20".to_string()), query.get_result(&connection));
+    }
+}
@@ -0,0 +1,162 @@
+//! `Char` is a blank padded string, and is sent as its UTF-8 bytes, including
+//! the padding. PostgreSQL's internal `"char"` type is a single byte, which we
+//! expose as `TinyInt`. It can also be loaded as a `char` when it holds an ASCII
+//! character.
+use std::borrow::Cow;
+use std::error::Error;
+use std::io::Write;
+
+use backend::{Backend, Pg};
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, IsNull, ToSql};
+
+primitive_impls! {
+    Char -> (String, 1042, 1014),
+}
+
+queryable_impls! {
+    Char -> char,
+    TinyInt -> char,
+}
+
+expression_impls! {
+    Char -> &'a str,
+    Char -> char,
+    TinyInt -> char,
+}
+
+as_expression_impls! {
+    Char -> Cow<'a, str>,
+}
+
+impl<DB> FromSql<types::Char, DB> for String where
+    DB: Backend,
+    String: FromSql<types::VarChar, DB>,
+{
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        <Self as FromSql<types::VarChar, DB>>::from_sql(bytes)
+    }
+}
+
+impl<DB> ToSql<types::Char, DB> for String where
+    DB: Backend,
+    for<'a> &'a str: ToSql<types::Char, DB>,
+{
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        (&self as &str).to_sql(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<'a, DB> ToSql<types::Char, DB> for &'a str where
+    DB: Backend,
+    &'a str: ToSql<types::VarChar, DB>,
+{
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        ToSql::<types::VarChar, DB>::to_sql(self, out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Loads a `CHAR(1)` value. Longer values may be loaded as long as everything
+/// after the first character is padding.
+impl<DB> FromSql<types::Char, DB> for char where
+    DB: Backend,
+    String: FromSql<types::VarChar, DB>,
+{
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let string = try!(<String as FromSql<types::VarChar, DB>>::from_sql(bytes));
+        let mut chars = string.trim_right_matches(' ').chars();
+        match (chars.next(), chars.next()) {
+            (None, _) => Ok(' '),
+            (Some(c), None) => Ok(c),
+            _ => {
+                let error_message = format!("Expected a single character, found {:?}", string);
+                Err(Box::<Error + Send + Sync>::from(error_message))
+            }
+        }
+    }
+}
+
+impl<DB: Backend> ToSql<types::Char, DB> for char {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        try!(out.write_all(self.to_string().as_bytes()));
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len_utf8()
+    }
+}
+
+impl FromSql<types::TinyInt, Pg> for char {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let byte = try!(<i8 as FromSql<types::TinyInt, Pg>>::from_sql(bytes));
+        if byte < 0 {
+            let error_message = format!("\"char\" value {} is not an ASCII character", byte as u8);
+            return Err(Box::<Error + Send + Sync>::from(error_message));
+        }
+        Ok(byte as u8 as char)
+    }
+}
+
+impl ToSql<types::TinyInt, Pg> for char {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        if *self as u32 > 0x7F {
+            let error_message = format!("{:?} can't be stored in a \"char\", \
+                                         as it isn't an ASCII character", self);
+            return Err(Box::<Error + Send + Sync>::from(error_message));
+        }
+        ToSql::<types::TinyInt, Pg>::to_sql(&(*self as u8 as i8), out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use backend::Pg;
+    use std::borrow::Cow;
+    use types::{Char, FromSql, TinyInt, ToSql};
+
+    #[test]
+    fn padding_is_ignored_when_loading_a_char() {
+        assert_eq!('Y', <char as FromSql<Char, Pg>>::from_sql(Some(b"Y  ")).unwrap());
+        assert_eq!(' ', <char as FromSql<Char, Pg>>::from_sql(Some(b" ")).unwrap());
+        assert_eq!('é', <char as FromSql<Char, Pg>>::from_sql(Some("é".as_bytes())).unwrap());
+        assert!(<char as FromSql<Char, Pg>>::from_sql(Some(b"YN")).is_err());
+    }
+
+    #[test]
+    fn chars_are_sent_as_utf8() {
+        let mut bytes = Vec::new();
+        ToSql::<Char, Pg>::to_sql(&'é', &mut bytes).unwrap();
+        assert_eq!("é".as_bytes(), &*bytes);
+    }
+
+    #[test]
+    fn strings_can_be_bound_to_char_without_allocating() {
+        let mut bytes = Vec::new();
+        let borrowed: Cow<str> = Cow::Borrowed("ab");
+        ToSql::<Char, Pg>::to_sql(&"Y", &mut bytes).unwrap();
+        ToSql::<Char, Pg>::to_sql(&borrowed, &mut bytes).unwrap();
+        assert_eq!(b"Yab".to_vec(), bytes);
+    }
+
+    #[test]
+    fn internal_char_type_only_holds_ascii() {
+        let mut bytes = Vec::new();
+        ToSql::<TinyInt, Pg>::to_sql(&'a', &mut bytes).unwrap();
+        assert_eq!(vec![b'a'], bytes);
+        assert!(ToSql::<TinyInt, Pg>::to_sql(&'é', &mut bytes).is_err());
+        assert_eq!('a', <char as FromSql<TinyInt, Pg>>::from_sql(Some(b"a")).unwrap());
+        assert!(<char as FromSql<TinyInt, Pg>>::from_sql(Some(&[200])).is_err());
+    }
+}
@@ -1,29 +1,64 @@
-//! This module makes it possible to map `chrono::DateTime` values to postgres `Date`
-//! and `Timestamp` fields. It is enabled with the `chrono` feature.
+//! This module makes it possible to map `chrono::NaiveDate`, `NaiveTime`,
+//! `NaiveDateTime`, `DateTime` and `Duration` values to postgres `Date`, `Time`,
+//! `Timestamp`, `Timestamptz` and `Interval` fields. It is enabled with the
+//! `chrono` feature.
+//!
+//! Values which chrono can't represent, such as `'infinity'::timestamp`, return
+//! an error when they are loaded. To load infinite values, use
+//! `data_types::MaybeInfinite<NaiveDateTime>`.
+//!
+//! `Timestamptz` values can be loaded as `DateTime<UTC>`, `DateTime<Local>` or
+//! `DateTime<FixedOffset>`. PostgreSQL sends these values in UTC, with no
+//! information about the offset they were written with, so a
+//! `DateTime<FixedOffset>` will always be loaded with an offset of zero. Any
+//! `DateTime` can be sent, and will be converted to UTC first.
+//!
+//! A `NaiveDateTime` can also be used with a `Timestamptz` column. Since it has
+//! no offset, the type it is wrapped in chooses how it is interpreted. It is
+//! never interpreted using the time zone of the database session.
+//!
+//! - A plain `NaiveDateTime` is in UTC, both when it is sent and when it is
+//!   loaded.
+//! - `data_types::LocalNaiveDateTime` is in the local time zone of the
+//!   application. Sending a local time which doesn't exist, or which is
+//!   ambiguous because of a daylight saving time change, returns an error.
 extern crate chrono;
 
 use std::error::Error;
 use std::io::Write;
-use self::chrono::{Duration, NaiveDateTime, NaiveDate, NaiveTime};
+use self::chrono::{DateTime, Duration, FixedOffset, Local, NaiveDateTime, NaiveDate, NaiveTime, TimeZone, UTC};
 use self::chrono::naive::date;
+use self::chrono::offset::LocalResult;
 
 use backend::Pg;
 use expression::AsExpression;
 use expression::bound::Bound;
 use query_source::Queryable;
-use super::{PgDate, PgTime, PgTimestamp};
-use types::{self, Date, FromSql, IsNull, Time, Timestamp, ToSql};
+use super::{interval_to_microseconds, PgDate, PgInterval, PgTime, PgTimestamp};
+use types::{self, Date, FromSql, Interval, IsNull, Time, Timestamp, Timestamptz, ToSql};
 
 expression_impls! {
     Date -> NaiveDate,
+    Interval -> Duration,
     Time -> NaiveTime,
     Timestamp -> NaiveDateTime,
+    Timestamptz -> NaiveDateTime,
+    Timestamptz -> LocalNaiveDateTime,
+    Timestamptz -> DateTime<UTC>,
+    Timestamptz -> DateTime<Local>,
+    Timestamptz -> DateTime<FixedOffset>,
 }
 
 queryable_impls! {
     Date -> NaiveDate,
+    Interval -> Duration,
     Time -> NaiveTime,
     Timestamp -> NaiveDateTime,
+    Timestamptz -> NaiveDateTime,
+    Timestamptz -> LocalNaiveDateTime,
+    Timestamptz -> DateTime<UTC>,
+    Timestamptz -> DateTime<Local>,
+    Timestamptz -> DateTime<FixedOffset>,
 }
 
 // Postgres timestamps start from January 1st 2000.
@@ -34,7 +69,14 @@ fn pg_epoch() -> NaiveDateTime {
 impl FromSql<Timestamp, Pg> for NaiveDateTime {
     fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
         let PgTimestamp(offset) = try!(FromSql::<Timestamp, Pg>::from_sql(bytes));
-        Ok(pg_epoch() + Duration::microseconds(offset))
+        match pg_epoch().checked_add(Duration::microseconds(offset)) {
+            Some(time) => Ok(time),
+            None => {
+                let error_message = format!("{:?} is outside of the range that chrono can represent",
+                                            PgTimestamp(offset));
+                Err(Box::<Error + Send + Sync>::from(error_message))
+            }
+        }
     }
 }
 
@@ -51,6 +93,75 @@ impl ToSql<Timestamp, Pg> for NaiveDateTime {
     }
 }
 
+impl FromSql<Timestamptz, Pg> for NaiveDateTime {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        FromSql::<Timestamp, Pg>::from_sql(bytes)
+    }
+}
+
+impl ToSql<Timestamptz, Pg> for NaiveDateTime {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        ToSql::<Timestamp, Pg>::to_sql(self, out)
+    }
+}
+
+/// A `NaiveDateTime` in the local time zone of the application. When used with
+/// a `Timestamptz` column, it is converted from local time when it is sent, and
+/// to local time when it is loaded. A plain `NaiveDateTime` is treated as UTC
+/// instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LocalNaiveDateTime(pub NaiveDateTime);
+
+impl FromSql<Timestamptz, Pg> for LocalNaiveDateTime {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let local = try!(<DateTime<Local> as FromSql<Timestamptz, Pg>>::from_sql(bytes));
+        Ok(LocalNaiveDateTime(local.naive_local()))
+    }
+}
+
+impl ToSql<Timestamptz, Pg> for LocalNaiveDateTime {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        match Local.from_local_datetime(&self.0) {
+            LocalResult::Single(local) => ToSql::<Timestamptz, Pg>::to_sql(&local, out),
+            LocalResult::Ambiguous(..) => {
+                let error_message = format!("{} is ambiguous in the local time zone", self.0);
+                Err(Box::<Error + Send + Sync>::from(error_message))
+            }
+            LocalResult::None => {
+                let error_message = format!("{} does not exist in the local time zone", self.0);
+                Err(Box::<Error + Send + Sync>::from(error_message))
+            }
+        }
+    }
+}
+
+impl FromSql<Timestamptz, Pg> for DateTime<UTC> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let naive_date_time = try!(<NaiveDateTime as FromSql<Timestamptz, Pg>>::from_sql(bytes));
+        Ok(DateTime::from_utc(naive_date_time, UTC))
+    }
+}
+
+impl FromSql<Timestamptz, Pg> for DateTime<Local> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let utc = try!(<DateTime<UTC> as FromSql<Timestamptz, Pg>>::from_sql(bytes));
+        Ok(utc.with_timezone(&Local))
+    }
+}
+
+impl FromSql<Timestamptz, Pg> for DateTime<FixedOffset> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let utc = try!(<DateTime<UTC> as FromSql<Timestamptz, Pg>>::from_sql(bytes));
+        Ok(utc.with_timezone(&FixedOffset::east(0)))
+    }
+}
+
+impl<TZ: TimeZone> ToSql<Timestamptz, Pg> for DateTime<TZ> {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        ToSql::<Timestamptz, Pg>::to_sql(&self.naive_utc(), out)
+    }
+}
+
 fn midnight() -> NaiveTime {
     NaiveTime::from_hms(0, 0, 0)
 }
@@ -98,28 +209,40 @@ impl FromSql<Date, Pg> for NaiveDate {
     }
 }
 
-#[cfg(test)]
+impl ToSql<Interval, Pg> for Duration {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        match self.num_microseconds() {
+            Some(microseconds) => {
+                let interval = PgInterval::from_microseconds(microseconds);
+                ToSql::<Interval, Pg>::to_sql(&interval, out)
+            }
+            None => {
+                let error_message = format!("{:?} is too long to be sent as an interval", self);
+                Err(Box::<Error + Send + Sync>::from(error_message))
+            }
+        }
+    }
+}
+
+impl FromSql<Interval, Pg> for Duration {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let interval = try!(<PgInterval as FromSql<Interval, Pg>>::from_sql(bytes));
+        interval_to_microseconds(interval).map(Duration::microseconds)
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
 mod tests {
-    extern crate dotenv;
     extern crate chrono;
 
-    use self::chrono::{Duration, NaiveDate, NaiveTime, UTC};
+    use self::chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, UTC};
     use self::chrono::naive::date;
-    use self::dotenv::dotenv;
 
-    use ::select;
-    use connection::{Connection, PgConnection};
     use expression::dsl::{sql, now};
     use prelude::*;
-    use types::{Date, Time, Timestamp};
-
-    fn connection() -> PgConnection {
-        dotenv().ok();
-
-        let connection_url = ::std::env::var("DATABASE_URL").ok()
-            .expect("DATABASE_URL must be set in order to run tests");
-        PgConnection::establish(&connection_url).unwrap()
-    }
+    use test_helpers::connection;
+    use types::{Date, Interval, Time, Timestamp, Timestamptz};
+    use types::data_types::LocalNaiveDateTime;
 
     #[test]
     fn unix_epoch_encodes_correctly() {
@@ -138,6 +261,29 @@ mod tests {
         assert_eq!(Ok(time), epoch_from_sql);
     }
 
+    #[test]
+    fn infinite_timestamps_are_an_error_rather_than_a_panic() {
+        let connection = connection();
+        let query = select(sql::<Timestamp>("'infinity'::timestamp"));
+        assert!(query.get_result::<NaiveDateTime>(&connection).is_err());
+        let query = select(sql::<Timestamptz>("'-infinity'::timestamptz"));
+        assert!(query.get_result::<DateTime<UTC>>(&connection).is_err());
+    }
+
+    #[test]
+    fn infinite_timestamps_can_be_loaded_as_maybe_infinite() {
+        use data_types::MaybeInfinite;
+
+        let connection = connection();
+        let query = select(sql::<Timestamp>("'infinity'::timestamp"));
+        let expected = MaybeInfinite::<NaiveDateTime>::Infinity;
+        assert_eq!(Ok(expected), query.get_result(&connection));
+
+        let time = NaiveDate::from_ymd(1970, 1, 1).and_hms(0, 0, 0);
+        let query = select(sql::<Timestamp>("'1970-01-01'::timestamp"));
+        assert_eq!(Ok(MaybeInfinite::Finite(time)), query.get_result(&connection));
+    }
+
     #[test]
     fn times_relative_to_now_encode_correctly() {
         let connection = connection();
@@ -150,6 +296,77 @@ mod tests {
         assert!(query.get_result::<bool>(&connection).unwrap());
     }
 
+    #[test]
+    fn utc_date_times_encode_correctly() {
+        let connection = connection();
+        let time = UTC.ymd(1970, 1, 1).and_hms(0, 0, 0);
+        let query = select(sql::<Timestamptz>("'1970-01-01 00:00:00+00'::timestamptz").eq(time));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+
+    #[test]
+    fn date_times_with_an_offset_are_converted_to_utc() {
+        let connection = connection();
+        let time = FixedOffset::east(2 * 3600).ymd(1970, 1, 1).and_hms(2, 0, 0);
+        let query = select(sql::<Timestamptz>("'1970-01-01 00:00:00+00'::timestamptz").eq(time));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+
+    #[test]
+    fn date_times_decode_as_utc_regardless_of_input_offset() {
+        let connection = connection();
+        let expected = UTC.ymd(1970, 1, 1).and_hms(0, 0, 0);
+        let query = select(sql::<Timestamptz>("'1970-01-01 02:00:00+02'::timestamptz"));
+        assert_eq!(Ok(expected), query.get_result::<DateTime<UTC>>(&connection));
+
+        let query = select(sql::<Timestamptz>("'1970-01-01 02:00:00+02'::timestamptz"));
+        let fixed = query.get_result::<DateTime<FixedOffset>>(&connection).unwrap();
+        assert_eq!(expected, fixed.with_timezone(&UTC));
+        assert_eq!(FixedOffset::east(0), *fixed.offset());
+
+        let query = select(sql::<Timestamptz>("'1970-01-01 02:00:00+02'::timestamptz"));
+        let local = query.get_result::<DateTime<Local>>(&connection).unwrap();
+        assert_eq!(expected, local.with_timezone(&UTC));
+    }
+
+    #[test]
+    fn date_times_with_any_offset_round_trip_to_the_same_instant() {
+        let connection = connection();
+        let fixed = FixedOffset::west(5 * 3600).ymd(2016, 3, 1).and_hms(19, 30, 0);
+        let query = select(fixed.into_sql::<Timestamptz>());
+        let loaded = query.get_result::<DateTime<FixedOffset>>(&connection).unwrap();
+        // `DateTime`s are equal if they're the same instant, whatever their offsets
+        assert_eq!(fixed, loaded);
+        assert_eq!(FixedOffset::east(0), *loaded.offset());
+        assert_eq!(fixed.with_timezone(&FixedOffset::east(0)).naive_local(), loaded.naive_local());
+
+        let local = fixed.with_timezone(&Local);
+        let query = select(local.into_sql::<Timestamptz>());
+        assert_eq!(Ok(local), query.get_result::<DateTime<Local>>(&connection));
+    }
+
+    #[test]
+    fn local_naive_date_times_are_treated_as_local_for_timestamptz() {
+        let connection = connection();
+        let local = Local.ymd(2016, 3, 1).and_hms(12, 0, 0);
+        let naive = LocalNaiveDateTime(local.naive_local());
+        let query = select(naive.into_sql::<Timestamptz>());
+        assert_eq!(Ok(local.with_timezone(&UTC)), query.get_result::<DateTime<UTC>>(&connection));
+        let query = select(naive.into_sql::<Timestamptz>());
+        assert_eq!(Ok(naive), query.get_result::<LocalNaiveDateTime>(&connection));
+    }
+
+    #[test]
+    fn naive_date_times_are_treated_as_utc_for_timestamptz() {
+        let connection = connection();
+        let time = NaiveDate::from_ymd(1970, 1, 1).and_hms(0, 0, 0);
+        let query = select(sql::<Timestamptz>("'1970-01-01 00:00:00+00'::timestamptz").eq(time));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+
+        let query = select(sql::<Timestamptz>("'1970-01-01 02:00:00+02'::timestamptz"));
+        assert_eq!(Ok(time), query.get_result(&connection));
+    }
+
     #[test]
     fn times_of_day_encode_correctly() {
         let connection = connection();
@@ -238,4 +455,22 @@ mod tests {
         let query = select(sql::<Date>("'72400-1-8'::date"));
         assert_eq!(Ok(distant_future), query.get_result::<NaiveDate>(&connection));
     }
+
+    #[test]
+    fn intervals_round_trip_through_postgres() {
+        let connection = connection();
+        let duration = Duration::hours(-30) + Duration::microseconds(5);
+        let query = select(sql::<Interval>("'-30 hours 0.000005 seconds'::interval"));
+        assert_eq!(Ok(duration), query.get_result::<Duration>(&connection));
+        let query = select(sql::<Interval>("'-30 hours 0.000005 seconds'::interval").eq(duration));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+
+    #[test]
+    fn days_and_months_are_converted_to_a_fixed_length() {
+        let connection = connection();
+        let query = select(sql::<Interval>("'1 month 2 days 3 seconds'::interval"));
+        let expected = Duration::days(32) + Duration::seconds(3);
+        assert_eq!(Ok(expected), query.get_result::<Duration>(&connection));
+    }
 }
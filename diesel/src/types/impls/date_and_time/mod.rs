@@ -1,13 +1,15 @@
 use std::error::Error;
 use std::io::Write;
 use std::ops::Add;
+use std::time::Duration;
 
 use backend::Pg;
 use expression::*;
 use expression::bound::Bound;
 use query_source::Queryable;
+use row::Row;
 use super::option::UnexpectedNullError;
-use types::{self, FromSql, ToSql, IsNull};
+use types::{self, FromSql, FromSqlRow, ToSql, IsNull};
 
 #[cfg(feature = "quickcheck")]
 mod quickcheck_impls;
@@ -15,6 +17,10 @@ mod quickcheck_impls;
 mod std_time;
 #[cfg(feature = "chrono")]
 mod chrono;
+#[cfg(feature = "chrono")]
+pub use self::chrono::LocalNaiveDateTime;
+#[cfg(feature = "time")]
+mod time;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 /// Timestamps are represented in Postgres as a 64 bit signed integer representing the number of
@@ -35,9 +41,14 @@ pub struct PgDate(pub i32);
 pub struct PgTime(pub i64);
 
 /// Intervals in Postgres are separated into 3 parts. A 64 bit integer representing time in
-/// microseconds, a 32 bit integer representing number of minutes, and a 32 bit integer
+/// microseconds, a 32 bit integer representing number of days, and a 32 bit integer
 /// representing number of months. This struct is a dumb wrapper type, meant only to indicate the
 /// meaning of these parts.
+///
+/// An interval can also be loaded as a `std::time::Duration`, or a `chrono::Duration` with the
+/// `chrono` feature. The length of a day or month depends on when the interval is applied, so this
+/// is lossy. Like `justify_interval`, each day is treated as 24 hours and each month as 30 days.
+/// Durations are always sent as microseconds, so sending one is not lossy.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PgInterval {
     pub microseconds: i64,
@@ -72,6 +83,7 @@ primitive_impls! {
     Interval -> (PgInterval, 1186, 1187),
     Time -> (PgTime, 1083, 1183),
     Timestamp -> (PgTimestamp, 1114, 1115),
+    Timestamptz -> (PgTimestamp, 1184, 1185),
 }
 
 impl ToSql<types::Timestamp, Pg> for PgTimestamp {
@@ -87,6 +99,18 @@ impl FromSql<types::Timestamp, Pg> for PgTimestamp {
     }
 }
 
+impl ToSql<types::Timestamptz, Pg> for PgTimestamp {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        ToSql::<types::Timestamp, Pg>::to_sql(self, out)
+    }
+}
+
+impl FromSql<types::Timestamptz, Pg> for PgTimestamp {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        FromSql::<types::Timestamp, Pg>::from_sql(bytes)
+    }
+}
+
 impl ToSql<types::Date, Pg> for PgDate {
     fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
         ToSql::<types::Integer, Pg>::to_sql(&self.0, out)
@@ -133,6 +157,182 @@ impl FromSql<types::Interval, Pg> for PgInterval {
     }
 }
 
+const MICROSECONDS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000;
+const DAYS_PER_MONTH: i64 = 30;
+
+/// The length of `interval` in microseconds, treating each day as 24 hours and each month as 30
+/// days. Returns an error if that doesn't fit in an `i64`.
+fn interval_to_microseconds(interval: PgInterval) -> Result<i64, Box<Error>> {
+    let days = (interval.months as i64) * DAYS_PER_MONTH + interval.days as i64;
+    days.checked_mul(MICROSECONDS_PER_DAY)
+        .and_then(|microseconds| microseconds.checked_add(interval.microseconds))
+        .ok_or_else(|| {
+            let error_message = format!("{:?} is too long to be represented as a duration", interval);
+            Box::<Error + Send + Sync>::from(error_message) as Box<Error>
+        })
+}
+
+expression_impls! {
+    Interval -> Duration,
+}
+
+queryable_impls! {
+    Interval -> Duration,
+}
+
+impl ToSql<types::Interval, Pg> for Duration {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let microseconds = (self.as_secs() as i64).checked_mul(1_000_000)
+            .and_then(|microseconds| microseconds.checked_add(self.subsec_nanos() as i64 / 1_000));
+        match microseconds {
+            Some(microseconds) => {
+                let interval = PgInterval::from_microseconds(microseconds);
+                ToSql::<types::Interval, Pg>::to_sql(&interval, out)
+            }
+            None => {
+                let error_message = format!("{:?} is too long to be sent as an interval", self);
+                Err(Box::<Error + Send + Sync>::from(error_message))
+            }
+        }
+    }
+}
+
+/// Negative intervals can't be represented as a `Duration`, and return an error.
+impl FromSql<types::Interval, Pg> for Duration {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let interval = try!(<PgInterval as FromSql<types::Interval, Pg>>::from_sql(bytes));
+        let microseconds = try!(interval_to_microseconds(interval));
+        if microseconds < 0 {
+            let error_message = format!("{:?} is negative, and can't be represented as a Duration", interval);
+            return Err(Box::<Error + Send + Sync>::from(error_message));
+        }
+        let seconds = (microseconds / 1_000_000) as u64;
+        let nanoseconds = (microseconds % 1_000_000) as u32 * 1_000;
+        Ok(Duration::new(seconds, nanoseconds))
+    }
+}
+
+/// A `Date`, `Timestamp` or `Timestamptz` which may be `'infinity'` or
+/// `'-infinity'`. Types such as `chrono::NaiveDateTime` can't represent these
+/// values, and return an error when loading them. Wrapping the type, such as
+/// `MaybeInfinite<NaiveDateTime>`, allows them to be loaded and sent. Finite
+/// values are loaded and sent as `T`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MaybeInfinite<T> {
+    NegativeInfinity,
+    Finite(T),
+    Infinity,
+}
+
+// PostgreSQL stores `'infinity'` and `'-infinity'` as the largest and smallest
+// values of the underlying integer.
+macro_rules! maybe_infinite_impls {
+    ($($Source:ident -> ($Raw:ident, $Int:ty)),+,) => {$(
+        impl<T> FromSql<types::$Source, Pg> for MaybeInfinite<T> where
+            T: FromSql<types::$Source, Pg>,
+        {
+            fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+                let $Raw(value) = try!(FromSql::<types::$Source, Pg>::from_sql(bytes));
+                if value == <$Int>::max_value() {
+                    Ok(MaybeInfinite::Infinity)
+                } else if value == <$Int>::min_value() {
+                    Ok(MaybeInfinite::NegativeInfinity)
+                } else {
+                    T::from_sql(bytes).map(MaybeInfinite::Finite)
+                }
+            }
+        }
+
+        impl<T> ToSql<types::$Source, Pg> for MaybeInfinite<T> where
+            T: ToSql<types::$Source, Pg>,
+        {
+            fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+                match *self {
+                    MaybeInfinite::Infinity => {
+                        ToSql::<types::$Source, Pg>::to_sql(&$Raw(<$Int>::max_value()), out)
+                    }
+                    MaybeInfinite::NegativeInfinity => {
+                        ToSql::<types::$Source, Pg>::to_sql(&$Raw(<$Int>::min_value()), out)
+                    }
+                    MaybeInfinite::Finite(ref value) => value.to_sql(out),
+                }
+            }
+        }
+
+        impl<T> ToSql<types::Nullable<types::$Source>, Pg> for MaybeInfinite<T> where
+            MaybeInfinite<T>: ToSql<types::$Source, Pg>,
+        {
+            fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+                ToSql::<types::$Source, Pg>::to_sql(self, out)
+            }
+        }
+
+        impl<T> FromSqlRow<types::$Source, Pg> for MaybeInfinite<T> where
+            MaybeInfinite<T>: FromSql<types::$Source, Pg>,
+        {
+            fn build_from_row<R: Row>(row: &mut R) -> Result<Self, Box<Error>> {
+                FromSql::<types::$Source, Pg>::from_sql(row.take())
+            }
+        }
+
+        impl<T> FromSqlRow<types::Nullable<types::$Source>, Pg> for Option<MaybeInfinite<T>> where
+            Option<MaybeInfinite<T>>: FromSql<types::Nullable<types::$Source>, Pg>,
+        {
+            fn build_from_row<R: Row>(row: &mut R) -> Result<Self, Box<Error>> {
+                FromSql::<types::Nullable<types::$Source>, Pg>::from_sql(row.take())
+            }
+        }
+
+        impl<T> Queryable<types::$Source, Pg> for MaybeInfinite<T> where
+            MaybeInfinite<T>: FromSqlRow<types::$Source, Pg>,
+        {
+            type Row = Self;
+
+            fn build(row: Self::Row) -> Self {
+                row
+            }
+        }
+
+        impl<T> AsExpression<types::$Source> for MaybeInfinite<T> {
+            type Expression = Bound<types::$Source, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl<'a, T> AsExpression<types::$Source> for &'a MaybeInfinite<T> {
+            type Expression = Bound<types::$Source, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl<T> AsExpression<types::Nullable<types::$Source>> for MaybeInfinite<T> {
+            type Expression = Bound<types::Nullable<types::$Source>, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl<'a, T> AsExpression<types::Nullable<types::$Source>> for &'a MaybeInfinite<T> {
+            type Expression = Bound<types::Nullable<types::$Source>, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+    )+}
+}
+
+maybe_infinite_impls! {
+    Date -> (PgDate, i32),
+    Timestamp -> (PgTimestamp, i64),
+    Timestamptz -> (PgTimestamp, i64),
+}
+
 impl Add<PgInterval> for PgInterval {
     type Output = PgInterval;
 
@@ -144,3 +344,64 @@ impl Add<PgInterval> for PgInterval {
         }
     }
 }
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use std::time::Duration;
+
+    use backend::Pg;
+    use expression::dsl::sql;
+    use prelude::*;
+    use super::{MaybeInfinite, PgDate, PgInterval, PgTimestamp};
+    use test_helpers::connection;
+    use types::{Date, FromSql, Interval, Timestamp, ToSql};
+
+    fn load_duration(interval: PgInterval) -> Result<Duration, String> {
+        let mut bytes = Vec::new();
+        ToSql::<Interval, Pg>::to_sql(&interval, &mut bytes).unwrap();
+        <Duration as FromSql<Interval, Pg>>::from_sql(Some(&bytes)).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn days_and_months_are_converted_to_a_fixed_length() {
+        let interval = PgInterval::new(1_500_000, 2, 1);
+        assert_eq!(Ok(Duration::new(32 * 24 * 60 * 60 + 1, 500_000_000)), load_duration(interval));
+    }
+
+    #[test]
+    fn negative_intervals_are_an_error() {
+        assert!(load_duration(PgInterval::from_microseconds(-1)).is_err());
+        assert!(load_duration(PgInterval::new(1, -1, 0)).is_err());
+    }
+
+    #[test]
+    fn intervals_too_long_for_a_duration_are_an_error() {
+        assert!(load_duration(PgInterval::new(i64::max_value(), 1, 0)).is_err());
+    }
+
+    #[test]
+    fn durations_round_trip_through_postgres() {
+        let connection = connection();
+        let duration = Duration::new(90, 5_000);
+        let query = select(sql::<Interval>("'1 minute 30.000005 seconds'::interval"));
+        assert_eq!(Ok(duration), query.get_result::<Duration>(&connection));
+        let query = select(sql::<Interval>("'1 minute 30.000005 seconds'::interval").eq(duration));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+
+    #[test]
+    fn infinite_timestamps_can_be_loaded_and_sent() {
+        let connection = connection();
+        let query = select(sql::<Timestamp>("'infinity'::timestamp"));
+        let infinity = MaybeInfinite::<PgTimestamp>::Infinity;
+        assert_eq!(Ok(infinity), query.get_result(&connection));
+        let query = select(sql::<Timestamp>("'-infinity'::timestamp"));
+        let negative_infinity = MaybeInfinite::<PgTimestamp>::NegativeInfinity;
+        assert_eq!(Ok(negative_infinity), query.get_result(&connection));
+
+        let query = select(sql::<Date>("'infinity'::date").eq(MaybeInfinite::<PgDate>::Infinity));
+        assert_eq!(Ok(true), query.get_result(&connection));
+        let query = select(sql::<Date>("'2000-01-02'::date"));
+        assert_eq!(Ok(MaybeInfinite::Finite(PgDate(1))), query.get_result(&connection));
+    }
+}
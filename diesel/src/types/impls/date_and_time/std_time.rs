@@ -67,27 +67,15 @@ fn duration_to_usecs(duration: Duration) -> u64 {
     seconds + subseconds as u64
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "postgres"))]
 mod tests {
-    extern crate dotenv;
-
-    use self::dotenv::dotenv;
     use std::time::{SystemTime, Duration, UNIX_EPOCH};
 
-    use ::select;
-    use connection::{Connection, PgConnection};
     use expression::dsl::{sql, now};
     use prelude::*;
+    use test_helpers::connection;
     use types::Timestamp;
 
-    fn connection() -> PgConnection {
-        dotenv().ok();
-
-        let connection_url = ::std::env::var("DATABASE_URL").ok()
-            .expect("DATABASE_URL must be set in order to run tests");
-        PgConnection::establish(&connection_url).unwrap()
-    }
-
     #[test]
     fn unix_epoch_encodes_correctly() {
         let connection = connection();
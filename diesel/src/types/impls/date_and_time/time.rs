@@ -0,0 +1,164 @@
+//! This module makes it possible to map values from the `time` crate to postgres `Date`,
+//! `Time`, `Timestamp` and `Timestamptz` fields. It is enabled with the `time` feature.
+//!
+//! `Timestamptz` values are sent to and loaded from PostgreSQL in UTC, so an
+//! `OffsetDateTime` will always be loaded with an offset of zero.
+extern crate time;
+
+use std::error::Error;
+use std::io::Write;
+use self::time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::{PgDate, PgTime, PgTimestamp};
+use types::{self, FromSql, IsNull, Timestamptz, ToSql};
+
+expression_impls! {
+    Date -> Date,
+    Time -> Time,
+    Timestamp -> PrimitiveDateTime,
+    Timestamptz -> OffsetDateTime,
+}
+
+queryable_impls! {
+    Date -> Date,
+    Time -> Time,
+    Timestamp -> PrimitiveDateTime,
+    Timestamptz -> OffsetDateTime,
+}
+
+// Postgres timestamps start from January 1st 2000.
+fn pg_epoch_date() -> Date {
+    Date::from_calendar_date(2000, Month::January, 1).unwrap()
+}
+
+fn pg_epoch() -> PrimitiveDateTime {
+    PrimitiveDateTime::new(pg_epoch_date(), Time::MIDNIGHT)
+}
+
+fn out_of_range_error(value: &str) -> Box<Error> {
+    let error_message = format!("{} is outside of the range that the time crate can represent", value);
+    Box::<Error + Send + Sync>::from(error_message)
+}
+
+impl FromSql<types::Timestamp, Pg> for PrimitiveDateTime {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let PgTimestamp(offset) = try!(FromSql::<types::Timestamp, Pg>::from_sql(bytes));
+        pg_epoch().checked_add(Duration::microseconds(offset))
+            .ok_or_else(|| out_of_range_error(&format!("{:?}", PgTimestamp(offset))))
+    }
+}
+
+impl ToSql<types::Timestamp, Pg> for PrimitiveDateTime {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let micros = (*self - pg_epoch()).whole_microseconds();
+        if micros > i64::max_value() as i128 || micros < i64::min_value() as i128 {
+            let error_message = format!("{:?} as microseconds is too large to fit in an i64", self);
+            return Err(Box::<Error + Send + Sync>::from(error_message));
+        }
+        ToSql::<types::Timestamp, Pg>::to_sql(&PgTimestamp(micros as i64), out)
+    }
+}
+
+impl FromSql<Timestamptz, Pg> for OffsetDateTime {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let utc = try!(<PrimitiveDateTime as FromSql<types::Timestamp, Pg>>::from_sql(bytes));
+        Ok(utc.assume_utc())
+    }
+}
+
+impl ToSql<Timestamptz, Pg> for OffsetDateTime {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let utc = self.to_offset(UtcOffset::UTC);
+        let naive_utc = PrimitiveDateTime::new(utc.date(), utc.time());
+        ToSql::<types::Timestamp, Pg>::to_sql(&naive_utc, out)
+    }
+}
+
+impl ToSql<types::Time, Pg> for Time {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let micros = (*self - Time::MIDNIGHT).whole_microseconds() as i64;
+        ToSql::<types::Time, Pg>::to_sql(&PgTime(micros), out)
+    }
+}
+
+impl FromSql<types::Time, Pg> for Time {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let PgTime(offset) = try!(FromSql::<types::Time, Pg>::from_sql(bytes));
+        Ok(Time::MIDNIGHT + Duration::microseconds(offset))
+    }
+}
+
+impl ToSql<types::Date, Pg> for Date {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let days_since_epoch = (*self - pg_epoch_date()).whole_days();
+        ToSql::<types::Date, Pg>::to_sql(&PgDate(days_since_epoch as i32), out)
+    }
+}
+
+impl FromSql<types::Date, Pg> for Date {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let PgDate(offset) = try!(FromSql::<types::Date, Pg>::from_sql(bytes));
+        pg_epoch_date().checked_add(Duration::days(offset as i64))
+            .ok_or_else(|| out_of_range_error(&format!("{:?}", PgDate(offset))))
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    extern crate time;
+
+    use self::time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+    use expression::dsl::sql;
+    use prelude::*;
+    use test_helpers::connection;
+    use types;
+
+    fn unix_epoch() -> PrimitiveDateTime {
+        PrimitiveDateTime::new(Date::from_calendar_date(1970, Month::January, 1).unwrap(), Time::MIDNIGHT)
+    }
+
+    #[test]
+    fn unix_epoch_round_trips_as_a_timestamp() {
+        let connection = connection();
+        let query = select(sql::<types::Timestamp>("'1970-01-01'::timestamp").eq(unix_epoch()));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+
+        let query = select(sql::<types::Timestamp>("'1970-01-01'::timestamp"));
+        assert_eq!(Ok(unix_epoch()), query.get_result(&connection));
+    }
+
+    #[test]
+    fn offset_date_times_are_converted_to_utc() {
+        let connection = connection();
+        let two_hours_ahead = UtcOffset::from_hms(2, 0, 0).unwrap();
+        let time = (unix_epoch() + time::Duration::hours(2)).assume_offset(two_hours_ahead);
+        let query = select(sql::<types::Timestamptz>("'1970-01-01 00:00:00+00'::timestamptz").eq(time));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+
+        let query = select(sql::<types::Timestamptz>("'1970-01-01 02:00:00+02'::timestamptz"));
+        let loaded = query.get_result::<OffsetDateTime>(&connection).unwrap();
+        assert_eq!(unix_epoch().assume_utc(), loaded);
+        assert_eq!(UtcOffset::UTC, loaded.offset());
+    }
+
+    #[test]
+    fn times_and_dates_round_trip() {
+        let connection = connection();
+        let time = Time::from_hms_micro(23, 37, 4, 2200).unwrap();
+        let query = select(sql::<types::Time>("'23:37:04.002200'::time"));
+        assert_eq!(Ok(time), query.get_result(&connection));
+        let query = select(sql::<types::Time>("'23:37:04.002200'::time").eq(time));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+
+        let date = Date::from_calendar_date(-398, Month::April, 11).unwrap();
+        let query = select(sql::<types::Date>("'399-4-11 BC'::date"));
+        assert_eq!(Ok(date), query.get_result(&connection));
+        let query = select(sql::<types::Date>("'399-4-11 BC'::date").eq(date));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+}
@@ -1,15 +1,24 @@
+//! `f32` and `f64` are sent and received as their IEEE 754 bit patterns, which
+//! is also how PostgreSQL represents `real` and `double precision` in its binary
+//! protocol. This means that `NaN`, positive and negative infinity, and negative
+//! zero all round trip without loss. PostgreSQL considers all `NaN` values to be
+//! equal to each other, and greater than every other value, which differs from
+//! the behavior of `NaN` in Rust.
 extern crate byteorder;
 
 use std::error::Error;
 use std::io::Write;
 
 use backend::Backend;
+use query_source::Queryable;
 use self::byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
 use super::option::UnexpectedNullError;
 use types::{self, FromSql, ToSql, IsNull};
 
 #[cfg(feature = "quickcheck")]
 mod quickcheck_impls;
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal;
 
 impl<DB: Backend> FromSql<types::Float, DB> for f32 {
     fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
@@ -136,3 +145,127 @@ impl ToSql<types::Numeric, Pg> for PgNumeric {
         Ok(IsNull::No)
     }
 }
+
+/// An `f64` which is loaded from a `Numeric` column. This is faster to work
+/// with than `PgNumeric`, but a numeric can hold far more digits than an `f64`,
+/// so the value is rounded to the nearest `f64`. Numerics which are too large
+/// are loaded as infinity. Use this only when exactness doesn't matter.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct LossyNumeric(pub f64);
+
+impl LossyNumeric {
+    pub fn into_inner(self) -> f64 {
+        self.0
+    }
+}
+
+impl ::std::ops::Deref for LossyNumeric {
+    type Target = f64;
+
+    fn deref(&self) -> &f64 {
+        &self.0
+    }
+}
+
+impl FromSql<types::Numeric, Pg> for LossyNumeric {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let (sign, weight, digits) = match try!(<PgNumeric as FromSql<types::Numeric, Pg>>::from_sql(bytes)) {
+            PgNumeric::Positive { weight, digits, .. } => ("", weight, digits),
+            PgNumeric::Negative { weight, digits, .. } => ("-", weight, digits),
+            PgNumeric::NaN => return Ok(LossyNumeric(::std::f64::NAN)),
+        };
+        if digits.is_empty() {
+            return Ok(LossyNumeric(0.0));
+        }
+        // Each digit is a group of 4 decimal digits, and the first is `weight`
+        // groups before the decimal point. Formatting the digits and parsing the
+        // result rounds correctly to the nearest `f64`.
+        let mut decimal = String::with_capacity(digits.len() * 4 + 8);
+        decimal.push_str(sign);
+        for digit in &digits {
+            decimal.push_str(&format!("{:04}", digit));
+        }
+        let exponent = 4 * (weight as i32 - digits.len() as i32 + 1);
+        decimal.push_str(&format!("e{}", exponent));
+        Ok(LossyNumeric(try!(decimal.parse())))
+    }
+}
+
+queryable_impls! {
+    Numeric -> LossyNumeric,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f32, f64};
+
+    use backend::Pg;
+    use data_types::PgNumeric;
+    use super::LossyNumeric;
+    use types::{self, FromSql, ToSql};
+
+    fn load_lossy(numeric: PgNumeric) -> f64 {
+        let mut bytes = Vec::new();
+        ToSql::<types::Numeric, Pg>::to_sql(&numeric, &mut bytes).unwrap();
+        <LossyNumeric as FromSql<types::Numeric, Pg>>::from_sql(Some(&bytes)).unwrap().into_inner()
+    }
+
+    #[test]
+    fn numerics_can_be_loaded_as_lossy_floats() {
+        let numeric = PgNumeric::Positive { weight: 0, scale: 4, digits: vec![12, 3400] };
+        assert_eq!(12.34, load_lossy(numeric));
+        let numeric = PgNumeric::Negative { weight: -1, scale: 5, digits: vec![1] };
+        assert_eq!(-0.0001, load_lossy(numeric));
+        let numeric = PgNumeric::Positive { weight: 2, scale: 0, digits: vec![1] };
+        assert_eq!(100_000_000.0, load_lossy(numeric));
+        let numeric = PgNumeric::Positive { weight: 0, scale: 0, digits: vec![] };
+        assert_eq!(0.0, load_lossy(numeric));
+        assert!(load_lossy(PgNumeric::NaN).is_nan());
+        let numeric = PgNumeric::Positive { weight: 100, scale: 0, digits: vec![1] };
+        assert_eq!(f64::INFINITY, load_lossy(numeric));
+    }
+
+    fn f32_bytes(value: f32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        ToSql::<types::Float, Pg>::to_sql(&value, &mut bytes).unwrap();
+        bytes
+    }
+
+    fn f64_bytes(value: f64) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        ToSql::<types::Double, Pg>::to_sql(&value, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn special_f32_values_are_sent_as_their_ieee_754_bits() {
+        assert_eq!(vec![0x7f, 0x80, 0, 0], f32_bytes(f32::INFINITY));
+        assert_eq!(vec![0xff, 0x80, 0, 0], f32_bytes(f32::NEG_INFINITY));
+        assert_eq!(vec![0x80, 0, 0, 0], f32_bytes(-0.0));
+        let nan = f32_bytes(f32::NAN);
+        assert_eq!(0x7f, nan[0] & 0x7f);
+        assert!(nan[1] & 0x80 != 0);
+    }
+
+    #[test]
+    fn special_f64_values_are_sent_as_their_ieee_754_bits() {
+        assert_eq!(vec![0x7f, 0xf0, 0, 0, 0, 0, 0, 0], f64_bytes(f64::INFINITY));
+        assert_eq!(vec![0xff, 0xf0, 0, 0, 0, 0, 0, 0], f64_bytes(f64::NEG_INFINITY));
+        assert_eq!(vec![0x80, 0, 0, 0, 0, 0, 0, 0], f64_bytes(-0.0));
+    }
+
+    #[test]
+    fn special_float_values_are_decoded_losslessly() {
+        let infinity = <f32 as FromSql<types::Float, Pg>>::from_sql(Some(&[0x7f, 0x80, 0, 0])).unwrap();
+        assert_eq!(f32::INFINITY, infinity);
+        let nan = <f32 as FromSql<types::Float, Pg>>::from_sql(Some(&[0x7f, 0xc0, 0, 0])).unwrap();
+        assert!(nan.is_nan());
+        let negative_zero = <f64 as FromSql<types::Double, Pg>>::from_sql(
+            Some(&[0x80, 0, 0, 0, 0, 0, 0, 0])).unwrap();
+        assert_eq!(0.0, negative_zero);
+        assert!(negative_zero.is_sign_negative());
+        let nan = <f64 as FromSql<types::Double, Pg>>::from_sql(
+            Some(&[0x7f, 0xf8, 0, 0, 0, 0, 0, 0])).unwrap();
+        assert!(nan.is_nan());
+    }
+}
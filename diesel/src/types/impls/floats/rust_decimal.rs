@@ -0,0 +1,168 @@
+//! This module makes it possible to map `rust_decimal::Decimal` values to postgres `Numeric`
+//! fields. It is enabled with the `rust_decimal` feature.
+//!
+//! This is one of several types which `Numeric` can be mapped to, alongside `PgNumeric`, so it
+//! can be enabled together with any other mapping. The type of the Rust value decides which
+//! mapping is used. `Decimal` is a fixed size, 96 bit decimal, so loading a value which doesn't
+//! fit, or `NaN`, returns an error.
+extern crate rust_decimal;
+
+use std::error::Error;
+use std::io::Write;
+use self::rust_decimal::Decimal;
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::PgNumeric;
+use types::{self, FromSql, IsNull, ToSql};
+
+expression_impls! {
+    Numeric -> Decimal,
+}
+
+queryable_impls! {
+    Numeric -> Decimal,
+}
+
+const NBASE: u128 = 10_000;
+
+fn too_large(numeric: &PgNumeric) -> Box<Error> {
+    let error_message = format!("{:?} is too large to be represented as a Decimal", numeric);
+    Box::<Error + Send + Sync>::from(error_message)
+}
+
+fn pg_numeric_to_decimal(numeric: &PgNumeric) -> Result<Decimal, Box<Error>> {
+    let (negative, weight, scale, digits) = match *numeric {
+        PgNumeric::Positive { weight, scale, ref digits } => (false, weight, scale, digits),
+        PgNumeric::Negative { weight, scale, ref digits } => (true, weight, scale, digits),
+        PgNumeric::NaN => {
+            return Err(Box::<Error + Send + Sync>::from("NaN cannot be represented as a Decimal"));
+        }
+    };
+
+    let mut mantissa: i128 = 0;
+    for digit in digits {
+        mantissa = try!(mantissa.checked_mul(NBASE as i128)
+            .and_then(|m| m.checked_add(*digit as i128))
+            .ok_or_else(|| too_large(numeric)));
+    }
+    // Each digit is 4 decimal places. The last digit is `weight - (digits.len() - 1)`
+    // groups away from the decimal point.
+    let mut exponent = 4 * (weight as i32 - digits.len() as i32 + 1);
+    while exponent > 0 {
+        mantissa = try!(mantissa.checked_mul(10).ok_or_else(|| too_large(numeric)));
+        exponent -= 1;
+    }
+    if negative {
+        mantissa = -mantissa;
+    }
+
+    let mut result = try!(Decimal::try_from_i128_with_scale(mantissa, (-exponent) as u32)
+        .map_err(|_| too_large(numeric)));
+    result.rescale(scale as u32);
+    Ok(result)
+}
+
+fn decimal_to_pg_numeric(decimal: &Decimal) -> PgNumeric {
+    let scale = decimal.scale();
+    let padding = (4 - scale % 4) % 4;
+    let mut mantissa = decimal.mantissa().abs() as u128 * 10u128.pow(padding);
+    let fractional_groups = ((scale + padding) / 4) as i16;
+
+    let mut digits = Vec::new();
+    while mantissa > 0 {
+        digits.push((mantissa % NBASE) as i16);
+        mantissa /= NBASE;
+    }
+    let weight = digits.len() as i16 - fractional_groups - 1;
+    digits.reverse();
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    let weight = if digits.is_empty() { 0 } else { weight };
+
+    if decimal.is_sign_negative() && !digits.is_empty() {
+        PgNumeric::Negative { weight: weight, scale: scale as u16, digits: digits }
+    } else {
+        PgNumeric::Positive { weight: weight, scale: scale as u16, digits: digits }
+    }
+}
+
+impl FromSql<types::Numeric, Pg> for Decimal {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let numeric = try!(<PgNumeric as FromSql<types::Numeric, Pg>>::from_sql(bytes));
+        pg_numeric_to_decimal(&numeric)
+    }
+}
+
+impl ToSql<types::Numeric, Pg> for Decimal {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        ToSql::<types::Numeric, Pg>::to_sql(&decimal_to_pg_numeric(self), out)
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    extern crate rust_decimal;
+
+    use self::rust_decimal::Decimal;
+    use super::{decimal_to_pg_numeric, pg_numeric_to_decimal};
+    use expression::dsl::sql;
+    use prelude::*;
+    use test_helpers::connection;
+    use types;
+    use types::impls::floats::PgNumeric;
+
+    fn decimal(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn decimals_convert_to_base_10000_digits() {
+        let expected = PgNumeric::Positive { weight: 0, scale: 2, digits: vec![1, 2300] };
+        assert_eq!(expected, decimal_to_pg_numeric(&decimal("1.23")));
+
+        let expected = PgNumeric::Negative { weight: 1, scale: 0, digits: vec![1, 2345] };
+        assert_eq!(expected, decimal_to_pg_numeric(&decimal("-12345")));
+
+        let expected = PgNumeric::Positive { weight: -1, scale: 5, digits: vec![1, 2000] };
+        assert_eq!(expected, decimal_to_pg_numeric(&decimal("0.00012")));
+
+        let expected = PgNumeric::Positive { weight: 0, scale: 1, digits: vec![] };
+        assert_eq!(expected, decimal_to_pg_numeric(&decimal("0.0")));
+    }
+
+    #[test]
+    fn decimals_round_trip_through_pg_numeric() {
+        for value in &["0", "1.23", "-12345", "0.00012", "10000", "123456789.000001", "-0.5"] {
+            let value = decimal(value);
+            let round_tripped = pg_numeric_to_decimal(&decimal_to_pg_numeric(&value)).unwrap();
+            assert_eq!(value, round_tripped);
+            assert_eq!(value.scale(), round_tripped.scale());
+        }
+    }
+
+    #[test]
+    fn nan_and_overflow_are_errors() {
+        assert!(pg_numeric_to_decimal(&PgNumeric::NaN).is_err());
+
+        let too_large = PgNumeric::Positive { weight: 10, scale: 0, digits: vec![1] };
+        assert!(pg_numeric_to_decimal(&too_large).is_err());
+    }
+
+    #[test]
+    fn decimals_and_pg_numeric_can_load_the_same_column() {
+        let connection = connection();
+        let query = select(sql::<types::Numeric>("'-12345.0012'::numeric"));
+        assert_eq!(Ok(decimal("-12345.0012")), query.get_result::<Decimal>(&connection));
+
+        let query = select(sql::<types::Numeric>("'-12345.0012'::numeric"));
+        let expected = PgNumeric::Negative { weight: 1, scale: 4, digits: vec![1, 2345, 12] };
+        assert_eq!(Ok(expected), query.get_result::<PgNumeric>(&connection));
+
+        let query = select(sql::<types::Numeric>("'-12345.0012'::numeric").eq(decimal("-12345.0012")));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+}
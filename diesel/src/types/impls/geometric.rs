@@ -0,0 +1,261 @@
+//! PostgreSQL sends geometric values as their coordinates, each of which is a
+//! 64 bit float. A `path` also starts with a byte which is 1 if the path is
+//! closed, and both a `path` and a `polygon` include the number of points
+//! before the points themselves.
+extern crate byteorder;
+
+use self::byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+use std::error::Error;
+use std::io::Write;
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, IsNull, ToSql};
+
+/// Marker trait for the geometric types, which can be used with the
+/// `distance`, `contains` and `is_contained_by` methods.
+pub trait Geometric {}
+
+impl Geometric for types::Point {}
+impl Geometric for types::Rectangle {}
+impl Geometric for types::Circle {}
+impl Geometric for types::Path {}
+impl Geometric for types::Polygon {}
+
+/// A point on a plane. This is what `point` values are loaded as.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PgPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl PgPoint {
+    pub fn new(x: f64, y: f64) -> Self {
+        PgPoint {
+            x: x,
+            y: y,
+        }
+    }
+}
+
+/// A rectangle, given by two of its opposite corners. This is what `box` values
+/// are loaded as. PostgreSQL always sends the upper right corner as `high`, and
+/// the lower left corner as `low`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PgBox {
+    pub high: PgPoint,
+    pub low: PgPoint,
+}
+
+/// A circle. This is what `circle` values are loaded as.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PgCircle {
+    pub center: PgPoint,
+    pub radius: f64,
+}
+
+/// A series of connected points. A closed path also connects its last point to
+/// its first. This is what `path` values are loaded as.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PgPath {
+    pub points: Vec<PgPoint>,
+    pub closed: bool,
+}
+
+/// A closed series of connected points. This is what `polygon` values are
+/// loaded as.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PgPolygon {
+    pub points: Vec<PgPoint>,
+}
+
+primitive_impls! {
+    Point -> (PgPoint, 600, 1017),
+    Rectangle -> (PgBox, 603, 1020),
+    Circle -> (PgCircle, 718, 719),
+    Path -> (PgPath, 602, 1019),
+    Polygon -> (PgPolygon, 604, 1027),
+}
+
+fn read_point(bytes: &mut &[u8]) -> Result<PgPoint, Box<Error>> {
+    let x = try!(bytes.read_f64::<BigEndian>());
+    let y = try!(bytes.read_f64::<BigEndian>());
+    Ok(PgPoint::new(x, y))
+}
+
+fn write_point<W: Write>(point: &PgPoint, out: &mut W) -> Result<(), Box<Error>> {
+    try!(out.write_f64::<BigEndian>(point.x));
+    try!(out.write_f64::<BigEndian>(point.y));
+    Ok(())
+}
+
+fn read_points(bytes: &mut &[u8]) -> Result<Vec<PgPoint>, Box<Error>> {
+    let num_points = try!(bytes.read_i32::<BigEndian>());
+    if num_points < 0 || num_points as usize * 16 != bytes.len() {
+        let error_message = format!("Invalid number of points: {}", num_points);
+        return Err(Box::<Error + Send + Sync>::from(error_message));
+    }
+    (0..num_points).map(|_| read_point(bytes)).collect()
+}
+
+fn write_points<W: Write>(points: &[PgPoint], out: &mut W) -> Result<IsNull, Box<Error>> {
+    try!(out.write_i32::<BigEndian>(points.len() as i32));
+    for point in points {
+        try!(write_point(point, out));
+    }
+    Ok(IsNull::No)
+}
+
+impl FromSql<types::Point, Pg> for PgPoint {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        read_point(&mut bytes)
+    }
+}
+
+impl ToSql<types::Point, Pg> for PgPoint {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        try!(write_point(self, out));
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<types::Rectangle, Pg> for PgBox {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        Ok(PgBox {
+            high: try!(read_point(&mut bytes)),
+            low: try!(read_point(&mut bytes)),
+        })
+    }
+}
+
+impl ToSql<types::Rectangle, Pg> for PgBox {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        try!(write_point(&self.high, out));
+        try!(write_point(&self.low, out));
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<types::Circle, Pg> for PgCircle {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        Ok(PgCircle {
+            center: try!(read_point(&mut bytes)),
+            radius: try!(bytes.read_f64::<BigEndian>()),
+        })
+    }
+}
+
+impl ToSql<types::Circle, Pg> for PgCircle {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        try!(write_point(&self.center, out));
+        try!(out.write_f64::<BigEndian>(self.radius));
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<types::Path, Pg> for PgPath {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        let closed = try!(bytes.read_u8()) != 0;
+        Ok(PgPath {
+            points: try!(read_points(&mut bytes)),
+            closed: closed,
+        })
+    }
+}
+
+impl ToSql<types::Path, Pg> for PgPath {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        try!(out.write_u8(self.closed as u8));
+        write_points(&self.points, out)
+    }
+}
+
+impl FromSql<types::Polygon, Pg> for PgPolygon {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        Ok(PgPolygon {
+            points: try!(read_points(&mut bytes)),
+        })
+    }
+}
+
+impl ToSql<types::Polygon, Pg> for PgPolygon {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        write_points(&self.points, out)
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use backend::Pg;
+    use expression::dsl::sql;
+    use prelude::*;
+    use super::{PgBox, PgCircle, PgPath, PgPoint, PgPolygon};
+    use test_helpers::connection;
+    use types::{Circle, FromSql, Path, Point, Polygon, Rectangle, ToSql};
+
+    fn triangle() -> Vec<PgPoint> {
+        vec![PgPoint::new(0.0, 0.0), PgPoint::new(4.0, 0.0), PgPoint::new(0.0, 3.0)]
+    }
+
+    #[test]
+    fn paths_start_with_whether_they_are_closed() {
+        let path = PgPath { points: vec![PgPoint::new(1.0, 2.0)], closed: true };
+        let mut bytes = Vec::new();
+        ToSql::<Path, Pg>::to_sql(&path, &mut bytes).unwrap();
+        assert_eq!(vec![1, 0, 0, 0, 1, 63, 240, 0, 0, 0, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0], bytes);
+        assert_eq!(path, <PgPath as FromSql<Path, Pg>>::from_sql(Some(&bytes)).unwrap());
+    }
+
+    #[test]
+    fn polygons_with_the_wrong_number_of_points_are_an_error() {
+        assert!(<PgPolygon as FromSql<Polygon, Pg>>::from_sql(Some(&[0, 0, 0, 1, 0])).is_err());
+    }
+
+    #[test]
+    fn geometric_values_round_trip_through_postgres() {
+        let connection = connection();
+        let point = PgPoint::new(1.5, -2.0);
+        assert_eq!(Ok(point), select(sql::<Point>("point(1.5, -2)")).get_result(&connection));
+
+        let rectangle = PgBox { high: PgPoint::new(2.0, 3.0), low: PgPoint::new(0.0, 1.0) };
+        let query = select(sql::<Rectangle>("box(point(0, 3), point(2, 1))"));
+        assert_eq!(Ok(rectangle), query.get_result(&connection));
+        let query = select(sql::<Rectangle>("box(point(0, 3), point(2, 1))").eq(rectangle));
+        assert_eq!(Ok(true), query.get_result(&connection));
+
+        let circle = PgCircle { center: PgPoint::new(1.0, 1.0), radius: 2.0 };
+        assert_eq!(Ok(circle), select(sql::<Circle>("circle(point(1, 1), 2)")).get_result(&connection));
+
+        let path = PgPath { points: triangle(), closed: false };
+        assert_eq!(Ok(path.clone()), select(sql::<Path>("'[(0,0),(4,0),(0,3)]'::path")).get_result(&connection));
+        let query = select(sql::<Path>("'[(0,0),(4,0),(0,3)]'::path").is_not_distinct_from(path));
+        assert_eq!(Ok(true), query.get_result(&connection));
+
+        let polygon = PgPolygon { points: triangle() };
+        let query = select(sql::<Polygon>("'((0,0),(4,0),(0,3))'::polygon"));
+        assert_eq!(Ok(polygon), query.get_result(&connection));
+    }
+
+    #[test]
+    fn distance_and_containment() {
+        let connection = connection();
+        let origin = || sql::<Point>("point(0, 0)");
+        assert_eq!(Ok(5.0), select(origin().distance(PgPoint::new(3.0, 4.0))).get_result(&connection));
+
+        let circle = PgCircle { center: PgPoint::new(0.0, 0.0), radius: 1.0 };
+        assert_eq!(Ok(true), select(origin().is_contained_by(circle)).get_result(&connection));
+        let polygon = PgPolygon { points: triangle() };
+        assert_eq!(Ok(true), select(sql::<Polygon>("'((0,0),(4,0),(0,3))'::polygon")
+            .contains(PgPoint::new(1.0, 1.0))).get_result(&connection));
+        let query = select(sql::<Point>("point(5, 5)").is_contained_by(polygon));
+        assert_eq!(Ok(false), query.get_result(&connection));
+    }
+}
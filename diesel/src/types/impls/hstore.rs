@@ -0,0 +1,141 @@
+//! `hstore` is provided by an extension, so its OID is not known until runtime.
+//! Bind parameters are sent with an OID of 0, which lets PostgreSQL infer the
+//! type from the query.
+//!
+//! PostgreSQL sends an `hstore` as the number of pairs, followed by each key and
+//! value. Each is sent as its length in bytes, and then its bytes. A `NULL`
+//! value has a length of -1.
+extern crate byteorder;
+
+use self::byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{Read, Write};
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, IsNull, ToSql};
+
+primitive_impls! {
+    Hstore -> (HashMap<String, Option<String>>, 0, 0),
+}
+
+fn read_string(bytes: &mut &[u8], length: i32) -> Result<String, Box<Error>> {
+    if length < 0 || length as usize > bytes.len() {
+        let error_message = format!("Invalid length for an hstore key or value: {}", length);
+        return Err(Box::<Error + Send + Sync>::from(error_message));
+    }
+    let mut string = vec![0; length as usize];
+    try!(bytes.read_exact(&mut string));
+    super::text::string_from_utf8(&string)
+}
+
+impl FromSql<types::Hstore, Pg> for HashMap<String, Option<String>> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        let count = try!(bytes.read_i32::<BigEndian>());
+        let mut map = HashMap::new();
+        for _ in 0..count {
+            let key_length = try!(bytes.read_i32::<BigEndian>());
+            let key = try!(read_string(&mut bytes, key_length));
+            let value = match try!(bytes.read_i32::<BigEndian>()) {
+                -1 => None,
+                value_length => Some(try!(read_string(&mut bytes, value_length))),
+            };
+            map.insert(key, value);
+        }
+        Ok(map)
+    }
+}
+
+impl ToSql<types::Hstore, Pg> for HashMap<String, Option<String>> {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        try!(out.write_i32::<BigEndian>(self.len() as i32));
+        for (key, value) in self {
+            try!(out.write_i32::<BigEndian>(key.len() as i32));
+            try!(out.write_all(key.as_bytes()));
+            match *value {
+                Some(ref value) => {
+                    try!(out.write_i32::<BigEndian>(value.len() as i32));
+                    try!(out.write_all(value.as_bytes()));
+                }
+                None => try!(out.write_i32::<BigEndian>(-1)),
+            }
+        }
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.iter().fold(4, |size, (key, value)| {
+            size + 8 + key.len() + value.as_ref().map(|v| v.len()).unwrap_or(0)
+        })
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use backend::Pg;
+    use expression::dsl::sql;
+    use prelude::*;
+    use test_helpers::connection_with_extension;
+    use types::{FromSql, Hstore, ToSql};
+
+    fn connection() -> PgConnection {
+        connection_with_extension("hstore")
+    }
+
+    fn map() -> HashMap<String, Option<String>> {
+        let mut map = HashMap::new();
+        map.insert("name".to_string(), Some("Sean".to_string()));
+        map.insert("hair_color".to_string(), None);
+        map
+    }
+
+    #[test]
+    fn hstore_round_trips_through_its_binary_format() {
+        let mut bytes = Vec::new();
+        ToSql::<Hstore, Pg>::to_sql(&map(), &mut bytes).unwrap();
+        let loaded = <HashMap<String, Option<String>> as FromSql<Hstore, Pg>>::from_sql(Some(&bytes));
+        assert_eq!(map(), loaded.unwrap());
+    }
+
+    #[test]
+    fn null_values_have_a_length_of_negative_one() {
+        let mut map = HashMap::new();
+        map.insert("a".to_string(), None);
+        let mut bytes = Vec::new();
+        ToSql::<Hstore, Pg>::to_sql(&map, &mut bytes).unwrap();
+        assert_eq!(vec![0, 0, 0, 1, 0, 0, 0, 1, b'a', 255, 255, 255, 255], bytes);
+    }
+
+    #[test]
+    fn truncated_values_are_an_error() {
+        let bytes = [0, 0, 0, 1, 0, 0, 0, 5, b'a'];
+        assert!(<HashMap<String, Option<String>> as FromSql<Hstore, Pg>>::from_sql(Some(&bytes)).is_err());
+    }
+
+    #[test]
+    fn hstore_round_trips_through_postgres() {
+        let connection = connection();
+        let query = select(sql::<Hstore>("'name => Sean, hair_color => NULL'::hstore"));
+        assert_eq!(Ok(map()), query.get_result(&connection));
+    }
+
+    #[test]
+    fn hstore_operators() {
+        let connection = connection();
+        let hstore = || sql::<Hstore>("'name => Sean, hair_color => NULL'::hstore");
+        let query = select(hstore().get("name"));
+        assert_eq!(Ok(Some("Sean".to_string())), query.get_result(&connection));
+        let query = select(hstore().get("age"));
+        assert_eq!(Ok(None::<String>), query.get_result(&connection));
+        assert_eq!(Ok(true), select(hstore().has_key("hair_color")).get_result(&connection));
+        assert_eq!(Ok(false), select(hstore().has_key("age")).get_result(&connection));
+        assert_eq!(Ok(true), select(hstore().contains(map())).get_result(&connection));
+    }
+}
@@ -2,11 +2,31 @@ extern crate byteorder;
 
 use std::error::Error;
 use std::io::Write;
+use std::num::{NonZeroI16, NonZeroI32, NonZeroI64};
 
-use backend::{Backend, Pg};
+use backend::{Backend, Debug, Pg};
+use expression::{AsExpression, SelectableExpression};
+use expression::bound::Bound;
+use expression::nullable::SelectableAsNullable;
+use query_source::Queryable;
 use self::byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
 use super::option::UnexpectedNullError;
-use types::{self, FromSql, ToSql, IsNull};
+use types::{self, FromSql, HasSqlType, ToSql, IsNull, NotNull, Unsigned};
+
+impl<DB: Backend> FromSql<types::TinyInt, DB> for i8 {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        bytes.read_i8().map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl<DB: Backend> ToSql<types::TinyInt, DB> for i8 {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        out.write_i8(*self)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
 
 impl<DB: Backend> FromSql<types::SmallInt, DB> for i16 {
     fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
@@ -53,6 +73,65 @@ impl<DB: Backend> ToSql<types::BigInt, DB> for i64 {
     }
 }
 
+macro_rules! narrowing_impls {
+    ($($Source:ident -> ($Target:ident, $Wide:ident)),+,) => {
+        $(
+            impl<DB: Backend> FromSql<types::$Source, DB> for $Target {
+                fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+                    let value = try!(<$Wide as FromSql<types::$Source, DB>>::from_sql(bytes));
+                    if value > $Target::max_value() as $Wide || value < $Target::min_value() as $Wide {
+                        let error_message = format!("{} is out of range for {}", value, stringify!($Target));
+                        return Err(Box::<Error + Send + Sync>::from(error_message));
+                    }
+                    Ok(value as $Target)
+                }
+            }
+        )+
+
+        queryable_impls!($($Source -> $Target),+,);
+    }
+}
+
+narrowing_impls! {
+    Integer -> (i16, i32),
+    BigInt -> (i16, i64),
+    BigInt -> (i32, i64),
+}
+
+macro_rules! non_zero_impls {
+    ($($Source:ident -> ($NonZero:ident, $Int:ty)),+,) => {
+        $(
+            impl<DB: Backend> FromSql<types::$Source, DB> for $NonZero {
+                fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+                    let value = try!(<$Int as FromSql<types::$Source, DB>>::from_sql(bytes));
+                    match $NonZero::new(value) {
+                        Some(value) => Ok(value),
+                        None => {
+                            let error_message = concat!("Unexpected zero loading a ", stringify!($NonZero));
+                            Err(Box::<Error + Send + Sync>::from(error_message))
+                        }
+                    }
+                }
+            }
+
+            impl<DB: Backend> ToSql<types::$Source, DB> for $NonZero {
+                fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+                    ToSql::<types::$Source, DB>::to_sql(&self.get(), out)
+                }
+            }
+        )+
+
+        queryable_impls!($($Source -> $NonZero),+,);
+        expression_impls!($($Source -> $NonZero),+,);
+    }
+}
+
+non_zero_impls! {
+    SmallInt -> (NonZeroI16, i16),
+    Integer -> (NonZeroI32, i32),
+    BigInt -> (NonZeroI64, i64),
+}
+
 impl FromSql<types::Oid, Pg> for u32 {
     fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
         let mut bytes = not_none!(bytes);
@@ -68,6 +147,103 @@ impl ToSql<types::Oid, Pg> for u32 {
     }
 }
 
+impl<ST: NotNull> NotNull for Unsigned<ST> {
+}
+
+impl<ST, Expr, QS> SelectableAsNullable<Expr, QS> for Unsigned<ST> where
+    Expr: SelectableExpression<QS>,
+{
+}
+
+impl<ST> HasSqlType<Unsigned<ST>> for Debug where
+    Debug: HasSqlType<ST>,
+{
+    fn metadata() {}
+}
+
+queryable_impls! {
+    UnsignedTinyInt -> u8,
+    UnsignedSmallInt -> u16,
+    UnsignedInteger -> u32,
+    UnsignedBigInt -> u64,
+}
+
+expression_impls! {
+    UnsignedTinyInt -> u8,
+    UnsignedSmallInt -> u16,
+    UnsignedInteger -> u32,
+    UnsignedBigInt -> u64,
+}
+
+impl<DB: Backend> FromSql<types::Unsigned<types::TinyInt>, DB> for u8 {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        bytes.read_u8().map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl<DB: Backend> ToSql<types::Unsigned<types::TinyInt>, DB> for u8 {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        out.write_u8(*self)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl<DB: Backend> FromSql<types::Unsigned<types::SmallInt>, DB> for u16 {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        bytes.read_u16::<BigEndian>().map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl<DB: Backend> ToSql<types::Unsigned<types::SmallInt>, DB> for u16 {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        out.write_u16::<BigEndian>(*self)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl<DB: Backend> FromSql<types::Unsigned<types::Integer>, DB> for u32 {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        bytes.read_u32::<BigEndian>().map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl<DB: Backend> ToSql<types::Unsigned<types::Integer>, DB> for u32 {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        out.write_u32::<BigEndian>(*self)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl<DB: Backend> FromSql<types::Unsigned<types::BigInt>, DB> for u64 {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        bytes.read_u64::<BigEndian>().map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl<DB: Backend> ToSql<types::Unsigned<types::BigInt>, DB> for u64 {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        out.write_u64::<BigEndian>(*self)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+#[test]
+fn i8_to_sql() {
+    let mut bytes = vec![];
+    ToSql::<types::TinyInt, Pg>::to_sql(&1i8, &mut bytes).unwrap();
+    ToSql::<types::TinyInt, Pg>::to_sql(&0i8, &mut bytes).unwrap();
+    ToSql::<types::TinyInt, Pg>::to_sql(&-1i8, &mut bytes).unwrap();
+    assert_eq!(bytes, vec![1, 0, 255]);
+}
+
 #[test]
 fn i16_to_sql() {
     let mut bytes = vec![];
@@ -97,3 +273,43 @@ fn i64_to_sql() {
                0, 0, 0, 0, 0, 0, 0, 0,
                255, 255, 255, 255, 255, 255, 255, 255]);
 }
+
+#[test]
+fn unsigned_integers_use_their_full_range() {
+    let mut bytes = vec![];
+    ToSql::<types::Unsigned<types::Integer>, Debug>::to_sql(&u32::max_value(), &mut bytes).unwrap();
+    assert_eq!(bytes, vec![255, 255, 255, 255]);
+    let loaded = <u32 as FromSql<types::Unsigned<types::Integer>, Debug>>::from_sql(Some(&bytes));
+    assert_eq!(u32::max_value(), loaded.unwrap());
+
+    let mut bytes = vec![];
+    ToSql::<types::Unsigned<types::BigInt>, Debug>::to_sql(&u64::max_value(), &mut bytes).unwrap();
+    let loaded = <u64 as FromSql<types::Unsigned<types::BigInt>, Debug>>::from_sql(Some(&bytes));
+    assert_eq!(u64::max_value(), loaded.unwrap());
+}
+
+#[test]
+fn non_zero_integers_cannot_be_loaded_from_zero() {
+    let mut bytes = vec![];
+    ToSql::<types::Integer, Pg>::to_sql(&NonZeroI32::new(7).unwrap(), &mut bytes).unwrap();
+    assert_eq!(bytes, vec![0, 0, 0, 7]);
+    let loaded = <NonZeroI32 as FromSql<types::Integer, Pg>>::from_sql(Some(&bytes));
+    assert_eq!(NonZeroI32::new(7), loaded.ok());
+
+    let error = <NonZeroI64 as FromSql<types::BigInt, Pg>>::from_sql(Some(&[0; 8])).unwrap_err();
+    assert_eq!("Unexpected zero loading a NonZeroI64", error.to_string());
+}
+
+#[test]
+fn narrowing_conversions_check_the_range() {
+    let mut bytes = vec![];
+    ToSql::<types::BigInt, Pg>::to_sql(&-70000i64, &mut bytes).unwrap();
+    assert_eq!(Ok(-70000), <i32 as FromSql<types::BigInt, Pg>>::from_sql(Some(&bytes))
+               .map_err(|e| e.to_string()));
+    let error = <i16 as FromSql<types::BigInt, Pg>>::from_sql(Some(&bytes)).unwrap_err();
+    assert_eq!("-70000 is out of range for i16", error.to_string());
+
+    let mut bytes = vec![];
+    ToSql::<types::BigInt, Pg>::to_sql(&i64::max_value(), &mut bytes).unwrap();
+    assert!(<i32 as FromSql<types::BigInt, Pg>>::from_sql(Some(&bytes)).is_err());
+}
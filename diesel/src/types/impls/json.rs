@@ -0,0 +1,108 @@
+//! This module makes it possible to map `serde_json::Value` to postgres `json`
+//! and `jsonb` fields. It is enabled with the `serde_json` feature.
+//!
+//! Both are sent as JSON text. PostgreSQL's binary format for `jsonb` starts
+//! with a version byte, which is always 1.
+extern crate serde_json;
+
+use std::error::Error;
+use std::io::Write;
+use self::serde_json::Value;
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, IsNull, ToSql};
+
+primitive_impls! {
+    Json -> (Value, 114, 199),
+    Jsonb -> (Value, 3802, 3807),
+}
+
+const JSONB_VERSION: u8 = 1;
+
+impl FromSql<types::Json, Pg> for Value {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let bytes = not_none!(bytes);
+        serde_json::from_slice(bytes).map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl ToSql<types::Json, Pg> for Value {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        serde_json::to_writer(out, self)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl FromSql<types::Jsonb, Pg> for Value {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let bytes = not_none!(bytes);
+        match bytes.split_first() {
+            Some((&JSONB_VERSION, json)) => serde_json::from_slice(json).map_err(|e| Box::new(e) as Box<Error>),
+            Some((&version, _)) => {
+                let error_message = format!("Unsupported jsonb version {}. Only version 1 is supported", version);
+                Err(Box::<Error + Send + Sync>::from(error_message))
+            }
+            None => Err(Box::<Error + Send + Sync>::from("Expected a jsonb value to have a version byte")),
+        }
+    }
+}
+
+impl ToSql<types::Jsonb, Pg> for Value {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        try!(out.write_all(&[JSONB_VERSION]));
+        ToSql::<types::Json, Pg>::to_sql(self, out)
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    extern crate serde_json;
+
+    use self::serde_json::Value;
+
+    use backend::Pg;
+    use expression::dsl::sql;
+    use prelude::*;
+    use test_helpers::connection;
+    use types::{self, FromSql, ToSql};
+
+    fn value() -> Value {
+        serde_json::from_str(r#"{"name": "Sean", "tags": [1, 2]}"#).unwrap()
+    }
+
+    #[test]
+    fn only_jsonb_is_prefixed_with_a_version_byte() {
+        let mut bytes = Vec::new();
+        ToSql::<types::Jsonb, Pg>::to_sql(&Value::Bool(true), &mut bytes).unwrap();
+        assert_eq!(b"\x01true".to_vec(), bytes);
+        let mut bytes = Vec::new();
+        ToSql::<types::Json, Pg>::to_sql(&Value::Bool(true), &mut bytes).unwrap();
+        assert_eq!(b"true".to_vec(), bytes);
+
+        let loaded = <Value as FromSql<types::Jsonb, Pg>>::from_sql(Some(b"\x01[1]")).unwrap();
+        assert_eq!(serde_json::from_str::<Value>("[1]").unwrap(), loaded);
+        assert!(<Value as FromSql<types::Jsonb, Pg>>::from_sql(Some(b"\x02[1]")).is_err());
+        assert!(<Value as FromSql<types::Jsonb, Pg>>::from_sql(Some(b"")).is_err());
+    }
+
+    #[test]
+    fn json_round_trips_through_postgres() {
+        let connection = connection();
+        let query = select(sql::<types::Json>(r#"'{"name": "Sean", "tags": [1, 2]}'::json"#));
+        assert_eq!(Ok(value()), query.get_result::<Value>(&connection));
+    }
+
+    #[test]
+    fn jsonb_round_trips_through_postgres() {
+        let connection = connection();
+        let query = select(sql::<types::Jsonb>(r#"'{"name": "Sean", "tags": [1, 2]}'::jsonb"#));
+        assert_eq!(Ok(value()), query.get_result::<Value>(&connection));
+        let query = select(sql::<types::Jsonb>(r#"'{"name": "Sean", "tags": [1, 2]}'::jsonb"#).eq(value()));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+}
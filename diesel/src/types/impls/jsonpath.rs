@@ -0,0 +1,73 @@
+//! A `jsonpath` is sent as its text, preceded by a version byte, which is
+//! always 1. `jsonpath` was added in PostgreSQL 12.
+use std::error::Error;
+use std::io::Write;
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, IsNull, ToSql};
+
+primitive_impls! {
+    Jsonpath -> (String, 4072, 4073),
+}
+
+expression_impls! {
+    Jsonpath -> &'a str,
+}
+
+const JSONPATH_VERSION: u8 = 1;
+
+impl FromSql<types::Jsonpath, Pg> for String {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let bytes = not_none!(bytes);
+        match bytes.split_first() {
+            Some((&JSONPATH_VERSION, path)) => super::text::string_from_utf8(path),
+            Some((&version, _)) => {
+                let error_message = format!("Unsupported jsonpath version {}. Only version 1 is supported", version);
+                Err(Box::<Error + Send + Sync>::from(error_message))
+            }
+            None => Err(Box::<Error + Send + Sync>::from("Expected a jsonpath value to have a version byte")),
+        }
+    }
+}
+
+impl ToSql<types::Jsonpath, Pg> for String {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        ToSql::<types::Jsonpath, Pg>::to_sql(&&self[..], out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len() + 1
+    }
+}
+
+impl<'a> ToSql<types::Jsonpath, Pg> for &'a str {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        try!(out.write_all(&[JSONPATH_VERSION]));
+        try!(out.write_all(self.as_bytes()));
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len() + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use backend::Pg;
+    use types::{FromSql, Jsonpath, ToSql};
+
+    #[test]
+    fn jsonpaths_are_sent_with_a_version_byte() {
+        let mut bytes = Vec::new();
+        ToSql::<Jsonpath, Pg>::to_sql(&"$.a", &mut bytes).unwrap();
+        assert_eq!(b"\x01$.a".to_vec(), bytes);
+        let loaded = <String as FromSql<Jsonpath, Pg>>::from_sql(Some(&bytes));
+        assert_eq!("$.a", loaded.unwrap());
+        assert!(<String as FromSql<Jsonpath, Pg>>::from_sql(Some(b"\x02$.a")).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+//! `ltree` and `lquery` are provided by an extension, so their OIDs are not
+//! known until runtime. Bind parameters are sent with an OID of 0, which lets
+//! PostgreSQL infer the type from the query.
+//!
+//! Both are sent as their text, preceded by a version byte, which is always 1.
+use std::error::Error;
+use std::io::Write;
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, IsNull, ToSql};
+
+/// A path of labels separated by dots, such as `Top.Science.Astronomy`. This
+/// is what `ltree` values are loaded as.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PgLtree(pub String);
+
+impl PgLtree {
+    /// The labels in this path, from the root down. The empty path has no
+    /// labels.
+    pub fn labels(&self) -> Vec<&str> {
+        if self.0.is_empty() {
+            Vec::new()
+        } else {
+            self.0.split('.').collect()
+        }
+    }
+}
+
+primitive_impls! {
+    Ltree -> (PgLtree, 0, 0),
+    Lquery -> (String, 0, 0),
+}
+
+expression_impls! {
+    Ltree -> &'a str,
+    Lquery -> &'a str,
+}
+
+const LTREE_VERSION: u8 = 1;
+
+fn read_versioned(bytes: &[u8], type_name: &str) -> Result<String, Box<Error>> {
+    match bytes.split_first() {
+        Some((&LTREE_VERSION, text)) => super::text::string_from_utf8(text),
+        Some((&version, _)) => {
+            let error_message = format!("Unsupported {} version {}. Only version 1 is supported", type_name, version);
+            Err(Box::<Error + Send + Sync>::from(error_message))
+        }
+        None => {
+            let error_message = format!("Expected an {} value to have a version byte", type_name);
+            Err(Box::<Error + Send + Sync>::from(error_message))
+        }
+    }
+}
+
+fn write_versioned<W: Write>(text: &str, out: &mut W) -> Result<IsNull, Box<Error>> {
+    try!(out.write_all(&[LTREE_VERSION]));
+    try!(out.write_all(text.as_bytes()));
+    Ok(IsNull::No)
+}
+
+impl FromSql<types::Ltree, Pg> for PgLtree {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        read_versioned(not_none!(bytes), "ltree").map(PgLtree)
+    }
+}
+
+impl ToSql<types::Ltree, Pg> for PgLtree {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        write_versioned(&self.0, out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.len() + 1
+    }
+}
+
+impl<'a> ToSql<types::Ltree, Pg> for &'a str {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        write_versioned(self, out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len() + 1
+    }
+}
+
+impl FromSql<types::Lquery, Pg> for String {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        read_versioned(not_none!(bytes), "lquery")
+    }
+}
+
+impl ToSql<types::Lquery, Pg> for String {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        write_versioned(self, out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len() + 1
+    }
+}
+
+impl<'a> ToSql<types::Lquery, Pg> for &'a str {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        write_versioned(self, out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len() + 1
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use backend::Pg;
+    use expression::dsl::sql;
+    use prelude::*;
+    use test_helpers::connection_with_extension;
+    use types::{FromSql, Ltree, ToSql};
+    use types::data_types::PgLtree;
+
+    fn connection() -> PgConnection {
+        connection_with_extension("ltree")
+    }
+
+    fn path(path: &str) -> PgLtree {
+        PgLtree(path.to_string())
+    }
+
+    #[test]
+    fn ltrees_are_sent_with_a_version_byte() {
+        let mut bytes = Vec::new();
+        ToSql::<Ltree, Pg>::to_sql(&path("Top.Science"), &mut bytes).unwrap();
+        assert_eq!(b"\x01Top.Science".to_vec(), bytes);
+        let loaded = <PgLtree as FromSql<Ltree, Pg>>::from_sql(Some(&bytes));
+        assert_eq!(path("Top.Science"), loaded.unwrap());
+        assert!(<PgLtree as FromSql<Ltree, Pg>>::from_sql(Some(b"\x02Top")).is_err());
+        assert!(<PgLtree as FromSql<Ltree, Pg>>::from_sql(Some(b"")).is_err());
+    }
+
+    #[test]
+    fn ltree_labels() {
+        assert_eq!(vec!["Top", "Science", "Astronomy"], path("Top.Science.Astronomy").labels());
+        assert!(path("").labels().is_empty());
+    }
+
+    #[test]
+    fn ltree_round_trips_through_postgres() {
+        let connection = connection();
+        let query = select(sql::<Ltree>("'Top.Science.Astronomy'::ltree"));
+        assert_eq!(Ok(path("Top.Science.Astronomy")), query.get_result(&connection));
+    }
+
+    #[test]
+    fn ltree_operators() {
+        let connection = connection();
+        let ltree = || sql::<Ltree>("'Top.Science.Astronomy'::ltree");
+        assert_eq!(Ok(true), select(ltree().is_descendant_of("Top.Science")).get_result(&connection));
+        assert_eq!(Ok(false), select(ltree().is_ancestor_of("Top.Science")).get_result(&connection));
+        assert_eq!(Ok(true), select(ltree().is_ancestor_of(path("Top.Science.Astronomy.Stars"))).get_result(&connection));
+        assert_eq!(Ok(true), select(ltree().matches("*.Science.*")).get_result(&connection));
+        assert_eq!(Ok(false), select(ltree().matches("Top.Hobbies.*")).get_result(&connection));
+    }
+}
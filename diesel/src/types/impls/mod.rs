@@ -9,7 +9,7 @@ macro_rules! not_none {
     }
 }
 
-macro_rules! expression_impls {
+macro_rules! as_expression_impls {
     ($($Source:ident -> $Target:ty),+,) => {
         $(
             impl<'a> AsExpression<types::$Source> for $Target {
@@ -43,7 +43,15 @@ macro_rules! expression_impls {
                     Bound::new(self)
                 }
             }
+        )+
+    }
+}
+
+macro_rules! expression_impls {
+    ($($Source:ident -> $Target:ty),+,) => {
+        as_expression_impls!($($Source -> $Target),+,);
 
+        $(
             impl<'a, DB> ToSql<types::Nullable<types::$Source>, DB> for $Target where
                 DB: $crate::backend::Backend + types::HasSqlType<types::$Source>,
                 $Target: ToSql<types::$Source, DB>,
@@ -51,6 +59,10 @@ macro_rules! expression_impls {
                 fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
                     ToSql::<types::$Source, DB>::to_sql(self, out)
                 }
+
+                fn size_hint(&self) -> usize {
+                    ToSql::<types::$Source, DB>::size_hint(self)
+                }
             }
         )+
     }
@@ -111,6 +123,12 @@ macro_rules! primitive_impls {
 
             impl types::NotNull for types::$Source {
             }
+
+            impl<Expr, QS> $crate::expression::nullable::SelectableAsNullable<Expr, QS>
+                for types::$Source where
+                Expr: $crate::expression::SelectableExpression<QS>,
+            {
+            }
         )+
         queryable_impls!($($Source -> $Target),+,);
         expression_impls!($($Source -> $Target),+,);
@@ -118,9 +136,29 @@ macro_rules! primitive_impls {
 }
 
 mod array;
+mod bits;
+pub mod bytea;
+mod catalog;
+mod chars;
 pub mod date_and_time;
 pub mod floats;
+pub mod geometric;
+mod hstore;
 mod integers;
+#[cfg(feature = "serde_json")]
+mod json;
+mod jsonpath;
+pub mod ltree;
+pub mod money;
+pub mod network;
 mod option;
+#[cfg(feature = "postgis")]
+mod postgis;
 mod primitives;
+mod record;
+pub mod text;
+mod text_search;
 mod tuples;
+#[cfg(feature = "uuid")]
+mod uuid;
+mod xml;
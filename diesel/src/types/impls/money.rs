@@ -0,0 +1,91 @@
+//! PostgreSQL sends a `money` value as a 64 bit integer, which is the amount in
+//! the smallest unit of the currency. The number of decimal places is set by
+//! the `lc_monetary` setting of the database, and is usually 2.
+use std::error::Error;
+use std::io::Write;
+use std::ops::{Add, Sub};
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, IsNull, ToSql};
+
+/// An amount of money in the smallest unit of its currency, such as cents for
+/// US dollars. This is what `money` values are loaded as. Diesel doesn't know
+/// which currency is used, as that is set by the `lc_monetary` setting of the
+/// database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Cents(pub i64);
+
+primitive_impls! {
+    Money -> (Cents, 790, 791),
+}
+
+impl FromSql<types::Money, Pg> for Cents {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        FromSql::<types::BigInt, Pg>::from_sql(bytes).map(Cents)
+    }
+}
+
+impl ToSql<types::Money, Pg> for Cents {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        ToSql::<types::BigInt, Pg>::to_sql(&self.0, out)
+    }
+}
+
+impl Add for Cents {
+    type Output = Cents;
+
+    fn add(self, other: Cents) -> Self::Output {
+        Cents(self.0 + other.0)
+    }
+}
+
+impl Sub for Cents {
+    type Output = Cents;
+
+    fn sub(self, other: Cents) -> Self::Output {
+        Cents(self.0 - other.0)
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use backend::Pg;
+    use expression::AsExpression;
+    use expression::dsl::sql;
+    use expression::ops::{Add, Mul};
+    use prelude::*;
+    use super::Cents;
+    use test_helpers::connection;
+    use types::{BigInt, FromSql, Money, ToSql};
+
+    #[test]
+    fn money_is_sent_as_an_integer() {
+        let mut bytes = Vec::new();
+        ToSql::<Money, Pg>::to_sql(&Cents(-1234), &mut bytes).unwrap();
+        assert_eq!(vec![255, 255, 255, 255, 255, 255, 251, 46], bytes);
+        assert_eq!(Cents(-1234), <Cents as FromSql<Money, Pg>>::from_sql(Some(&bytes)).unwrap());
+    }
+
+    #[test]
+    fn money_round_trips_through_postgres() {
+        let connection = connection();
+        let query = select(sql::<Money>("12.34::numeric::money"));
+        assert_eq!(Ok(Cents(1234)), query.get_result(&connection));
+        let query = select(sql::<Money>("12.34::numeric::money").eq(Cents(1234)));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+
+    #[test]
+    fn money_can_be_added_and_multiplied() {
+        let connection = connection();
+        let money = || sql::<Money>("12.34::numeric::money");
+        let query = select(Add::new(money(), AsExpression::<Money>::as_expression(Cents(66))));
+        assert_eq!(Ok(Cents(1300)), query.get_result(&connection));
+        let query = select(Mul::new(money(), AsExpression::<BigInt>::as_expression(2i64)));
+        assert_eq!(Ok(Cents(2468)), query.get_result(&connection));
+    }
+}
@@ -0,0 +1,202 @@
+//! PostgreSQL sends `inet` and `cidr` values as the address family, the number
+//! of bits in the netmask, whether the value is a `cidr`, the number of bytes in
+//! the address, and then the address itself.
+use std::error::Error;
+use std::io::Write;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, IsNull, ToSql};
+
+/// An IP address along with the number of bits in its netmask, such as
+/// `192.168.0.1/24`. This is what `inet` and `cidr` values are loaded as. An
+/// `inet` which is a single address can also be loaded as a
+/// `std::net::IpAddr`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PgNetwork {
+    pub address: IpAddr,
+    pub prefix_length: u8,
+}
+
+impl PgNetwork {
+    /// A network containing only the given address.
+    pub fn from_address(address: IpAddr) -> Self {
+        PgNetwork {
+            address: address,
+            prefix_length: max_prefix_length(&address),
+        }
+    }
+}
+
+primitive_impls! {
+    Inet -> (PgNetwork, 869, 1041),
+    Cidr -> (PgNetwork, 650, 651),
+}
+
+expression_impls! {
+    Inet -> IpAddr,
+}
+
+queryable_impls! {
+    Inet -> IpAddr,
+}
+
+const PGSQL_AF_INET: u8 = 2;
+const PGSQL_AF_INET6: u8 = 3;
+
+fn max_prefix_length(address: &IpAddr) -> u8 {
+    match *address {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+fn network_from_sql(bytes: &[u8]) -> Result<PgNetwork, Box<Error>> {
+    if bytes.len() < 4 || bytes.len() != 4 + bytes[3] as usize {
+        let error_message = format!("Invalid inet or cidr value: {:?}", bytes);
+        return Err(Box::<Error + Send + Sync>::from(error_message));
+    }
+    let (family, prefix_length, address) = (bytes[0], bytes[1], &bytes[4..]);
+    let address = match (family, address.len()) {
+        (PGSQL_AF_INET, 4) => IpAddr::V4(Ipv4Addr::new(address[0], address[1], address[2], address[3])),
+        (PGSQL_AF_INET6, 16) => {
+            let mut segments = [0u16; 8];
+            for (segment, pair) in segments.iter_mut().zip(address.chunks(2)) {
+                *segment = (pair[0] as u16) << 8 | pair[1] as u16;
+            }
+            IpAddr::V6(Ipv6Addr::new(segments[0], segments[1], segments[2], segments[3],
+                                     segments[4], segments[5], segments[6], segments[7]))
+        }
+        _ => {
+            let error_message = format!("Invalid inet or cidr value: {:?}", bytes);
+            return Err(Box::<Error + Send + Sync>::from(error_message));
+        }
+    };
+    Ok(PgNetwork { address: address, prefix_length: prefix_length })
+}
+
+fn network_to_sql<W: Write>(network: &PgNetwork, is_cidr: bool, out: &mut W) -> Result<IsNull, Box<Error>> {
+    if network.prefix_length > max_prefix_length(&network.address) {
+        let error_message = format!("{:?} has a prefix length which is too large for its address", network);
+        return Err(Box::<Error + Send + Sync>::from(error_message));
+    }
+    let (family, address) = match network.address {
+        IpAddr::V4(address) => (PGSQL_AF_INET, address.octets().to_vec()),
+        IpAddr::V6(address) => (PGSQL_AF_INET6, address.octets().to_vec()),
+    };
+    try!(out.write_all(&[family, network.prefix_length, is_cidr as u8, address.len() as u8]));
+    try!(out.write_all(&address));
+    Ok(IsNull::No)
+}
+
+impl FromSql<types::Inet, Pg> for PgNetwork {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        network_from_sql(not_none!(bytes))
+    }
+}
+
+impl ToSql<types::Inet, Pg> for PgNetwork {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        network_to_sql(self, false, out)
+    }
+}
+
+impl FromSql<types::Cidr, Pg> for PgNetwork {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        network_from_sql(not_none!(bytes))
+    }
+}
+
+impl ToSql<types::Cidr, Pg> for PgNetwork {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        network_to_sql(self, true, out)
+    }
+}
+
+/// Only an `inet` which is a single address can be loaded as an `IpAddr`. Any
+/// other value returns an error, rather than silently dropping the netmask.
+impl FromSql<types::Inet, Pg> for IpAddr {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let network = try!(network_from_sql(not_none!(bytes)));
+        if network.prefix_length == max_prefix_length(&network.address) {
+            Ok(network.address)
+        } else {
+            let error_message = format!("Expected an inet value to be a single address, but it was \
+                                         {}/{}. Load it as a PgNetwork instead.",
+                                        network.address, network.prefix_length);
+            Err(Box::<Error + Send + Sync>::from(error_message))
+        }
+    }
+}
+
+impl ToSql<types::Inet, Pg> for IpAddr {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        ToSql::<types::Inet, Pg>::to_sql(&PgNetwork::from_address(*self), out)
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use backend::Pg;
+    use expression::dsl::sql;
+    use prelude::*;
+    use super::PgNetwork;
+    use test_helpers::connection;
+    use types::{self, FromSql, ToSql};
+
+    fn network(address: &str, prefix_length: u8) -> PgNetwork {
+        PgNetwork { address: address.parse().unwrap(), prefix_length: prefix_length }
+    }
+
+    #[test]
+    fn networks_are_sent_with_their_family_and_netmask() {
+        let mut bytes = Vec::new();
+        ToSql::<types::Cidr, Pg>::to_sql(&network("192.168.0.0", 24), &mut bytes).unwrap();
+        assert_eq!(vec![2, 24, 1, 4, 192, 168, 0, 0], bytes);
+
+        let mut bytes = Vec::new();
+        ToSql::<types::Inet, Pg>::to_sql(&IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)), &mut bytes).unwrap();
+        assert_eq!(vec![3, 128, 0, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], bytes);
+    }
+
+    #[test]
+    fn invalid_networks_are_an_error() {
+        let mut bytes = Vec::new();
+        assert!(ToSql::<types::Inet, Pg>::to_sql(&network("10.0.0.1", 33), &mut bytes).is_err());
+        assert!(<PgNetwork as FromSql<types::Inet, Pg>>::from_sql(Some(&[2, 32, 0, 4, 10, 0])).is_err());
+        assert!(<PgNetwork as FromSql<types::Inet, Pg>>::from_sql(Some(&[9, 32, 0, 4, 10, 0, 0, 1])).is_err());
+    }
+
+    #[test]
+    fn inet_and_cidr_round_trip_through_postgres() {
+        let connection = connection();
+        let query = select(sql::<types::Inet>("'10.0.0.1/8'::inet"));
+        assert_eq!(Ok(network("10.0.0.1", 8)), query.get_result(&connection));
+        let query = select(sql::<types::Inet>("'10.0.0.1/8'::inet").eq(network("10.0.0.1", 8)));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+
+        let query = select(sql::<types::Cidr>("'2001:db8::/32'::cidr"));
+        assert_eq!(Ok(network("2001:db8::", 32)), query.get_result(&connection));
+        let query = select(sql::<types::Cidr>("'2001:db8::/32'::cidr").eq(network("2001:db8::", 32)));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+
+    #[test]
+    fn single_addresses_can_be_loaded_as_ip_addr() {
+        let connection = connection();
+        let address = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+        let query = select(sql::<types::Inet>("'127.0.0.1'::inet"));
+        assert_eq!(Ok(address), query.get_result::<IpAddr>(&connection));
+        let query = select(sql::<types::Inet>("'127.0.0.1'::inet").eq(address));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+
+        let query = select(sql::<types::Inet>("'127.0.0.1/8'::inet"));
+        assert!(query.get_result::<IpAddr>(&connection).is_err());
+    }
+}
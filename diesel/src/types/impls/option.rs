@@ -39,6 +39,10 @@ impl<T, ST, DB> Queryable<Nullable<ST>, DB> for Option<T> where
     fn build(row: Self::Row) -> Self {
         row.map(T::build)
     }
+
+    fn describe_error(error: Box<Error>) -> Box<Error> {
+        T::describe_error(error)
+    }
 }
 
 impl<T, ST, DB> ToSql<Nullable<ST>, DB> for Option<T> where
@@ -53,6 +57,10 @@ impl<T, ST, DB> ToSql<Nullable<ST>, DB> for Option<T> where
             Ok(IsNull::Yes)
         }
     }
+
+    fn size_hint(&self) -> usize {
+        self.as_ref().map(|value| value.size_hint()).unwrap_or(0)
+    }
 }
 
 impl<T, ST> AsExpression<Nullable<ST>> for Option<T> where
@@ -0,0 +1,100 @@
+//! This module makes it possible to map `postgis::ewkb::Geometry` and
+//! `postgis::ewkb::Point` values to PostGIS `geometry` fields. It is enabled
+//! with the `postgis` feature.
+//!
+//! PostGIS sends geometries in its extended well-known binary format (EWKB),
+//! which includes the SRID of the geometry if it has one. `geometry` is
+//! provided by an extension, so its OID is not known until runtime. Bind
+//! parameters are sent with an OID of 0, which lets PostgreSQL infer the type
+//! from the query.
+extern crate postgis;
+
+use std::error::Error;
+use std::io::Write;
+use self::postgis::ewkb::{AsEwkbGeometry, AsEwkbPoint, EwkbRead, EwkbWrite, Geometry, Point};
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, IsNull, ToSql};
+
+primitive_impls! {
+    Geometry -> (Geometry, 0, 0),
+}
+
+expression_impls! {
+    Geometry -> Point,
+}
+
+queryable_impls! {
+    Geometry -> Point,
+}
+
+impl FromSql<types::Geometry, Pg> for Geometry {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        Geometry::read_ewkb(&mut bytes).map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl ToSql<types::Geometry, Pg> for Geometry {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        self.as_ewkb().write_ewkb(out)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+/// Loading a geometry which isn't a point as a `Point` returns an error.
+impl FromSql<types::Geometry, Pg> for Point {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        Point::read_ewkb(&mut bytes).map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl ToSql<types::Geometry, Pg> for Point {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        self.as_ewkb().write_ewkb(out)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate postgis;
+
+    use self::postgis::ewkb::{Geometry, Point};
+
+    use backend::Pg;
+    use types::{self, FromSql, ToSql};
+
+    // A little endian EWKB point at (1, 2) with an SRID of 4326
+    const POINT: &'static [u8] = &[
+        1, 1, 0, 0, 32, 230, 16, 0, 0,
+        0, 0, 0, 0, 0, 0, 240, 63,
+        0, 0, 0, 0, 0, 0, 0, 64,
+    ];
+
+    #[test]
+    fn points_are_sent_as_ewkb() {
+        let point = Point { x: 1.0, y: 2.0, srid: Some(4326) };
+        let mut bytes = Vec::new();
+        ToSql::<types::Geometry, Pg>::to_sql(&point, &mut bytes).unwrap();
+        assert_eq!(POINT.to_vec(), bytes);
+    }
+
+    #[test]
+    fn geometries_are_loaded_from_ewkb() {
+        let point = <Point as FromSql<types::Geometry, Pg>>::from_sql(Some(POINT)).unwrap();
+        assert_eq!(Point { x: 1.0, y: 2.0, srid: Some(4326) }, point);
+
+        match <Geometry as FromSql<types::Geometry, Pg>>::from_sql(Some(POINT)).unwrap() {
+            Geometry::Point(point) => assert_eq!((1.0, 2.0), (point.x, point.y)),
+            geometry => panic!("Expected a point, got {:?}", geometry),
+        }
+    }
+}
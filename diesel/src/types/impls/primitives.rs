@@ -6,12 +6,13 @@ use data_types::PgNumeric;
 use expression::bound::Bound;
 use expression::AsExpression;
 use super::option::UnexpectedNullError;
-use types::{HasSqlType, FromSql, ToSql, IsNull, NotNull};
+use types::{HasSqlType, FromSql, FromSqlRow, ToSql, IsNull, NotNull};
 use {Queryable, types};
 
 primitive_impls! {
     Bool -> (bool, 16, 1000),
 
+    TinyInt -> (i8, 18, 1002),
     SmallInt -> (i16, 21, 1005),
     Integer -> (i32, 23, 1007),
     BigInt -> (i64, 20, 1016),
@@ -37,16 +38,38 @@ expression_impls! {
 
 impl NotNull for () {}
 
-impl FromSql<types::Bool, Pg> for bool {
+/// PostgreSQL sends booleans as a single byte, but backends which store
+/// booleans as integers will send an integer of any width. Both are accepted, as
+/// long as the value is 0 or 1.
+impl<DB: Backend + HasSqlType<types::Bool>> FromSql<types::Bool, DB> for bool {
     fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
         match bytes {
-            Some(bytes) => Ok(bytes[0] != 0),
+            Some(bytes) => bool_from_integer_bytes(bytes),
             None => Ok(false),
         }
     }
 }
 
-impl ToSql<types::Bool, Pg> for bool {
+fn bool_from_integer_bytes(bytes: &[u8]) -> Result<bool, Box<Error>> {
+    if bytes.is_empty() || bytes.len() > 8 {
+        let error_message = format!("Expected a boolean to be 1 to 8 bytes, got {}", bytes.len());
+        return Err(Box::<Error + Send + Sync>::from(error_message));
+    }
+    let value = bytes.iter().fold(0u64, |value, &byte| (value << 8) | byte as u64);
+    match value {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => {
+            let error_message = format!("Invalid value for a boolean: {:?}. Expected 0 or 1", bytes);
+            Err(Box::<Error + Send + Sync>::from(error_message))
+        }
+    }
+}
+
+/// Booleans are sent as a single byte, which is 0 or 1. This is what
+/// PostgreSQL expects, and is also a valid one byte integer for backends which
+/// store booleans as integers.
+impl<DB: Backend + HasSqlType<types::Bool>> ToSql<types::Bool, DB> for bool {
     fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
         let write_result = if *self {
             out.write_all(&[1])
@@ -62,7 +85,7 @@ impl ToSql<types::Bool, Pg> for bool {
 impl<DB: Backend> FromSql<types::VarChar, DB> for String {
     fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
         let bytes = not_none!(bytes);
-        String::from_utf8(bytes.into()).map_err(|e| Box::new(e) as Box<Error>)
+        super::text::string_from_utf8(bytes)
     }
 }
 
@@ -73,6 +96,10 @@ impl<DB> ToSql<types::VarChar, DB> for String where
     fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
         (&self as &str).to_sql(out)
     }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
 }
 
 impl<'a, DB: Backend> ToSql<types::VarChar, DB> for &'a str {
@@ -81,6 +108,10 @@ impl<'a, DB: Backend> ToSql<types::VarChar, DB> for &'a str {
             .map(|_| IsNull::No)
             .map_err(|e| Box::new(e) as Box<Error>)
     }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
 }
 
 impl<DB> FromSql<types::Text, DB> for String where
@@ -99,6 +130,10 @@ impl<DB> ToSql<types::Text, DB> for String where
     fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
         (&self as &str).to_sql(out)
     }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
 }
 
 impl<'a, DB> ToSql<types::Text, DB> for &'a str where
@@ -108,6 +143,10 @@ impl<'a, DB> ToSql<types::Text, DB> for &'a str where
     fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
         ToSql::<types::VarChar, DB>::to_sql(self, out)
     }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
 }
 
 impl<DB: Backend> FromSql<types::Binary, DB> for Vec<u8> {
@@ -123,6 +162,10 @@ impl<DB> ToSql<types::Binary, DB> for Vec<u8> where
     fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
         (&self as &[u8]).to_sql(out)
     }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
 }
 
 impl<'a, DB: Backend> ToSql<types::Binary, DB> for &'a [u8] {
@@ -131,22 +174,36 @@ impl<'a, DB: Backend> ToSql<types::Binary, DB> for &'a [u8] {
             .map(|_| IsNull::No)
             .map_err(|e| Box::new(e) as Box<Error>)
     }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
 }
 
 use std::borrow::{Cow, ToOwned};
 impl<'a, T: ?Sized, ST, DB> ToSql<ST, DB> for Cow<'a, T> where
-    T: 'a + ToOwned + ToSql<ST, DB>,
+    T: 'a + ToOwned,
     DB: Backend + HasSqlType<ST>,
-    T::Owned: ToSql<ST, DB>,
+    for<'b> &'b T: ToSql<ST, DB>,
 {
     fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
-        match self {
-            &Cow::Borrowed(ref t) => t.to_sql(out),
-            &Cow::Owned(ref t) => t.to_sql(out),
-        }
+        let borrowed: &T = &**self;
+        ToSql::<ST, DB>::to_sql(&borrowed, out)
+    }
+
+    fn size_hint(&self) -> usize {
+        let borrowed: &T = &**self;
+        ToSql::<ST, DB>::size_hint(&borrowed)
     }
 }
 
+as_expression_impls! {
+    VarChar -> Cow<'a, str>,
+    Text -> Cow<'a, str>,
+
+    Binary -> Cow<'a, [u8]>,
+}
+
 impl<'a, T: ?Sized, ST, DB> FromSql<ST, DB> for Cow<'a, T> where
     T: 'a + ToOwned,
     DB: Backend + HasSqlType<ST>,
@@ -157,7 +214,7 @@ impl<'a, T: ?Sized, ST, DB> FromSql<ST, DB> for Cow<'a, T> where
     }
 }
 
-impl <'a, T: ?Sized, ST, DB> ::types::FromSqlRow<ST, DB> for Cow<'a, T> where
+impl <'a, T: ?Sized, ST, DB> FromSqlRow<ST, DB> for Cow<'a, T> where
     T: 'a + ToOwned,
     DB: Backend + HasSqlType<ST>,
     Cow<'a, T>: FromSql<ST, DB>,
@@ -167,6 +224,83 @@ impl <'a, T: ?Sized, ST, DB> ::types::FromSqlRow<ST, DB> for Cow<'a, T> where
     }
 }
 
+impl<'a, T: ?Sized, ST, DB> Queryable<ST, DB> for Cow<'a, T> where
+    T: 'a + ToOwned,
+    DB: Backend + HasSqlType<ST>,
+    Cow<'a, T>: FromSqlRow<ST, DB>,
+{
+    type Row = Self;
+
+    fn build(row: Self) -> Self {
+        row
+    }
+}
+
+macro_rules! smart_pointer_impls {
+    ($($Pointer:ident),+) => {$(
+        impl<T: ?Sized, ST, DB> ToSql<ST, DB> for $Pointer<T> where
+            DB: Backend + HasSqlType<ST>,
+            for<'a> &'a T: ToSql<ST, DB>,
+        {
+            fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+                let borrowed: &T = &**self;
+                ToSql::<ST, DB>::to_sql(&borrowed, out)
+            }
+
+            fn size_hint(&self) -> usize {
+                let borrowed: &T = &**self;
+                ToSql::<ST, DB>::size_hint(&borrowed)
+            }
+        }
+    )+}
+}
+
+use std::rc::Rc;
+use std::sync::Arc;
+smart_pointer_impls!(Box, Rc, Arc);
+
+// `Box<T>` can't implement `AsExpression` generically, as it would overlap with
+// the impl for every `Expression`.
+impl<T: ?Sized, ST> AsExpression<ST> for Rc<T> where
+    for<'a> &'a T: AsExpression<ST>,
+{
+    type Expression = Bound<ST, Self>;
+
+    fn as_expression(self) -> Self::Expression {
+        Bound::new(self)
+    }
+}
+
+impl<'a, T: ?Sized, ST> AsExpression<ST> for &'a Rc<T> where
+    for<'b> &'b T: AsExpression<ST>,
+{
+    type Expression = Bound<ST, Self>;
+
+    fn as_expression(self) -> Self::Expression {
+        Bound::new(self)
+    }
+}
+
+impl<T: ?Sized, ST> AsExpression<ST> for Arc<T> where
+    for<'a> &'a T: AsExpression<ST>,
+{
+    type Expression = Bound<ST, Self>;
+
+    fn as_expression(self) -> Self::Expression {
+        Bound::new(self)
+    }
+}
+
+impl<'a, T: ?Sized, ST> AsExpression<ST> for &'a Arc<T> where
+    for<'b> &'b T: AsExpression<ST>,
+{
+    type Expression = Bound<ST, Self>;
+
+    fn as_expression(self) -> Self::Expression {
+        Bound::new(self)
+    }
+}
+
 #[test]
 fn bool_to_sql() {
     let mut bytes = vec![];
@@ -175,8 +309,68 @@ fn bool_to_sql() {
     assert_eq!(bytes, vec![1u8, 0u8]);
 }
 
+#[test]
+fn bool_round_trips_on_backends_which_store_integers() {
+    use backend::Debug;
+
+    for &value in &[true, false] {
+        let mut bytes = vec![];
+        ToSql::<types::Bool, Debug>::to_sql(&value, &mut bytes).unwrap();
+        assert_eq!(Ok(value), <bool as FromSql<types::Bool, Debug>>::from_sql(Some(&bytes))
+            .map_err(|e| e.to_string()));
+    }
+    assert!(<bool as FromSql<types::Bool, Debug>>::from_sql(Some(&[0, 0, 0, 2])).is_err());
+}
+
 #[test]
 fn bool_from_sql_treats_null_as_false() {
     let result = <bool as FromSql<types::Bool, Pg>>::from_sql(None).unwrap();
     assert!(!result);
 }
+
+#[test]
+fn bool_from_sql_accepts_integers_of_any_width() {
+    let from_sql = <bool as FromSql<types::Bool, Pg>>::from_sql;
+    assert_eq!(true, from_sql(Some(&[1])).unwrap());
+    assert_eq!(false, from_sql(Some(&[0, 0, 0, 0])).unwrap());
+    assert_eq!(true, from_sql(Some(&[0, 0, 0, 0, 0, 0, 0, 1])).unwrap());
+}
+
+#[test]
+fn bool_from_sql_rejects_values_other_than_0_and_1() {
+    let from_sql = <bool as FromSql<types::Bool, Pg>>::from_sql;
+    let error = from_sql(Some(&[2])).unwrap_err();
+    assert_eq!("Invalid value for a boolean: [2]. Expected 0 or 1", error.to_string());
+    assert!(from_sql(Some(&[0, 1, 0, 0])).is_err());
+    assert!(from_sql(Some(&[])).is_err());
+}
+
+#[test]
+fn cow_str_to_sql() {
+    let mut bytes = vec![];
+    let borrowed: Cow<str> = Cow::Borrowed("Sean");
+    let owned: Cow<str> = Cow::Owned("Tess".to_string());
+    ToSql::<types::VarChar, Pg>::to_sql(&borrowed, &mut bytes).unwrap();
+    ToSql::<types::Text, Pg>::to_sql(&owned, &mut bytes).unwrap();
+    assert_eq!(bytes, b"SeanTess".to_vec());
+}
+
+#[test]
+fn cow_bytes_to_sql() {
+    let mut bytes = vec![];
+    let borrowed: Cow<[u8]> = Cow::Borrowed(&[1, 2]);
+    let owned: Cow<[u8]> = Cow::Owned(vec![3]);
+    ToSql::<types::Binary, Pg>::to_sql(&borrowed, &mut bytes).unwrap();
+    ToSql::<types::Nullable<types::Binary>, Pg>::to_sql(&owned, &mut bytes).unwrap();
+    assert_eq!(bytes, vec![1u8, 2, 3]);
+}
+
+#[test]
+fn smart_pointers_to_sql() {
+    let mut bytes = vec![];
+    let boxed: Box<str> = "Sean".to_string().into_boxed_str();
+    ToSql::<types::VarChar, Pg>::to_sql(&boxed, &mut bytes).unwrap();
+    ToSql::<types::Text, Pg>::to_sql(&Rc::new("Tess".to_string()), &mut bytes).unwrap();
+    ToSql::<types::Integer, Pg>::to_sql(&Arc::new(1), &mut bytes).unwrap();
+    assert_eq!(bytes, b"SeanTess\0\0\0\x01".to_vec());
+}
@@ -0,0 +1,265 @@
+//! PostgreSQL sends a composite value as the number of fields, followed by
+//! each field. Each field is sent as the OID of its type, its length in bytes
+//! (or -1 for `NULL`), and then its bytes.
+//!
+//! The OID of the composite type itself is not known until runtime, so it is
+//! sent with an OID of 0, which lets PostgreSQL infer the type from the query.
+//! For the same reason, an anonymous `record` can be loaded, but can only be
+//! sent where PostgreSQL can tell which composite type it should be, such as
+//! when comparing it to a column.
+extern crate byteorder;
+
+use self::byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+use std::error::Error;
+use std::io::Write;
+
+use backend::{Debug, Pg, PgTypeMetadata};
+use expression::{AsExpression, SelectableExpression};
+use expression::bound::Bound;
+use expression::nullable::SelectableAsNullable;
+use query_source::Queryable;
+use result::DeserializeFieldError;
+use row::Row;
+use super::option::UnexpectedNullError;
+use types::{FromSql, FromSqlRow, HasSqlType, IsNull, NotNull, Nullable, Record, ToSql};
+
+// FIXME(https://github.com/rust-lang/rust/issues/19630) Remove this work-around
+macro_rules! e {
+    ($e:expr) => { $e }
+}
+
+fn read_field<T, ST>(bytes: &mut &[u8], index: usize) -> Result<T, Box<Error>> where
+    T: FromSql<ST, Pg>,
+{
+    let result = read_field_bytes(bytes).and_then(T::from_sql);
+    result.map_err(|e| Box::new(DeserializeFieldError::new(index, e)) as Box<Error>)
+}
+
+fn read_field_bytes<'a>(bytes: &mut &'a [u8]) -> Result<Option<&'a [u8]>, Box<Error>> {
+    let _oid = try!(bytes.read_u32::<BigEndian>());
+    let length = try!(bytes.read_i32::<BigEndian>());
+    if length == -1 {
+        return Ok(None);
+    }
+    if length < 0 || length as usize > bytes.len() {
+        let error_message = format!("Invalid length for a field of a composite value: {}", length);
+        return Err(Box::<Error + Send + Sync>::from(error_message));
+    }
+    let (field, rest) = bytes.split_at(length as usize);
+    *bytes = rest;
+    Ok(Some(field))
+}
+
+fn write_field<T, ST, W>(field: &T, buffer: &mut Vec<u8>, out: &mut W) -> Result<(), Box<Error>> where
+    T: ToSql<ST, Pg>,
+    Pg: HasSqlType<ST>,
+    W: Write,
+{
+    let oid = <Pg as HasSqlType<ST>>::metadata().oid;
+    if oid == 0 {
+        return Err(Box::<Error + Send + Sync>::from(
+            "Composite values containing types whose OID is not known cannot be sent as bind parameters"));
+    }
+    try!(out.write_u32::<BigEndian>(oid));
+    buffer.clear();
+    match try!(field.to_sql(buffer)) {
+        IsNull::Yes => try!(out.write_i32::<BigEndian>(-1)),
+        IsNull::No => {
+            try!(out.write_i32::<BigEndian>(buffer.len() as i32));
+            try!(out.write_all(buffer));
+        }
+    }
+    Ok(())
+}
+
+macro_rules! record_impls {
+    ($(
+        $Tuple:tt {
+            $(($idx:tt) -> $T:ident, $ST:ident,)+
+        }
+    )+) => {
+        $(
+            impl<$($ST),+> HasSqlType<Record<($($ST,)+)>> for Pg {
+                fn metadata() -> PgTypeMetadata {
+                    PgTypeMetadata {
+                        oid: 0,
+                        array_oid: 0,
+                    }
+                }
+            }
+
+            impl<$($ST),+> HasSqlType<Record<($($ST,)+)>> for Debug {
+                fn metadata() {}
+            }
+
+            impl<$($ST),+> NotNull for Record<($($ST,)+)> {
+            }
+
+            impl<$($ST),+, Expr, QS> SelectableAsNullable<Expr, QS> for Record<($($ST,)+)> where
+                Expr: SelectableExpression<QS>,
+            {
+            }
+
+            impl<$($T),+, $($ST),+> FromSql<Record<($($ST,)+)>, Pg> for ($($T,)+) where
+                $($T: FromSql<$ST, Pg>),+,
+            {
+                fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+                    let mut bytes = not_none!(bytes);
+                    let num_fields = try!(bytes.read_i32::<BigEndian>());
+                    if num_fields != $Tuple {
+                        let error_message = format!("Expected a composite value with {} fields, \
+                                                     but it had {}", $Tuple, num_fields);
+                        return Err(Box::<Error + Send + Sync>::from(error_message));
+                    }
+                    Ok(($(try!(read_field::<$T, $ST>(&mut bytes, e!($idx))),)+))
+                }
+            }
+
+            impl<$($T),+, $($ST),+> ToSql<Record<($($ST,)+)>, Pg> for ($($T,)+) where
+                $($T: ToSql<$ST, Pg>),+,
+                $(Pg: HasSqlType<$ST>),+,
+            {
+                fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+                    try!(out.write_i32::<BigEndian>($Tuple));
+                    let mut buffer = Vec::new();
+                    $(try!(write_field::<$T, $ST, W>(&e!(self.$idx), &mut buffer, out));)+
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl<$($T),+, $($ST),+> AsExpression<Record<($($ST,)+)>> for ($($T,)+) {
+                type Expression = Bound<Record<($($ST,)+)>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+
+            impl<$($T),+, $($ST),+> FromSqlRow<Record<($($ST,)+)>, Pg> for ($($T,)+) where
+                ($($T,)+): FromSql<Record<($($ST,)+)>, Pg>,
+            {
+                fn build_from_row<R: Row>(row: &mut R) -> Result<Self, Box<Error>> {
+                    FromSql::<Record<($($ST,)+)>, Pg>::from_sql(row.take())
+                }
+            }
+
+            impl<$($T),+, $($ST),+> FromSqlRow<Nullable<Record<($($ST,)+)>>, Pg> for Option<($($T,)+)> where
+                ($($T,)+): FromSql<Record<($($ST,)+)>, Pg>,
+            {
+                fn build_from_row<R: Row>(row: &mut R) -> Result<Self, Box<Error>> {
+                    FromSql::<Nullable<Record<($($ST,)+)>>, Pg>::from_sql(row.take())
+                }
+            }
+
+            impl<$($T),+, $($ST),+> Queryable<Record<($($ST,)+)>, Pg> for ($($T,)+) where
+                ($($T,)+): FromSqlRow<Record<($($ST,)+)>, Pg>,
+            {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> Self {
+                    row
+                }
+            }
+        )+
+    }
+}
+
+record_impls! {
+    1 {
+        (0) -> A, SA,
+    }
+    2 {
+        (0) -> A, SA,
+        (1) -> B, SB,
+    }
+    3 {
+        (0) -> A, SA,
+        (1) -> B, SB,
+        (2) -> C, SC,
+    }
+    4 {
+        (0) -> A, SA,
+        (1) -> B, SB,
+        (2) -> C, SC,
+        (3) -> D, SD,
+    }
+    5 {
+        (0) -> A, SA,
+        (1) -> B, SB,
+        (2) -> C, SC,
+        (3) -> D, SD,
+        (4) -> E, SE,
+    }
+    6 {
+        (0) -> A, SA,
+        (1) -> B, SB,
+        (2) -> C, SC,
+        (3) -> D, SD,
+        (4) -> E, SE,
+        (5) -> F, SF,
+    }
+    7 {
+        (0) -> A, SA,
+        (1) -> B, SB,
+        (2) -> C, SC,
+        (3) -> D, SD,
+        (4) -> E, SE,
+        (5) -> F, SF,
+        (6) -> G, SG,
+    }
+    8 {
+        (0) -> A, SA,
+        (1) -> B, SB,
+        (2) -> C, SC,
+        (3) -> D, SD,
+        (4) -> E, SE,
+        (5) -> F, SF,
+        (6) -> G, SG,
+        (7) -> H, SH,
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use backend::Pg;
+    use expression::dsl::sql;
+    use prelude::*;
+    use test_helpers::connection;
+    use types::{FromSql, Integer, Nullable, Record, Text, ToSql};
+
+    #[test]
+    fn records_are_sent_with_the_oid_of_each_field() {
+        let mut bytes = Vec::new();
+        let record = (1, None::<String>);
+        ToSql::<Record<(Integer, Nullable<Text>)>, Pg>::to_sql(&record, &mut bytes).unwrap();
+        let expected = vec![0, 0, 0, 2, 0, 0, 0, 23, 0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 25, 255, 255, 255, 255];
+        assert_eq!(expected, bytes);
+
+        let loaded = <(i32, Option<String>) as FromSql<Record<(Integer, Nullable<Text>)>, Pg>>::from_sql(Some(&bytes));
+        assert_eq!(record, loaded.unwrap());
+    }
+
+    #[test]
+    fn records_with_the_wrong_number_of_fields_are_an_error() {
+        let bytes = [0, 0, 0, 1, 0, 0, 0, 23, 0, 0, 0, 4, 0, 0, 0, 1];
+        let loaded = <(i32, i32) as FromSql<Record<(Integer, Integer)>, Pg>>::from_sql(Some(&bytes));
+        assert!(loaded.is_err());
+    }
+
+    #[test]
+    fn records_can_be_loaded_from_postgres() {
+        let connection = connection();
+        let query = select(sql::<Record<(Integer, Text)>>("ROW(1, 'hello')"));
+        assert_eq!(Ok((1, "hello".to_string())), query.get_result(&connection));
+        let query = select(sql::<Nullable<Record<(Integer, Text)>>>("NULL::record"));
+        assert_eq!(Ok(None::<(i32, String)>), query.get_result(&connection));
+    }
+
+    #[test]
+    fn records_can_be_compared_to_composite_types() {
+        let connection = connection();
+        connection.execute("CREATE TEMPORARY TABLE points (x INTEGER, y INTEGER)").unwrap();
+        let query = select(sql::<Record<(Integer, Integer)>>("ROW(1, 2)::points").eq((1, 2)));
+        assert_eq!(Ok(true), query.get_result(&connection));
+    }
+}
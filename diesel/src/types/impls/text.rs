@@ -0,0 +1,137 @@
+use std::error::Error;
+use std::fmt;
+use std::str;
+
+use backend::Backend;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql};
+
+/// Returned when a `Text` or `VarChar` value loaded as a `String` is not valid
+/// UTF-8. To load such values anyway, use
+/// [`LossyString`](../data_types/struct.LossyString.html) to replace the
+/// invalid bytes, or `Vec<u8>` to get the raw bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidUtf8Error {
+    /// The index of the first byte which is not valid UTF-8
+    pub valid_up_to: usize,
+    /// The first bytes of the invalid sequence, up to 4 bytes
+    pub invalid_bytes: Vec<u8>,
+}
+
+impl InvalidUtf8Error {
+    fn new(bytes: &[u8], valid_up_to: usize) -> Self {
+        let invalid_bytes = bytes[valid_up_to..].iter().take(4).cloned().collect();
+        InvalidUtf8Error {
+            valid_up_to: valid_up_to,
+            invalid_bytes: invalid_bytes,
+        }
+    }
+}
+
+impl fmt::Display for InvalidUtf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Text value was not valid UTF-8. Found invalid bytes {:?} at byte offset {}",
+               self.invalid_bytes, self.valid_up_to)
+    }
+}
+
+impl Error for InvalidUtf8Error {
+    fn description(&self) -> &str {
+        "Text value was not valid UTF-8"
+    }
+}
+
+pub fn string_from_utf8(bytes: &[u8]) -> Result<String, Box<Error>> {
+    match str::from_utf8(bytes) {
+        Ok(string) => Ok(string.to_string()),
+        Err(e) => Err(Box::new(InvalidUtf8Error::new(bytes, e.valid_up_to()))),
+    }
+}
+
+/// A `String` which is loaded from a `Text` or `VarChar` column, replacing any
+/// bytes which are not valid UTF-8 with `U+FFFD REPLACEMENT CHARACTER`, instead
+/// of returning an error.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LossyString(pub String);
+
+impl LossyString {
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl ::std::ops::Deref for LossyString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<DB: Backend> FromSql<types::VarChar, DB> for LossyString {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let bytes = not_none!(bytes);
+        Ok(LossyString(String::from_utf8_lossy(bytes).into_owned()))
+    }
+}
+
+impl<DB: Backend> FromSql<types::Text, DB> for LossyString {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        FromSql::<types::VarChar, DB>::from_sql(bytes)
+    }
+}
+
+/// Loads the raw bytes of a `Text` or `VarChar` value, without checking that
+/// they are valid UTF-8.
+impl<DB: Backend> FromSql<types::VarChar, DB> for Vec<u8> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        Ok(not_none!(bytes).into())
+    }
+}
+
+impl<DB: Backend> FromSql<types::Text, DB> for Vec<u8> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        Ok(not_none!(bytes).into())
+    }
+}
+
+queryable_impls! {
+    VarChar -> LossyString,
+    Text -> LossyString,
+    VarChar -> Vec<u8>,
+    Text -> Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use backend::Pg;
+    use types::{FromSql, Text, VarChar};
+    use super::{InvalidUtf8Error, LossyString};
+
+    const INVALID: &'static [u8] = b"Sean \xF0\x90\x80 Griffin";
+
+    #[test]
+    fn invalid_utf8_errors_include_the_offset() {
+        let error = <String as FromSql<Text, Pg>>::from_sql(Some(INVALID)).unwrap_err();
+        assert_eq!("Text value was not valid UTF-8. Found invalid bytes [240, 144, 128, 32] \
+                    at byte offset 5", error.to_string());
+        let error = InvalidUtf8Error { valid_up_to: 5, invalid_bytes: vec![240, 144, 128, 32] };
+        assert_eq!(error.to_string(),
+                   <String as FromSql<VarChar, Pg>>::from_sql(Some(INVALID)).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn lossy_strings_replace_invalid_bytes() {
+        let string = <LossyString as FromSql<Text, Pg>>::from_sql(Some(INVALID)).unwrap();
+        assert_eq!("Sean \u{FFFD} Griffin", &*string);
+        let string = <LossyString as FromSql<VarChar, Pg>>::from_sql(Some(&b"Tess"[..])).unwrap();
+        assert_eq!(LossyString("Tess".to_string()), string);
+    }
+
+    #[test]
+    fn text_can_be_loaded_as_raw_bytes() {
+        let bytes = <Vec<u8> as FromSql<Text, Pg>>::from_sql(Some(INVALID)).unwrap();
+        assert_eq!(INVALID.to_vec(), bytes);
+    }
+}
@@ -0,0 +1,40 @@
+//! `tsvector` and `tsquery` values aren't mapped to any Rust type. They are
+//! meant to be built and compared in SQL, with functions such as
+//! [`to_tsvector`](../../expression/dsl/fn.to_tsvector.html) and the `matches`
+//! method.
+use backend::{Debug, Pg, PgTypeMetadata};
+use expression::SelectableExpression;
+use expression::nullable::SelectableAsNullable;
+use types::{self, HasSqlType, NotNull};
+
+macro_rules! unmapped_type_impls {
+    ($($Source:ident -> ($oid:expr, $array_oid:expr)),+,) => {$(
+        impl HasSqlType<types::$Source> for Pg {
+            fn metadata() -> PgTypeMetadata {
+                PgTypeMetadata {
+                    oid: $oid,
+                    array_oid: $array_oid,
+                }
+            }
+        }
+
+        impl HasSqlType<types::$Source> for Debug {
+            fn metadata() {
+                ()
+            }
+        }
+
+        impl NotNull for types::$Source {
+        }
+
+        impl<Expr, QS> SelectableAsNullable<Expr, QS> for types::$Source where
+            Expr: SelectableExpression<QS>,
+        {
+        }
+    )+}
+}
+
+unmapped_type_impls! {
+    TsVector -> (3614, 3643),
+    TsQuery -> (3615, 3645),
+}
@@ -1,8 +1,10 @@
 use backend::Backend;
 use expression::{Expression, SelectableExpression, NonAggregate};
+use expression::nullable::SelectableAsNullable;
 use persistable::InsertableColumns;
 use query_builder::{Changeset, AsChangeset, QueryBuilder, BuildQueryResult, QueryFragment};
 use query_source::{QuerySource, Queryable, Table, Column};
+use result::DeserializeFieldError;
 use row::Row;
 use std::error::Error;
 use types::{HasSqlType, FromSqlRow, ToSql, Nullable, IntoNullable, NotNull};
@@ -32,6 +34,11 @@ macro_rules! tuple_impls {
             impl<$($T),+> NotNull for ($($T,)+) {
             }
 
+            impl<$($T),+, Expr, QS> SelectableAsNullable<Expr, QS> for ($($T,)+) where
+                Expr: SelectableExpression<QS, Nullable<($($T,)+)>>,
+            {
+            }
+
             impl<$($T),+, $($ST),+, DB> FromSqlRow<($($ST,)+), DB> for ($($T,)+) where
                 DB: Backend,
                 $($T: FromSqlRow<$ST, DB>),+,
@@ -39,7 +46,7 @@ macro_rules! tuple_impls {
                 DB: HasSqlType<($($ST,)+)>,
             {
                 fn build_from_row<RowT: Row>(row: &mut RowT) -> Result<Self, Box<Error>> {
-                    Ok(($(try!($T::build_from_row(row)),)+))
+                    Ok(($(try!(build_element::<$T, $ST, DB, RowT>(row, e!($idx))),)+))
                 }
             }
 
@@ -51,9 +58,10 @@ macro_rules! tuple_impls {
             {
                 fn build_from_row<RowT: Row>(row: &mut RowT) -> Result<Self, Box<Error>> {
                     if e!(row.next_is_null($Tuple)) {
+                        e!(row.advance($Tuple));
                         Ok(None)
                     } else {
-                        Ok(Some(($(try!($T::build_from_row(row)),)+)))
+                        Ok(Some(($(try!(build_element::<$T, $ST, DB, RowT>(row, e!($idx))),)+)))
                     }
                 }
             }
@@ -69,6 +77,29 @@ macro_rules! tuple_impls {
                 fn build(row: Self::Row) -> Self {
                     ($($T::build(e!(row.$idx)),)+)
                 }
+
+                fn describe_error(error: Box<Error>) -> Box<Error> {
+                    let error = match error.downcast::<DeserializeFieldError>() {
+                        Ok(error) => error,
+                        Err(error) => return error,
+                    };
+                    if error.field_name.is_some() {
+                        return error;
+                    }
+                    let DeserializeFieldError { index, column_name, error: cause, .. } = *error;
+                    $(
+                        if index == e!($idx) {
+                            let described = <$T as Queryable<$ST, DB>>::describe_error(cause);
+                            return if described.is::<DeserializeFieldError>() {
+                                described
+                            } else {
+                                Box::new(DeserializeFieldError::new(index, described)
+                                    .with_column_name(column_name))
+                            };
+                        }
+                    )+
+                    Box::new(DeserializeFieldError::new(index, cause).with_column_name(column_name))
+                }
             }
 
             impl<$($T: Expression + NonAggregate),+> Expression for ($($T,)+) {
@@ -157,6 +188,20 @@ macro_rules! tuple_impls {
     }
 }
 
+fn build_element<T, ST, DB, R>(row: &mut R, index: usize) -> Result<T, Box<Error>> where
+    T: FromSqlRow<ST, DB>,
+    DB: Backend + HasSqlType<ST>,
+    R: Row,
+{
+    let column_index = row.column_index();
+    T::build_from_row(row).map_err(|e| {
+        let column_name = column_index.and_then(|idx| row.column_name_at(idx))
+            .map(|name| name.to_string());
+        let error = DeserializeFieldError::new(index, e).with_column_name(column_name);
+        Box::new(error) as Box<Error>
+    })
+}
+
 tuple_impls! {
     1 {
         (0) -> A, SA, TA,
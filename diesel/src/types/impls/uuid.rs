@@ -0,0 +1,80 @@
+//! This module makes it possible to map `uuid::Uuid` values to postgres `uuid`
+//! fields. It is enabled with the `uuid` feature. PostgreSQL sends a UUID as its
+//! 16 bytes, in the same order they appear in its text representation.
+extern crate uuid;
+
+use std::error::Error;
+use std::io::Write;
+use self::uuid::Uuid;
+
+use backend::Pg;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, IsNull, ToSql};
+
+primitive_impls! {
+    Uuid -> (Uuid, 2950, 2951),
+}
+
+impl FromSql<types::Uuid, Pg> for Uuid {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let bytes = not_none!(bytes);
+        Uuid::from_slice(bytes).map_err(|_| {
+            let error_message = format!("Expected a UUID to be 16 bytes, got {}", bytes.len());
+            Box::<Error + Send + Sync>::from(error_message) as Box<Error>
+        })
+    }
+}
+
+impl ToSql<types::Uuid, Pg> for Uuid {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        out.write_all(self.as_bytes())
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+
+    fn size_hint(&self) -> usize {
+        16
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    extern crate uuid;
+
+    use self::uuid::Uuid;
+
+    use backend::Pg;
+    use expression::dsl::sql;
+    use prelude::*;
+    use test_helpers::connection;
+    use types::{self, FromSql, ToSql};
+
+    #[test]
+    fn uuids_are_sent_as_their_16_bytes() {
+        let uuid = Uuid::parse_str("936da01f-9abd-4d9d-80c7-02af85c822a8").unwrap();
+        let mut bytes = Vec::new();
+        ToSql::<types::Uuid, Pg>::to_sql(&uuid, &mut bytes).unwrap();
+        assert_eq!(uuid.as_bytes().to_vec(), bytes);
+        assert_eq!(Ok(uuid), <Uuid as FromSql<types::Uuid, Pg>>::from_sql(Some(&bytes))
+            .map_err(|e| e.to_string()));
+    }
+
+    #[test]
+    fn uuids_of_the_wrong_length_are_an_error() {
+        let error = <Uuid as FromSql<types::Uuid, Pg>>::from_sql(Some(&[1, 2, 3])).unwrap_err();
+        assert_eq!("Expected a UUID to be 16 bytes, got 3", error.to_string());
+    }
+
+    #[test]
+    fn uuids_round_trip_through_postgres() {
+        let connection = connection();
+        let uuid = Uuid::parse_str("936da01f-9abd-4d9d-80c7-02af85c822a8").unwrap();
+        let query = select(sql::<types::Uuid>("'936da01f-9abd-4d9d-80c7-02af85c822a8'::uuid"));
+        assert_eq!(Ok(uuid), query.get_result::<Uuid>(&connection));
+        let query = select(sql::<types::Uuid>("'936da01f-9abd-4d9d-80c7-02af85c822a8'::uuid").eq(uuid));
+        assert!(query.get_result::<bool>(&connection).unwrap());
+    }
+}
@@ -0,0 +1,53 @@
+//! PostgreSQL sends `xml` values as text. Sending a malformed document is an
+//! error on the server.
+use std::error::Error;
+use std::io::Write;
+
+use backend::Backend;
+use expression::AsExpression;
+use expression::bound::Bound;
+use query_source::Queryable;
+use types::{self, FromSql, IsNull, ToSql};
+
+primitive_impls! {
+    Xml -> (String, 142, 143),
+}
+
+expression_impls! {
+    Xml -> &'a str,
+}
+
+impl<DB> FromSql<types::Xml, DB> for String where
+    DB: Backend,
+    String: FromSql<types::VarChar, DB>,
+{
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        <Self as FromSql<types::VarChar, DB>>::from_sql(bytes)
+    }
+}
+
+impl<DB> ToSql<types::Xml, DB> for String where
+    DB: Backend,
+    for<'a> &'a str: ToSql<types::Xml, DB>,
+{
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        (&self as &str).to_sql(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<'a, DB> ToSql<types::Xml, DB> for &'a str where
+    DB: Backend,
+    &'a str: ToSql<types::VarChar, DB>,
+{
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        ToSql::<types::VarChar, DB>::to_sql(self, out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
@@ -10,13 +10,22 @@ pub mod structs {
         //! Structs to represent the primitive equivalent of SQL types where
         //! there is no existing Rust primitive, or where using it would be
         //! confusing (such as date and time types)
-        pub use super::super::impls::date_and_time::{PgTimestamp, PgDate, PgTime, PgInterval};
-        pub use super::super::impls::floats::PgNumeric;
+        pub use super::super::impls::date_and_time::{PgTimestamp, PgDate, PgTime, PgInterval, MaybeInfinite};
+        #[cfg(feature = "chrono")]
+        pub use super::super::impls::date_and_time::LocalNaiveDateTime;
+        pub use super::super::impls::floats::{LossyNumeric, PgNumeric};
+        pub use super::super::impls::geometric::{PgBox, PgCircle, PgPath, PgPoint, PgPolygon};
+        pub use super::super::impls::ltree::PgLtree;
+        pub use super::super::impls::money::Cents;
+        pub use super::super::impls::network::PgNetwork;
+        pub use super::super::impls::text::{InvalidUtf8Error, LossyString};
     }
 }
 
 /// Marker trait for types which can be compared for ordering.
 pub use self::ord::SqlOrd;
+pub use self::impls::geometric::Geometric;
+pub use self::impls::bytea::bytea_from_text;
 
 use backend::{Backend, TypeMetadata};
 use row::Row;
@@ -29,12 +38,23 @@ pub type SmallSerial = SmallInt;
 pub type Serial = Integer;
 pub type BigSerial = BigInt;
 
+/// A single byte integer. On PostgreSQL this is the internal `"char"` type.
+#[derive(Clone, Copy, Default)] pub struct TinyInt;
 #[derive(Clone, Copy, Default)] pub struct SmallInt;
 #[doc(hidden)] pub type Int2 = SmallInt;
 #[derive(Clone, Copy, Default)] pub struct Integer;
 #[doc(hidden)] pub type Int4 = Integer;
 #[derive(Clone, Copy, Default)] pub struct BigInt;
 #[doc(hidden)] pub type Int8 = BigInt;
+/// An unsigned integer, such as `Unsigned<Integer>`. `Unsigned<TinyInt>`,
+/// `Unsigned<SmallInt>`, `Unsigned<Integer>` and `Unsigned<BigInt>` can be
+/// mapped to `u8`, `u16`, `u32` and `u64`. PostgreSQL doesn't have unsigned integers, so these can only be used
+/// with backends which do.
+#[derive(Clone, Copy, Default)] pub struct Unsigned<ST>(ST);
+#[doc(hidden)] pub type UnsignedTinyInt = Unsigned<TinyInt>;
+#[doc(hidden)] pub type UnsignedSmallInt = Unsigned<SmallInt>;
+#[doc(hidden)] pub type UnsignedInteger = Unsigned<Integer>;
+#[doc(hidden)] pub type UnsignedBigInt = Unsigned<BigInt>;
 
 #[derive(Clone, Copy, Default)] pub struct Float;
 #[doc(hidden)] pub type Float4 = Float;
@@ -43,20 +63,113 @@ pub type BigSerial = BigInt;
 #[derive(Clone, Copy, Default)] pub struct Numeric;
 
 #[derive(Clone, Copy, Default)] pub struct Oid;
+/// The `oid` of a table, used by PostgreSQL's system catalogs. This can be
+/// mapped to `u32`.
+#[derive(Clone, Copy, Default)] pub struct Regclass;
+/// The `oid` of a type, used by PostgreSQL's system catalogs. This can be
+/// mapped to `u32`.
+#[derive(Clone, Copy, Default)] pub struct Regtype;
+/// A list of `oid`s, used by PostgreSQL's system catalogs. This can be mapped
+/// to `Vec<u32>`.
+#[derive(Clone, Copy, Default)] pub struct OidVector;
 
 #[derive(Clone, Copy, Default)] pub struct VarChar;
 #[doc(hidden)] pub type Varchar = VarChar;
 #[derive(Clone, Copy, Default)] pub struct Text;
+/// A fixed length, blank padded string. This can be mapped to `String`, or to
+/// `char` for a `CHAR(1)` column.
+#[derive(Clone, Copy, Default)] pub struct Char;
+#[doc(hidden)] pub type Bpchar = Char;
+
+/// An XML document or fragment. This can be mapped to `String`.
+#[derive(Clone, Copy, Default)] pub struct Xml;
 
 #[derive(Clone, Copy, Default)] pub struct Binary;
+/// A fixed length string of bits. This can be mapped to `Vec<bool>`.
+#[derive(Clone, Copy, Default)] pub struct Bit;
+/// A variable length string of bits. This can be mapped to `Vec<bool>`.
+#[derive(Clone, Copy, Default)] pub struct Varbit;
+
+/// An IPv4 or IPv6 host address, and optionally its subnet. This can be mapped
+/// to `data_types::PgNetwork`, or to `std::net::IpAddr` for a single address.
+#[derive(Clone, Copy, Default)] pub struct Inet;
+/// An IPv4 or IPv6 network. This can be mapped to `data_types::PgNetwork`.
+#[derive(Clone, Copy, Default)] pub struct Cidr;
+/// A set of string keys, each with an optional string value, provided by the
+/// `hstore` extension. This can be mapped to `HashMap<String, Option<String>>`.
+#[derive(Clone, Copy, Default)] pub struct Hstore;
+/// A path of labels in a tree, such as `Top.Science.Astronomy`, provided by
+/// the `ltree` extension. This can be mapped to `data_types::PgLtree`.
+#[derive(Clone, Copy, Default)] pub struct Ltree;
+/// A pattern which matches `Ltree` paths, such as `*.Science.*`, provided by
+/// the `ltree` extension. This can be mapped to `String`.
+#[derive(Clone, Copy, Default)] pub struct Lquery;
+/// An amount of money in the currency set by the database's `lc_monetary`
+/// setting. This can be mapped to `data_types::Cents`. It can be added to or
+/// subtracted from other `Money`, and multiplied or divided by a `BigInt`.
+#[derive(Clone, Copy, Default)] pub struct Money;
+/// A document prepared for full text search. This can't be mapped to a Rust
+/// type, and is meant to be used with functions such as
+/// [`to_tsvector`](../expression/dsl/fn.to_tsvector.html).
+#[derive(Clone, Copy, Default)] pub struct TsVector;
+/// A full text search query. This can't be mapped to a Rust type, and is meant
+/// to be used with functions such as
+/// [`to_tsquery`](../expression/dsl/fn.to_tsquery.html).
+#[derive(Clone, Copy, Default)] pub struct TsQuery;
+
+/// A point on a plane. This can be mapped to `data_types::PgPoint`.
+#[derive(Clone, Copy, Default)] pub struct Point;
+/// A PostgreSQL `box`. This can be mapped to `data_types::PgBox`. It is named
+/// `Rectangle` so that it doesn't shadow `std::boxed::Box` when this module is
+/// glob imported.
+#[derive(Clone, Copy, Default)] pub struct Rectangle;
+/// A circle. This can be mapped to `data_types::PgCircle`.
+#[derive(Clone, Copy, Default)] pub struct Circle;
+/// A series of connected points. This can be mapped to `data_types::PgPath`.
+#[derive(Clone, Copy, Default)] pub struct Path;
+/// A closed series of connected points. This can be mapped to
+/// `data_types::PgPolygon`.
+#[derive(Clone, Copy, Default)] pub struct Polygon;
 
 #[derive(Clone, Copy, Default)] pub struct Date;
 #[derive(Clone, Copy, Default)] pub struct Interval;
 #[derive(Clone, Copy, Default)] pub struct Time;
 #[derive(Clone, Copy, Default)] pub struct Timestamp;
+/// A `timestamp with time zone`. PostgreSQL always stores these values in UTC,
+/// and sends them to Diesel as UTC regardless of the session's time zone. The
+/// offset a value was sent with is not stored, so a value loaded with an offset,
+/// such as a chrono `DateTime<FixedOffset>`, is the same instant that was sent,
+/// but always has an offset of `+00:00`.
+#[derive(Clone, Copy, Default)] pub struct Timestamptz;
+
+/// A `json` value. With the `serde_json` feature, this can be mapped to
+/// `serde_json::Value`.
+#[cfg(feature = "serde_json")]
+#[derive(Clone, Copy, Default)] pub struct Json;
+/// A `jsonb` value. With the `serde_json` feature, this can be mapped to
+/// `serde_json::Value`.
+#[cfg(feature = "serde_json")]
+#[derive(Clone, Copy, Default)] pub struct Jsonb;
+/// A SQL/JSON path expression, such as `$.tags[*].name`. This can be mapped to
+/// `String`. Requires PostgreSQL 12.
+#[derive(Clone, Copy, Default)] pub struct Jsonpath;
+
+/// A `uuid`. With the `uuid` feature, this can be mapped to `uuid::Uuid`.
+#[cfg(feature = "uuid")]
+#[derive(Clone, Copy, Default)] pub struct Uuid;
+
+/// A PostGIS `geometry`. With the `postgis` feature, this can be mapped to
+/// `postgis::ewkb::Geometry`, or to `postgis::ewkb::Point` for a point.
+#[cfg(feature = "postgis")]
+#[derive(Clone, Copy, Default)] pub struct Geometry;
 
 #[derive(Clone, Copy, Default)] pub struct Nullable<T: NotNull>(T);
 #[derive(Clone, Copy, Default)] pub struct Array<T>(T);
+/// A composite value, such as a row or a value of a type created with `CREATE
+/// TYPE ... AS`. `T` is a tuple of the SQL types of its fields. This can be
+/// mapped to a tuple of the same length, or to a struct which derives
+/// `Queryable`, with up to 8 fields.
+#[derive(Clone, Copy, Default)] pub struct Record<T>(T);
 
 pub trait HasSqlType<ST>: TypeMetadata {
     fn metadata() -> Self::TypeMetadata;
@@ -101,6 +214,14 @@ pub enum IsNull {
 /// text.
 pub trait ToSql<A, DB: Backend + HasSqlType<A>> {
     fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>>;
+
+    /// An estimate of the number of bytes that `to_sql` will write. This is
+    /// used to preallocate the buffer that the value is written to, and does
+    /// not need to be exact. Types whose size is not cheap to calculate should
+    /// use the default of 0.
+    fn size_hint(&self) -> usize {
+        0
+    }
 }
 
 impl<'a, A, T, DB> ToSql<A, DB> for &'a T where
@@ -110,4 +231,8 @@ impl<'a, A, T, DB> ToSql<A, DB> for &'a T where
     fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
         (*self).to_sql(out)
     }
+
+    fn size_hint(&self) -> usize {
+        (*self).size_hint()
+    }
 }
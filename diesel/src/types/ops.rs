@@ -60,6 +60,16 @@ impl Sub for super::Timestamp {
     type Output = super::Timestamp;
 }
 
+impl Add for super::Timestamptz {
+    type Rhs = super::Interval;
+    type Output = super::Timestamptz;
+}
+
+impl Sub for super::Timestamptz {
+    type Rhs = super::Interval;
+    type Output = super::Timestamptz;
+}
+
 impl Add for super::Date {
     type Rhs = super::Interval;
     type Output = super::Timestamp;
@@ -69,3 +79,23 @@ impl Sub for super::Date {
     type Rhs = super::Interval;
     type Output = super::Timestamp;
 }
+
+impl Add for super::Money {
+    type Rhs = super::Money;
+    type Output = super::Money;
+}
+
+impl Sub for super::Money {
+    type Rhs = super::Money;
+    type Output = super::Money;
+}
+
+impl Mul for super::Money {
+    type Rhs = super::BigInt;
+    type Output = super::Money;
+}
+
+impl Div for super::Money {
+    type Rhs = super::BigInt;
+    type Output = super::Money;
+}
@@ -2,6 +2,7 @@ use types::{self, NotNull};
 
 pub trait SqlOrd {}
 
+impl SqlOrd for types::TinyInt {}
 impl SqlOrd for types::SmallInt {}
 impl SqlOrd for types::Integer {}
 impl SqlOrd for types::BigInt {}
@@ -9,8 +10,12 @@ impl SqlOrd for types::Float {}
 impl SqlOrd for types::Double {}
 impl SqlOrd for types::VarChar {}
 impl SqlOrd for types::Text {}
+impl SqlOrd for types::Char {}
 impl SqlOrd for types::Date {}
 impl SqlOrd for types::Interval {}
+impl SqlOrd for types::Ltree {}
+impl SqlOrd for types::Money {}
 impl SqlOrd for types::Time {}
 impl SqlOrd for types::Timestamp {}
+impl SqlOrd for types::Timestamptz {}
 impl<T: SqlOrd + NotNull> SqlOrd for types::Nullable<T> {}
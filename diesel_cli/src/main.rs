@@ -10,7 +10,7 @@ use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 use diesel::{migrations, Connection};
 use diesel::connection::PgConnection;
 use self::setup_error::SetupError;
-use std::{env, fs};
+use std::{env, fs, io};
 use std::error::Error;
 use std::path::{PathBuf, Path};
 
@@ -36,6 +36,15 @@ fn main() {
                 .about("Reverts and re-runs the latest migration. Useful \
                       for testing that a migration can in fact be reverted.")
                 .arg(database_arg())
+        ).subcommand(
+            SubCommand::with_name("script")
+                .about("Writes a SQL script which runs all pending migrations, \
+                      instead of running them")
+                .arg(database_arg())
+                .arg(Arg::with_name("OUTPUT_FILE")
+                     .help("The file to write the script to. Writes to stdout \
+                           if unspecified.")
+                 )
         ).subcommand(
             SubCommand::with_name("generate")
                 .about("Generate a new migration with the given name, and \
@@ -77,27 +86,33 @@ fn run_migration_command(matches: &ArgMatches) {
                 .map_err(handle_error).unwrap();
         }
         ("redo", Some(args)) => {
+            migrations::redo_latest_migration(&connection(&database_url(args)))
+                .map_err(handle_error).unwrap();
+        }
+        ("script", Some(args)) => {
             let connection = connection(&database_url(args));
-            connection.transaction(|| {
-                let reverted_version = try!(migrations::revert_latest_migration(&connection));
-                migrations::run_migration_with_version(&connection, &reverted_version)
-            }).unwrap_or_else(handle_error);
+            match args.value_of("OUTPUT_FILE") {
+                Some(path) => {
+                    let mut file = fs::File::create(path).map_err(handle_error).unwrap();
+                    migrations::write_pending_migrations_script(&connection, &mut file)
+                }
+                None => {
+                    let stdout = io::stdout();
+                    migrations::write_pending_migrations_script(&connection, &mut stdout.lock())
+                }
+            }.map_err(handle_error).unwrap();
         }
         ("generate", Some(args)) => {
             let migration_name = args.value_of("MIGRATION_NAME").unwrap();
-            let timestamp = Local::now().format("%Y%m%d%H%M%S");
-            let versioned_name = format!("{}_{}", &timestamp, migration_name);
-            let migration_dir = migrations::find_migrations_directory()
-                .map_err(handle_error).unwrap().join(versioned_name);
-            fs::create_dir(&migration_dir).unwrap();
+            let timestamp = Local::now().format("%Y%m%d%H%M%S").to_string();
+            let migrations_dir = migrations::find_migrations_directory()
+                .map_err(handle_error).unwrap();
+            let migration_dir = migrations::generate_migration_with_version(
+                &migrations_dir, &timestamp, migration_name).map_err(handle_error).unwrap();
 
             // FIXME: It would be nice to print these as relative paths
-            let up_path = migration_dir.join("up.sql");
-            println!("Creating {}", up_path.display());
-            fs::File::create(up_path).unwrap();
-            let down_path = migration_dir.join("down.sql");
-            println!("Creating {}", down_path.display());
-            fs::File::create(down_path).unwrap();
+            println!("Creating {}", migration_dir.join("up.sql").display());
+            println!("Creating {}", migration_dir.join("down.sql").display());
         }
         _ => unreachable!("The cli parser should prevent reaching here"),
     }
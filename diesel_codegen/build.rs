@@ -32,6 +32,8 @@ mod inner {
         register_quote_macro!(quote_item, expand_quote_item);
         register_quote_macro!(quote_tokens, expand_quote_tokens);
         register_quote_macro!(quote_expr, expand_quote_expr);
+        register_quote_macro!(quote_arm, expand_quote_arm);
+        register_quote_macro!(quote_stmt, expand_quote_stmt);
 
         let src = Path::new("src/lib.in.rs");
         let dst = Path::new(&out_dir).join("lib.rs");
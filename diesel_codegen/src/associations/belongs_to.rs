@@ -161,20 +161,20 @@ fn selectable_column_impl(
     let column = builder.column_path(column_name);
 
     [quote_item!(builder.cx,
-        impl ::diesel::expression::SelectableExpression<
-            ::diesel::query_source::InnerJoinSource<$parent_table, $child_table>
+        impl<On> ::diesel::expression::SelectableExpression<
+            ::diesel::query_source::InnerJoinSource<$parent_table, $child_table, On>
         > for $column {}
     ).unwrap(), quote_item!(builder.cx,
-        impl ::diesel::expression::SelectableExpression<
-            ::diesel::query_source::InnerJoinSource<$child_table, $parent_table>
+        impl<On> ::diesel::expression::SelectableExpression<
+            ::diesel::query_source::InnerJoinSource<$child_table, $parent_table, On>
         > for $column {}
     ).unwrap(), quote_item!(builder.cx,
-        impl ::diesel::expression::SelectableExpression<
-            ::diesel::query_source::LeftOuterJoinSource<$child_table, $parent_table>,
+        impl<On> ::diesel::expression::SelectableExpression<
+            ::diesel::query_source::LeftOuterJoinSource<$child_table, $parent_table, On>,
         > for $column {}
     ).unwrap(), quote_item!(builder.cx,
-        impl ::diesel::expression::SelectableExpression<
-            ::diesel::query_source::LeftOuterJoinSource<$parent_table, $child_table>,
+        impl<On> ::diesel::expression::SelectableExpression<
+            ::diesel::query_source::LeftOuterJoinSource<$parent_table, $child_table, On>,
             <<$column as ::diesel::Expression>::SqlType
                 as ::diesel::types::IntoNullable>::Nullable,
         > for $column {}
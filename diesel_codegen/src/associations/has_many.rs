@@ -97,20 +97,20 @@ fn selectable_column_impl(
     let column = builder.column_path(column_name);
 
     [quote_item!(builder.cx,
-        impl ::diesel::expression::SelectableExpression<
-            ::diesel::query_source::InnerJoinSource<$table, $foreign_table>
+        impl<On> ::diesel::expression::SelectableExpression<
+            ::diesel::query_source::InnerJoinSource<$table, $foreign_table, On>
         > for $column {}
     ).unwrap(), quote_item!(builder.cx,
-        impl ::diesel::expression::SelectableExpression<
-            ::diesel::query_source::InnerJoinSource<$foreign_table, $table>
+        impl<On> ::diesel::expression::SelectableExpression<
+            ::diesel::query_source::InnerJoinSource<$foreign_table, $table, On>
         > for $column {}
     ).unwrap(), quote_item!(builder.cx,
-        impl ::diesel::expression::SelectableExpression<
-            ::diesel::query_source::LeftOuterJoinSource<$table, $foreign_table>,
+        impl<On> ::diesel::expression::SelectableExpression<
+            ::diesel::query_source::LeftOuterJoinSource<$table, $foreign_table, On>,
         > for $column {}
     ).unwrap(), quote_item!(builder.cx,
-        impl ::diesel::expression::SelectableExpression<
-            ::diesel::query_source::LeftOuterJoinSource<$foreign_table, $table>,
+        impl<On> ::diesel::expression::SelectableExpression<
+            ::diesel::query_source::LeftOuterJoinSource<$foreign_table, $table, On>,
             <<$column as ::diesel::Expression>::SqlType
                 as ::diesel::types::IntoNullable>::Nullable,
         > for $column {}
@@ -3,7 +3,8 @@ use syntax::codemap::Span;
 use syntax::ext::base::{Annotatable, ExtCtxt};
 use syntax::parse::token::str_to_ident;
 
-use model::{infer_association_name, Model};
+use model::Model;
+use util::snake_case;
 
 mod has_many;
 mod belongs_to;
@@ -65,7 +66,7 @@ fn build_association_options(
 }
 
 fn to_foreign_key(model_name: &str) -> ast::Ident {
-    let lower_cased = infer_association_name(model_name);
+    let lower_cased = snake_case(model_name);
     str_to_ident(&format!("{}_id", &lower_cased))
 }
 
@@ -1,4 +1,5 @@
 use syntax::ast;
+use syntax::attr::AttrMetaMethods;
 use syntax::ext::base::ExtCtxt;
 use syntax::ptr::P;
 
@@ -9,6 +10,7 @@ pub struct Attr {
     pub column_name: ast::Ident,
     pub field_name: Option<ast::Ident>,
     pub ty: P<ast::Ty>,
+    pub embed: bool,
 }
 
 impl Attr {
@@ -17,17 +19,20 @@ impl Attr {
         let column_name =
             str_value_of_attr_with_name(cx, &field.node.attrs, "column_name");
         let ty = field.node.ty.clone();
+        let embed = field.node.attrs.iter().any(|a| a.check_name("embed"));
 
         match (column_name, field_name) {
             (Some(column_name), f) => Some(Attr {
                 column_name: column_name,
                 field_name: f,
                 ty: ty,
+                embed: embed,
             }),
             (None, Some(field_name)) => Some(Attr {
                 column_name: field_name.clone(),
                 field_name: Some(field_name),
                 ty: ty,
+                embed: embed,
             }),
             (None, None) => {
                 cx.span_err(field.span,
@@ -1,7 +1,10 @@
 mod associations;
 mod attr;
 mod insertable;
+mod migrations;
 mod model;
+mod newtype;
 mod queryable;
 mod schema_inference;
+mod text_enum;
 mod update;
@@ -29,12 +29,15 @@ pub fn register(reg: &mut syntex::Registry) {
     reg.add_attr("feature(custom_attribute)");
 
     reg.add_decorator("derive_Queryable", queryable::expand_derive_queryable);
+    reg.add_decorator("derive_DieselNewType", newtype::expand_derive_diesel_new_type);
+    reg.add_decorator("derive_DieselTextEnum", text_enum::expand_derive_diesel_text_enum);
     reg.add_decorator("insertable_into", insertable::expand_insert);
     reg.add_decorator("changeset_for", update::expand_changeset_for);
     reg.add_decorator("has_many", associations::expand_has_many);
     reg.add_decorator("belongs_to", associations::expand_belongs_to);
     reg.add_macro("infer_table_from_schema", schema_inference::expand_load_table);
     reg.add_macro("infer_schema", schema_inference::expand_infer_schema);
+    reg.add_macro("embed_migrations", migrations::expand_embed_migrations);
 
     reg.add_post_expansion_pass(util::strip_attributes);
 }
@@ -48,6 +51,14 @@ pub fn register(reg: &mut rustc_plugin::Registry) {
         intern("derive_Queryable"),
         MultiDecorator(Box::new(queryable::expand_derive_queryable))
     );
+    reg.register_syntax_extension(
+        intern("derive_DieselNewType"),
+        MultiDecorator(Box::new(newtype::expand_derive_diesel_new_type))
+    );
+    reg.register_syntax_extension(
+        intern("derive_DieselTextEnum"),
+        MultiDecorator(Box::new(text_enum::expand_derive_diesel_text_enum))
+    );
     reg.register_syntax_extension(
         intern("insertable_into"),
         MultiDecorator(Box::new(insertable::expand_insert))
@@ -66,4 +77,5 @@ pub fn register(reg: &mut rustc_plugin::Registry) {
     );
     reg.register_macro("infer_table_from_schema", schema_inference::expand_load_table);
     reg.register_macro("infer_schema", schema_inference::expand_infer_schema);
+    reg.register_macro("embed_migrations", migrations::expand_embed_migrations);
 }
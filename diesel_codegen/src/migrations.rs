@@ -0,0 +1,112 @@
+use diesel::migrations::{migration_is_reversible, migration_paths_in_directory, migration_runs_in_transaction};
+use diesel::migrations::search_for_migrations_directory;
+use syntax::ast;
+use syntax::codemap::Span;
+use syntax::ext::base::*;
+use syntax::ext::build::AstBuilder;
+use syntax::parse::token::intern_and_get_ident;
+use syntax::ptr::P;
+use syntax::util::small_vector::SmallVector;
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+pub fn expand_embed_migrations<'cx>(
+    cx: &'cx mut ExtCtxt,
+    sp: Span,
+    tts: &[ast::TokenTree]
+) -> Box<MacResult+'cx> {
+    let migrations_dir = match migrations_directory(cx, sp, tts) {
+        Some(dir) => dir,
+        None => return DummyResult::any(sp),
+    };
+    let migrations = match migration_paths_in_directory(&migrations_dir) {
+        Ok(migrations) => migrations,
+        Err(e) => {
+            cx.span_err(sp, &format!("Error reading migrations from {}: {}",
+                                     migrations_dir.display(), e));
+            return DummyResult::any(sp);
+        }
+    };
+
+    let migration_exprs = migrations.iter().map(|&(ref version, ref path)| {
+        let version = cx.expr_str(sp, intern_and_get_ident(version));
+        let up_path = path_str(cx, sp, &path.join("up.sql"));
+        let run_in_transaction = match migration_runs_in_transaction(path) {
+            Ok(run_in_transaction) => cx.expr_bool(sp, run_in_transaction),
+            Err(e) => {
+                cx.span_err(sp, &format!("Error reading {}: {}", path.display(), e));
+                cx.expr_bool(sp, true)
+            }
+        };
+        // Irreversible migrations might not have a down.sql
+        let down_sql = match migration_is_reversible(path) {
+            Ok(true) => {
+                let down_path = path_str(cx, sp, &path.join("down.sql"));
+                quote_expr!(cx, include_str!($down_path))
+            }
+            Ok(false) => cx.expr_str(sp, intern_and_get_ident("-- IRREVERSIBLE")),
+            Err(e) => {
+                cx.span_err(sp, &format!("Error reading {}: {}", path.display(), e));
+                cx.expr_str(sp, intern_and_get_ident(""))
+            }
+        };
+        quote_expr!(cx, ::diesel::migrations::EmbeddedMigration {
+            version: $version,
+            up_sql: include_str!($up_path),
+            down_sql: $down_sql,
+            run_in_transaction: $run_in_transaction,
+        })
+    }).collect();
+    let all_migrations = cx.expr_vec_slice(sp, migration_exprs);
+
+    let item = quote_item!(cx, mod embedded_migrations {
+        const ALL_MIGRATIONS: &'static [::diesel::migrations::EmbeddedMigration] = $all_migrations;
+
+        pub fn run<C: ::diesel::Connection>(conn: &C)
+            -> Result<(), ::diesel::migrations::RunMigrationsError>
+        {
+            run_with_output(conn, &mut ::std::io::stdout())
+        }
+
+        pub fn run_with_output<C: ::diesel::Connection>(conn: &C, out: &mut ::std::io::Write)
+            -> Result<(), ::diesel::migrations::RunMigrationsError>
+        {
+            ::diesel::migrations::run_pending_embedded_migrations(conn, ALL_MIGRATIONS, out)
+        }
+    }).unwrap();
+    MacEager::items(SmallVector::one(item))
+}
+
+fn migrations_directory(cx: &mut ExtCtxt, sp: Span, tts: &[ast::TokenTree]) -> Option<PathBuf> {
+    let manifest_dir = match env::var("CARGO_MANIFEST_DIR") {
+        Ok(dir) => PathBuf::from(dir),
+        Err(_) => {
+            cx.span_err(sp, "embed_migrations! can only be used when compiling with Cargo");
+            return None;
+        }
+    };
+    let exprs = match get_exprs_from_tts(cx, sp, tts) {
+        Some(exprs) => exprs,
+        None => return None,
+    };
+
+    match exprs.len() {
+        0 => search_for_migrations_directory(&manifest_dir).map_err(|e| {
+            cx.span_err(sp, &e.to_string());
+        }).ok(),
+        1 => {
+            let expr = exprs.into_iter().next().unwrap();
+            expr_to_string(cx, expr, "expected string literal")
+                .map(|(path, _)| manifest_dir.join(&*path))
+        }
+        _ => {
+            cx.span_err(sp, "embed_migrations! takes at most 1 argument");
+            None
+        }
+    }
+}
+
+fn path_str(cx: &mut ExtCtxt, sp: Span, path: &Path) -> P<ast::Expr> {
+    cx.expr_str(sp, intern_and_get_ident(&path.to_string_lossy()))
+}
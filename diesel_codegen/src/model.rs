@@ -5,7 +5,7 @@ use syntax::ptr::P;
 use syntax::parse::token::str_to_ident;
 
 use attr::Attr;
-use util::{str_value_of_attr_with_name, struct_ty};
+use util::{snake_case, str_value_of_attr_with_name, struct_ty};
 
 pub struct Model {
     pub ty: P<ast::Ty>,
@@ -54,24 +54,8 @@ impl Model {
     }
 }
 
-pub fn infer_association_name(name: &str) -> String {
-    let mut result = String::with_capacity(name.len());
-    result.push_str(&name[..1].to_lowercase());
-    for character in name[1..].chars() {
-        if character.is_uppercase() {
-            result.push('_');
-            for lowercase in character.to_lowercase() {
-                result.push(lowercase);
-            }
-        } else {
-            result.push(character);
-        }
-    }
-    result
-}
-
 fn infer_table_name(name: &str) -> String {
-    let mut result = infer_association_name(name);
+    let mut result = snake_case(name);
     result.push('s');
     result
 }
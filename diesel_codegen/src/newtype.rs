@@ -0,0 +1,131 @@
+use syntax::ast::{self, Item, MetaItem};
+use syntax::codemap::Span;
+use syntax::ext::base::{Annotatable, ExtCtxt};
+use syntax::ext::build::AstBuilder;
+use syntax::parse::token::*;
+use syntax::ptr::P;
+
+use util::struct_ty;
+
+pub fn expand_derive_diesel_new_type(
+    cx: &mut ExtCtxt,
+    span: Span,
+    meta_item: &MetaItem,
+    annotatable: &Annotatable,
+    push: &mut FnMut(Annotatable)
+) {
+    if let Annotatable::Item(ref item) = *annotatable {
+        let (generics, inner_ty) = match new_type_parts(item) {
+            Some(parts) => parts,
+            None => {
+                cx.span_err(span, "`#[derive(DieselNewType)]` can only be applied to tuple structs with a single field");
+                return;
+            }
+        };
+
+        let ty = struct_ty(cx, span, item.ident, &generics);
+        let struct_name = item.ident;
+
+        let to_sql_generics = generics_with_params(cx, span, &generics, &["__ST", "__DB"]);
+        push(Annotatable::Item(quote_item!(cx,
+            impl$to_sql_generics ::diesel::types::ToSql<__ST, __DB> for $ty where
+                __DB: ::diesel::backend::Backend + ::diesel::types::HasSqlType<__ST>,
+                $inner_ty: ::diesel::types::ToSql<__ST, __DB>,
+            {
+                fn to_sql<W: ::std::io::Write>(&self, out: &mut W)
+                    -> Result<::diesel::types::IsNull, Box<::std::error::Error>>
+                {
+                    ::diesel::types::ToSql::<__ST, __DB>::to_sql(&self.0, out)
+                }
+
+                fn size_hint(&self) -> usize {
+                    ::diesel::types::ToSql::<__ST, __DB>::size_hint(&self.0)
+                }
+            }
+        ).unwrap()));
+
+        let from_sql_generics = generics_with_params(cx, span, &generics, &["__ST", "__DB"]);
+        push(Annotatable::Item(quote_item!(cx,
+            impl$from_sql_generics ::diesel::types::FromSql<__ST, __DB> for $ty where
+                __DB: ::diesel::backend::Backend + ::diesel::types::HasSqlType<__ST>,
+                $inner_ty: ::diesel::types::FromSql<__ST, __DB>,
+            {
+                fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<::std::error::Error>> {
+                    ::diesel::types::FromSql::<__ST, __DB>::from_sql(bytes).map($struct_name)
+                }
+            }
+        ).unwrap()));
+
+        let queryable_generics = generics_with_params(cx, span, &generics, &["__ST", "__DB"]);
+        push(Annotatable::Item(quote_item!(cx,
+            impl$queryable_generics ::diesel::Queryable<__ST, __DB> for $ty where
+                __DB: ::diesel::backend::Backend + ::diesel::types::HasSqlType<__ST>,
+                $inner_ty: ::diesel::Queryable<__ST, __DB>,
+            {
+                type Row = <$inner_ty as ::diesel::Queryable<__ST, __DB>>::Row;
+
+                fn build(row: Self::Row) -> Self {
+                    $struct_name(::diesel::Queryable::<__ST, __DB>::build(row))
+                }
+
+                fn describe_error(error: Box<::std::error::Error>) -> Box<::std::error::Error> {
+                    <$inner_ty as ::diesel::Queryable<__ST, __DB>>::describe_error(error)
+                }
+            }
+        ).unwrap()));
+
+        let as_expression_generics = generics_with_params(cx, span, &generics, &["__ST"]);
+        push(Annotatable::Item(quote_item!(cx,
+            impl$as_expression_generics ::diesel::expression::AsExpression<__ST> for $ty where
+                $inner_ty: ::diesel::expression::AsExpression<__ST>,
+            {
+                type Expression = ::diesel::expression::bound::Bound<__ST, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    ::diesel::expression::bound::Bound::new(self)
+                }
+            }
+        ).unwrap()));
+
+        let mut ref_generics = generics_with_params(cx, span, &generics, &["__ST"]);
+        ref_generics.lifetimes.push(cx.lifetime_def(span, intern("'__expr"), Vec::new()));
+        push(Annotatable::Item(quote_item!(cx,
+            impl$ref_generics ::diesel::expression::AsExpression<__ST> for &'__expr $ty where
+                $inner_ty: ::diesel::expression::AsExpression<__ST>,
+            {
+                type Expression = ::diesel::expression::bound::Bound<__ST, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    ::diesel::expression::bound::Bound::new(self)
+                }
+            }
+        ).unwrap()));
+    } else {
+        cx.span_err(meta_item.span,
+                    "`derive` may only be applied to enums and structs");
+    };
+}
+
+fn new_type_parts(item: &Item) -> Option<(ast::Generics, P<ast::Ty>)> {
+    match item.node {
+        ast::ItemStruct(ast::VariantData::Tuple(ref fields, _), ref generics) if fields.len() == 1 => {
+            Some((generics.clone(), fields[0].node.ty.clone()))
+        }
+        _ => None,
+    }
+}
+
+fn generics_with_params(
+    cx: &mut ExtCtxt,
+    span: Span,
+    generics: &ast::Generics,
+    names: &[&str],
+) -> ast::Generics {
+    let mut generics = generics.clone();
+    let mut params = generics.ty_params.into_vec();
+    for name in names {
+        params.push(cx.typaram(span, str_to_ident(name), P::empty(), None));
+    }
+    generics.ty_params = params.into();
+    generics
+}
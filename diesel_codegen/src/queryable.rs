@@ -34,23 +34,63 @@ pub fn expand_derive_queryable(
 
         let build_impl = struct_literal_with_fields_assigned_to_row_elements(
             span, &item, cx, &attrs);
+        let struct_name = cx.expr_str(span, item.ident.name.as_str());
+        let field_names = cx.expr_vec_slice(span, attrs.iter().map(|f| {
+            let name = f.field_name.unwrap_or(f.column_name);
+            cx.expr_str(span, name.name.as_str())
+        }).collect());
         let mut params = generics.ty_params.into_vec();
         params.push(ty_param_with_name(cx, span, "__ST"));
         params.push(ty_param_with_name(cx, span, "__DB"));
         generics.ty_params = params.into();
 
-        let impl_item = quote_item!(cx,
-            impl$generics ::diesel::Queryable<__ST, __DB> for $ty where
-                __DB: ::diesel::backend::Backend + ::diesel::types::HasSqlType<__ST>,
-                $row_type: ::diesel::types::FromSqlRow<__ST, __DB>,
-            {
-                type Row = $row_type;
+        let impl_item = if attrs.iter().any(|f| f.embed) {
+            // When a field embeds another `Queryable` struct, its columns can't
+            // be deserialized with `FromSqlRow`. Instead we load a tuple of the
+            // field types, which builds each field with its own `Queryable`
+            // impl.
+            quote_item!(cx,
+                impl$generics ::diesel::Queryable<__ST, __DB> for $ty where
+                    __DB: ::diesel::backend::Backend + ::diesel::types::HasSqlType<__ST>,
+                    $row_type: ::diesel::Queryable<__ST, __DB>,
+                {
+                    type Row = <$row_type as ::diesel::Queryable<__ST, __DB>>::Row;
 
-                fn build(row: Self::Row) -> Self {
-                    $build_impl
+                    fn build(row: Self::Row) -> Self {
+                        let row = <$row_type as ::diesel::Queryable<__ST, __DB>>::build(row);
+                        $build_impl
+                    }
+
+                    fn describe_error(error: Box<::std::error::Error>) -> Box<::std::error::Error> {
+                        let error = <$row_type as ::diesel::Queryable<__ST, __DB>>::describe_error(error);
+                        match error.downcast::<::diesel::result::DeserializeFieldError>() {
+                            Ok(error) => Box::new((*error).name_field($struct_name, $field_names)),
+                            Err(error) => error,
+                        }
+                    }
                 }
-            }
-        ).unwrap();
+            ).unwrap()
+        } else {
+            quote_item!(cx,
+                impl$generics ::diesel::Queryable<__ST, __DB> for $ty where
+                    __DB: ::diesel::backend::Backend + ::diesel::types::HasSqlType<__ST>,
+                    $row_type: ::diesel::types::FromSqlRow<__ST, __DB>,
+                {
+                    type Row = $row_type;
+
+                    fn build(row: Self::Row) -> Self {
+                        $build_impl
+                    }
+
+                    fn describe_error(error: Box<::std::error::Error>) -> Box<::std::error::Error> {
+                        match error.downcast::<::diesel::result::DeserializeFieldError>() {
+                            Ok(error) => Box::new((*error).name_field($struct_name, $field_names)),
+                            Err(error) => error,
+                        }
+                    }
+                }
+            ).unwrap()
+        };
 
         push(Annotatable::Item(impl_item));
     } else {
@@ -0,0 +1,149 @@
+use syntax::ast::{self, Item, MetaItem};
+use syntax::codemap::Span;
+use syntax::ext::base::{Annotatable, ExtCtxt};
+use syntax::ext::build::AstBuilder;
+use syntax::parse::token::*;
+
+use util::{snake_case, str_value_of_attr_with_name};
+
+struct TextVariant {
+    name: ast::Ident,
+    value: String,
+}
+
+pub fn expand_derive_diesel_text_enum(
+    cx: &mut ExtCtxt,
+    span: Span,
+    meta_item: &MetaItem,
+    annotatable: &Annotatable,
+    push: &mut FnMut(Annotatable)
+) {
+    if let Annotatable::Item(ref item) = *annotatable {
+        let variants = match text_variants(cx, item) {
+            Some(variants) => variants,
+            None => {
+                cx.span_err(span, "`#[derive(DieselTextEnum)]` can only be applied to enums with no fields");
+                return;
+            }
+        };
+
+        let enum_name = item.ident;
+        let expected_values = variants.iter()
+            .map(|v| format!("`{}`", v.value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let error_prefix = cx.expr_str(span, intern_and_get_ident(
+            &format!("Unrecognized value for {}. Expected one of {}. Got", enum_name, expected_values)));
+
+        let to_sql_arms = variants.iter().map(|v| {
+            let variant_name = v.name;
+            let value = cx.expr_str(span, intern_and_get_ident(&v.value));
+            quote_arm!(cx, $enum_name::$variant_name => $value,)
+        }).collect::<Vec<_>>();
+        let to_sql_match = cx.expr_match(span, quote_expr!(cx, *self), to_sql_arms);
+
+        let from_sql_checks = variants.iter().map(|v| {
+            let variant_name = v.name;
+            let value = cx.expr_str(span, intern_and_get_ident(&v.value));
+            quote_stmt!(cx, if value == $value { return Ok($enum_name::$variant_name); }).unwrap()
+        }).collect::<Vec<_>>();
+
+        push(Annotatable::Item(quote_item!(cx,
+            impl<__ST, __DB> ::diesel::types::ToSql<__ST, __DB> for $enum_name where
+                __DB: ::diesel::backend::Backend + ::diesel::types::HasSqlType<__ST>,
+                &'static str: ::diesel::types::ToSql<__ST, __DB>,
+            {
+                fn to_sql<W: ::std::io::Write>(&self, out: &mut W)
+                    -> Result<::diesel::types::IsNull, Box<::std::error::Error>>
+                {
+                    let value: &'static str = $to_sql_match;
+                    ::diesel::types::ToSql::<__ST, __DB>::to_sql(&value, out)
+                }
+            }
+        ).unwrap()));
+
+        push(Annotatable::Item(quote_item!(cx,
+            impl<__ST, __DB> ::diesel::types::FromSql<__ST, __DB> for $enum_name where
+                __DB: ::diesel::backend::Backend + ::diesel::types::HasSqlType<__ST>,
+                String: ::diesel::types::FromSql<__ST, __DB>,
+            {
+                fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<::std::error::Error>> {
+                    let value: String = try!(::diesel::types::FromSql::<__ST, __DB>::from_sql(bytes));
+                    $from_sql_checks
+                    let error_message = format!("{} `{}`", $error_prefix, value);
+                    Err(Box::<::std::error::Error + Send + Sync>::from(error_message))
+                }
+            }
+        ).unwrap()));
+
+        push(Annotatable::Item(quote_item!(cx,
+            impl<__ST, __DB> ::diesel::types::FromSqlRow<__ST, __DB> for $enum_name where
+                __DB: ::diesel::backend::Backend + ::diesel::types::HasSqlType<__ST>,
+                $enum_name: ::diesel::types::FromSql<__ST, __DB>,
+            {
+                fn build_from_row<R: ::diesel::row::Row>(row: &mut R)
+                    -> Result<Self, Box<::std::error::Error>>
+                {
+                    ::diesel::types::FromSql::<__ST, __DB>::from_sql(row.take())
+                }
+            }
+        ).unwrap()));
+
+        push(Annotatable::Item(quote_item!(cx,
+            impl<__ST, __DB> ::diesel::Queryable<__ST, __DB> for $enum_name where
+                __DB: ::diesel::backend::Backend + ::diesel::types::HasSqlType<__ST>,
+                $enum_name: ::diesel::types::FromSqlRow<__ST, __DB>,
+            {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> Self {
+                    row
+                }
+            }
+        ).unwrap()));
+
+        push(Annotatable::Item(quote_item!(cx,
+            impl<__ST> ::diesel::expression::AsExpression<__ST> for $enum_name where
+                &'static str: ::diesel::types::ToSql<__ST, ::diesel::backend::Pg>,
+            {
+                type Expression = ::diesel::expression::bound::Bound<__ST, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    ::diesel::expression::bound::Bound::new(self)
+                }
+            }
+        ).unwrap()));
+
+        push(Annotatable::Item(quote_item!(cx,
+            impl<'__expr, __ST> ::diesel::expression::AsExpression<__ST> for &'__expr $enum_name where
+                &'static str: ::diesel::types::ToSql<__ST, ::diesel::backend::Pg>,
+            {
+                type Expression = ::diesel::expression::bound::Bound<__ST, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    ::diesel::expression::bound::Bound::new(self)
+                }
+            }
+        ).unwrap()));
+    } else {
+        cx.span_err(meta_item.span,
+                    "`derive` may only be applied to enums and structs");
+    };
+}
+
+fn text_variants(cx: &mut ExtCtxt, item: &Item) -> Option<Vec<TextVariant>> {
+    let enum_def = match item.node {
+        ast::ItemEnum(ref enum_def, ref generics) if !generics.is_parameterized() => enum_def,
+        _ => return None,
+    };
+    enum_def.variants.iter().map(|variant| {
+        if !variant.node.data.is_unit() {
+            return None;
+        }
+        let name = variant.node.name;
+        let value = str_value_of_attr_with_name(cx, &variant.node.attrs, "sql_value")
+            .map(|value| value.name.as_str().to_string())
+            .unwrap_or_else(|| snake_case(&name.name.as_str()));
+        Some(TextVariant { name: name, value: value })
+    }).collect()
+}
@@ -38,7 +38,9 @@ pub fn strip_attributes(krate: ast::Crate) -> ast::Crate {
 
     impl fold::Folder for StripAttributeFolder {
         fn fold_attribute(&mut self, attr: ast::Attribute) -> Option<ast::Attribute> {
-            if attr.check_name("table_name") {
+            if attr.check_name("table_name") || attr.check_name("sql_value") ||
+                attr.check_name("embed")
+            {
                 None
             } else {
                 Some(attr)
@@ -66,6 +68,22 @@ pub fn struct_ty(
     cx.ty_path(cx.path_all(span, false, vec![name], lifetimes, ty_params, Vec::new()))
 }
 
+pub fn snake_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    result.push_str(&name[..1].to_lowercase());
+    for character in name[1..].chars() {
+        if character.is_uppercase() {
+            result.push('_');
+            for lowercase in character.to_lowercase() {
+                result.push(lowercase);
+            }
+        } else {
+            result.push(character);
+        }
+    }
+    result
+}
+
 pub fn ty_param_of_option(ty: &ast::Ty) -> Option<&P<ast::Ty>> {
     match ty.node {
         ast::TyPath(_, ref path) => {
@@ -77,3 +95,9 @@ pub fn ty_param_of_option(ty: &ast::Ty) -> Option<&P<ast::Ty>> {
         _ => None,
     }
 }
+
+#[test]
+fn snake_case_inserts_underscores_before_capitals() {
+    assert_eq!("foo", &snake_case("Foo"));
+    assert_eq!("pending_review", &snake_case("PendingReview"));
+}
@@ -0,0 +1,19 @@
+#[macro_use]
+extern crate diesel;
+
+use diesel::*;
+
+table! {
+    users {
+        id -> Serial,
+        name -> VarChar,
+    }
+}
+
+fn main() {
+    use self::users::dsl::*;
+
+    let query = users.select(name).distinct_on(name);
+    debug_sql!(query);
+    //~^ ERROR E0277
+}
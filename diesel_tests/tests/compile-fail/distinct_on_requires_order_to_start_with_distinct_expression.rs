@@ -0,0 +1,20 @@
+#[macro_use]
+extern crate diesel;
+
+use diesel::*;
+
+table! {
+    users {
+        id -> Serial,
+        name -> VarChar,
+    }
+}
+
+fn main() {
+    use self::users::dsl::*;
+
+    let source = users.distinct_on(name).order(id);
+    //~^ ERROR E0277
+    let source = users.order(id).distinct_on(name);
+    //~^ ERROR E0277
+}
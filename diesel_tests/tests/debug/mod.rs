@@ -1,4 +1,5 @@
 use diesel::*;
+use diesel::connection::mock::MockConnection;
 
 #[test]
 fn test_debug_count_output() {
@@ -13,3 +14,76 @@ fn test_debug_output() {
     let command = update(users.filter(id.eq(1))).set(name.eq("new_name"));
     assert_eq!(debug_sql!(command), "UPDATE `users` SET `name` = ? WHERE `users`.`id` = ?")
 }
+
+#[test]
+fn debug_sql_can_render_sql_for_a_specific_backend() {
+    use diesel::backend::Pg;
+    use schema::users::dsl::*;
+
+    let command = update(users.filter(id.eq(1))).set(name.eq("new_name"));
+    assert_eq!(debug_sql!(command, Pg),
+        r#"UPDATE "users" SET "name" = $1 WHERE "users"."id" = $2"#);
+}
+
+#[test]
+fn test_debug_distinct_on_output() {
+    use diesel::backend::Pg;
+    use schema::users::dsl::*;
+    let query = users.select(name).distinct_on(name).order(name.desc());
+    assert_eq!(debug_sql!(query, Pg),
+        r#"SELECT DISTINCT ON ("users"."name") "users"."name" FROM "users" ORDER BY "users"."name" DESC"#)
+}
+
+#[test]
+fn test_debug_offset_without_limit_output() {
+    use schema::users::dsl::*;
+    let query = users.select(id).offset(10);
+    assert_eq!(debug_sql!(query), "SELECT `users`.`id` FROM `users` LIMIT 18446744073709551615 OFFSET ?")
+}
+
+#[test]
+fn limit_and_offset_are_sent_as_bind_params() {
+    use schema::users::dsl::*;
+    let connection = MockConnection::new();
+    let _ = users.select(id).limit(10).offset(20).load::<i32>(&connection).unwrap();
+
+    let query = connection.last_query().unwrap();
+    assert_eq!("SELECT `users`.`id` FROM `users` LIMIT ? OFFSET ?", query.sql);
+    assert_eq!(vec![
+        Some(vec![0, 0, 0, 0, 0, 0, 0, 10]),
+        Some(vec![0, 0, 0, 0, 0, 0, 0, 20]),
+    ], query.binds);
+}
+
+#[test]
+fn eq_any_is_an_in_list_on_backends_without_arrays() {
+    use schema::users::dsl::*;
+    let query = users.select(id).filter(name.eq_any(vec!["Sean", "Tess"]));
+    assert_eq!(debug_sql!(query), "SELECT `users`.`id` FROM `users` WHERE `users`.`name` IN (?, ?)");
+    let query = users.select(id).filter(name.eq_any(Vec::<&str>::new()));
+    assert_eq!(debug_sql!(query), "SELECT `users`.`id` FROM `users` WHERE 1 = 0");
+}
+
+#[test]
+fn eq_any_with_many_values_is_a_values_join_on_backends_without_arrays() {
+    use schema::users::dsl::*;
+    let ids = (0..1001).collect::<Vec<i32>>();
+    let query = users.select(name).filter(id.eq_any(&ids));
+    let rows = vec!["ROW(?)"; 1001].join(", ");
+    assert_eq!(debug_sql!(query), format!(
+        "SELECT `users`.`name` FROM `users` WHERE `users`.`id` IN \
+         (SELECT column_0 FROM (VALUES {}) AS eq_any_values)", rows));
+    let query = users.select(name).filter(id.eq_any(&ids[..1000]));
+    assert_eq!(debug_sql!(query), format!(
+        "SELECT `users`.`name` FROM `users` WHERE `users`.`id` IN ({})",
+        vec!["?"; 1000].join(", ")));
+}
+
+#[test]
+fn eq_any_is_a_single_array_bind_on_pg() {
+    use diesel::backend::Pg;
+    use schema::users::dsl::*;
+    let query = users.select(id).filter(name.eq_any(vec!["Sean", "Tess"]));
+    assert_eq!(debug_sql!(query, Pg),
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."name" = ANY($1)"#);
+}
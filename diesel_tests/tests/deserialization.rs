@@ -19,3 +19,85 @@ fn generated_queryable_allows_lifetimes() {
     };
     assert_eq!(Ok(expected_user), users.select((id, name)).first(&connection));
 }
+
+#[derive(Queryable, PartialEq, Debug)]
+struct PostWithAuthor {
+    #[embed]
+    post: Post,
+    #[embed]
+    author: User,
+}
+
+#[derive(Queryable, PartialEq, Debug)]
+struct UserWithOptionalPost<'a> {
+    name: Cow<'a, str>,
+    #[embed]
+    post: Option<Post>,
+}
+
+#[test]
+fn queryable_structs_can_embed_other_queryable_structs() {
+    let connection = connection_with_sean_and_tess_in_users_table();
+    connection.execute("INSERT INTO posts (id, user_id, title) VALUES (1, 1, 'Hello')")
+        .unwrap();
+
+    let source = posts::table.inner_join(users::table);
+    let expected_data = vec![PostWithAuthor {
+        post: Post::new(1, 1, "Hello", None),
+        author: User::new(1, "Sean"),
+    }];
+    let actual_data: Vec<PostWithAuthor> = source.load(&connection).unwrap().collect();
+    assert_eq!(expected_data, actual_data);
+
+    let source = users::table.left_outer_join(posts::table)
+        .select((users::name, posts::all_columns.nullable()))
+        .order(users::id);
+    let expected_data = vec![
+        UserWithOptionalPost {
+            name: Cow::Owned("Sean".to_string()),
+            post: Some(Post::new(1, 1, "Hello", None)),
+        },
+        UserWithOptionalPost {
+            name: Cow::Owned("Tess".to_string()),
+            post: None,
+        },
+    ];
+    let actual_data: Vec<UserWithOptionalPost> = source.load(&connection).unwrap().collect();
+    assert_eq!(expected_data, actual_data);
+}
+
+#[test]
+fn deserialization_errors_name_the_struct_and_field() {
+    use diesel::backend::Pg;
+    use diesel::result::DeserializeFieldError;
+    use diesel::types::{Integer, VarChar};
+
+    let cause = Box::<::std::error::Error + Send + Sync>::from("Unexpected null for non-null column");
+    let error = Box::new(DeserializeFieldError::new(1, cause));
+    let described = <CowUser as Queryable<(Integer, VarChar), Pg>>::describe_error(error);
+
+    assert_eq!("Error deserializing field `name` of `CowUser`: Unexpected null for non-null column",
+               described.to_string());
+}
+
+#[test]
+#[should_panic(expected = "Error deserializing field `name` of `CowUser`")]
+fn loading_null_into_a_non_optional_field_names_the_field() {
+    use diesel::expression::dsl::sql;
+    use diesel::types::{Integer, VarChar};
+
+    let connection = connection();
+    let query = select(sql::<(Integer, VarChar)>("1, NULL::varchar"));
+    let _ = query.first::<CowUser>(&connection);
+}
+
+#[test]
+#[should_panic(expected = "Error deserializing field `name` of `CowUser` (column `user_name`)")]
+fn deserialization_errors_name_the_column() {
+    use diesel::expression::dsl::sql;
+    use diesel::types::{Integer, VarChar};
+
+    let connection = connection();
+    let query = select(sql::<(Integer, VarChar)>("1, NULL::varchar AS user_name"));
+    let _ = query.first::<CowUser>(&connection);
+}
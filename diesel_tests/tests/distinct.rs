@@ -0,0 +1,45 @@
+use schema::*;
+use diesel::*;
+
+#[test]
+fn distinct_on_keeps_the_first_row_for_each_value() {
+    use schema::users::dsl::*;
+    let connection = connection();
+
+    connection.execute("INSERT INTO users (name, hair_color) VALUES
+        ('Sean', 'black'),
+        ('Tess', 'brown'),
+        ('Jim', 'black')
+    ").unwrap();
+
+    let expected_data = vec![
+        ("Jim".to_string(), Some("black".to_string())),
+        ("Tess".to_string(), Some("brown".to_string())),
+    ];
+    let source = users.select((name, hair_color))
+        .distinct_on(hair_color)
+        .order((hair_color, name));
+    let actual_data: Vec<_> = source.load(&connection).unwrap().collect();
+
+    assert_eq!(expected_data, actual_data);
+}
+
+#[test]
+fn distinct_on_can_be_called_after_order() {
+    use schema::users::dsl::*;
+    let connection = connection();
+
+    connection.execute("INSERT INTO users (name, hair_color) VALUES
+        ('Sean', 'black'),
+        ('Tess', 'brown'),
+        ('Jim', 'black')
+    ").unwrap();
+
+    let expected_data = vec![Some("brown".to_string()), Some("black".to_string())];
+    let source = users.select(hair_color)
+        .order(hair_color.desc())
+        .distinct_on(hair_color);
+    let actual_data: Vec<_> = source.load(&connection).unwrap().collect();
+
+    assert_eq!(expected_data, actual_data);
+}
@@ -0,0 +1,50 @@
+use schema;
+use diesel::*;
+use diesel::connection::SimpleConnection;
+
+/// Takes the migrations lock before the test changes anything, so it waits for
+/// any other test which is running migrations instead of deadlocking with it.
+fn connection() -> PgConnection {
+    let connection = schema::connection();
+    let lock_sql = migrations::MigrationConfig::default().transaction_lock_sql();
+    connection.execute(&lock_sql).unwrap();
+    connection
+}
+
+embed_migrations!();
+
+#[test]
+fn embedded_migrations_run_the_pending_migrations() {
+    let connection = connection();
+    connection.batch_execute(include_str!("../../migrations/20160107090901_add_tags_to_posts/down.sql"))
+        .unwrap();
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '20160107090901'")
+        .unwrap();
+
+    embedded_migrations::run(&connection).unwrap();
+
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '20160107090901'";
+    assert_eq!(Ok(1), connection.execute(query));
+    assert!(connection.execute("SELECT tags FROM posts").is_ok());
+}
+
+#[test]
+fn embedded_migrations_do_nothing_when_no_migrations_are_pending() {
+    let connection = connection();
+    embedded_migrations::run(&connection).unwrap();
+    embedded_migrations::run(&connection).unwrap();
+}
+
+#[test]
+fn embedded_migrations_can_write_progress_to_any_writer() {
+    let connection = connection();
+    connection.batch_execute(include_str!("../../migrations/20160107090901_add_tags_to_posts/down.sql"))
+        .unwrap();
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '20160107090901'")
+        .unwrap();
+
+    let mut output = Vec::new();
+    embedded_migrations::run_with_output(&connection, &mut output).unwrap();
+
+    assert_eq!(b"Running migration 20160107090901\n".to_vec(), output);
+}
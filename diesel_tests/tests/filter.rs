@@ -282,3 +282,66 @@ fn filter_by_boxed_predicate() {
     assert_eq!(Ok(sean), queried_sean);
     assert_eq!(Ok(tess), queried_tess);
 }
+
+#[test]
+fn filter_by_predicates_combined_at_runtime() {
+    type Predicate = Box<BoxableExpression<users::table, types::Bool, Pg, SqlType=types::Bool>>;
+
+    fn search(name_like: Option<&str>, id_at_least: Option<i32>) -> Predicate {
+        let mut predicates = Vec::<Predicate>::new();
+        if let Some(name_like) = name_like {
+            predicates.push(Box::new(users::name.like(name_like.to_string())));
+        }
+        if let Some(id_at_least) = id_at_least {
+            predicates.push(Box::new(users::id.ge(id_at_least)));
+        }
+        let always_true: Predicate = Box::new(AsExpression::<types::Bool>::as_expression(true));
+        predicates.into_iter().fold(always_true, |acc, predicate| Box::new(acc.and(predicate)))
+    }
+
+    let connection = connection_with_sean_and_tess_in_users_table();
+    let sean = User::new(1, "Sean");
+    let tess = User::new(2, "Tess");
+    let load = |predicate| {
+        users::table.filter(predicate).order(users::id).load(&connection)
+            .unwrap().collect::<Vec<User>>()
+    };
+
+    assert_eq!(vec![sean.clone(), tess.clone()], load(search(None, None)));
+    assert_eq!(vec![sean.clone()], load(search(Some("S%"), None)));
+    assert_eq!(vec![tess.clone()], load(search(None, Some(2))));
+    assert_eq!(Vec::<User>::new(), load(search(Some("S%"), Some(2))));
+}
+
+#[test]
+fn filter_by_eq_any() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_sean_and_tess_in_users_table();
+    let sean = User::new(1, "Sean");
+    let tess = User::new(2, "Tess");
+
+    let data = users.filter(name.eq_any(vec!["Sean", "Jim"])).load(&connection);
+    assert_eq!(vec![sean.clone()], data.unwrap().collect::<Vec<_>>());
+    let data = users.filter(id.eq_any(&[1, 2][..])).order(id).load(&connection);
+    assert_eq!(vec![sean, tess], data.unwrap().collect::<Vec<_>>());
+}
+
+#[test]
+fn eq_any_sends_large_lists_as_a_single_bind_parameter() {
+    use schema::users::dsl::*;
+
+    use std::sync::{Arc, Mutex};
+
+    let connection = connection_with_sean_and_tess_in_users_table();
+    let bind_counts = Arc::new(Mutex::new(Vec::new()));
+    let hook_bind_counts = bind_counts.clone();
+    connection.set_query_hook(move |event| {
+        hook_bind_counts.lock().unwrap().push(event.binds().len());
+    });
+    let ids = (2..100_000).collect::<Vec<i32>>();
+    let query = users.select(name).filter(id.eq_any(&ids));
+
+    assert_eq!(Ok("Tess".to_string()), query.first(&connection));
+    assert_eq!(vec![1], *bind_counts.lock().unwrap());
+}
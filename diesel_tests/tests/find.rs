@@ -35,3 +35,36 @@ fn find_with_non_serial_pk() {
     assert_eq!(Ok("Tess".to_string()), users.find("Tess".to_string()).first(&connection));
     assert_eq!(Ok(None::<String>), users.find("Wibble").first(&connection).optional());
 }
+
+#[test]
+fn partially_built_queries_can_be_returned_using_helper_types() {
+    use diesel::helper_types::{Find, FindAllBy, Count, Limit};
+    use schema::users;
+
+    fn find_user(id: i32) -> Find<users::table, i32> {
+        users::table.find(id)
+    }
+
+    fn users_with_names<'a>(names: &'a [&'a str]) -> FindAllBy<users::table, users::name, &'a [&'a str]> {
+        users::table.filter(users::name.eq_any(names))
+    }
+
+    fn count_users() -> Count<users::table> {
+        users::table.count()
+    }
+
+    fn first_two_users() -> Limit<users::table> {
+        users::table.limit(2)
+    }
+
+    let connection = connection();
+    connection.execute("INSERT INTO users (id, name) VALUES (1, 'Sean'), (2, 'Tess'), (3, 'Jim')")
+        .unwrap();
+
+    assert_eq!(Ok(User::new(2, "Tess")), find_user(2).first(&connection));
+    let names = ["Sean", "Jim"];
+    let found = users_with_names(&names).order(users::id).load(&connection).unwrap();
+    assert_eq!(vec![User::new(1, "Sean"), User::new(3, "Jim")], found.collect::<Vec<_>>());
+    assert_eq!(Ok(3), count_users().first(&connection));
+    assert_eq!(2, first_two_users().load::<User>(&connection).unwrap().count());
+}
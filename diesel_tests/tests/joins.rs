@@ -107,6 +107,23 @@ fn left_outer_joins() {
     assert_eq!(expected_data, actual_data);
 }
 
+#[test]
+fn left_outer_joins_can_be_filtered_by_the_left_side() {
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    connection.execute("INSERT INTO posts (id, user_id, title) VALUES
+        (1, 1, 'Hello')
+    ").unwrap();
+
+    let tess = User::new(2, "Tess");
+    let expected_data = vec![(tess, None::<Post>)];
+    let source = users::table.left_outer_join(posts::table)
+        .filter(users::name.eq("Tess"));
+    let actual_data: Vec<_> = source.load(&connection).unwrap().collect();
+
+    assert_eq!(expected_data, actual_data);
+}
+
 #[test]
 fn columns_on_right_side_of_left_outer_joins_are_nullable() {
     let connection = connection_with_sean_and_tess_in_users_table();
@@ -194,6 +211,109 @@ fn select_right_side_with_nullable_column_first() {
     assert_eq!(expected_data, actual_data);
 }
 
+#[test]
+fn select_right_side_before_left_side_of_left_outer_join() {
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    connection.execute("INSERT INTO posts (user_id, title, body) VALUES
+        (1, 'Hello', 'Content')
+    ").unwrap();
+
+    let expected_data = vec![
+        (Some(("Hello".to_string(), Some("Content".to_string()))), "Sean".to_string()),
+        (None, "Tess".to_string()),
+    ];
+
+    let source = users::table.left_outer_join(posts::table).select(((posts::title, posts::body), users::name));
+    let actual_data: Vec<_> = source.load(&connection).unwrap().collect();
+
+    assert_eq!(expected_data, actual_data);
+}
+
+#[test]
+fn right_side_of_left_outer_join_can_be_selected_as_nullable() {
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    connection.execute("INSERT INTO posts (id, user_id, title) VALUES (1, 1, 'Hello')")
+        .unwrap();
+
+    let sean = User::new(1, "Sean");
+    let tess = User::new(2, "Tess");
+    let expected_data = vec![
+        (sean, Some(Post::new(1, 1, "Hello", None))),
+        (tess, None),
+    ];
+
+    let source = users::table.left_outer_join(posts::table)
+        .select((users::all_columns, posts::all_columns.nullable()));
+    let actual_data: Vec<_> = source.load(&connection).unwrap().collect();
+
+    assert_eq!(expected_data, actual_data);
+}
+
+#[test]
+fn inner_join_with_explicit_on_clause() {
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    connection.execute("INSERT INTO posts (id, user_id, title) VALUES
+        (1, 1, 'Hello'),
+        (2, 1, 'World'),
+        (3, 2, 'World')
+    ").unwrap();
+
+    let sean = User::new(1, "Sean");
+    let seans_second_post = Post::new(2, 1, "World", None);
+
+    let expected_data = vec![(sean, seans_second_post)];
+    let source = users::table.inner_join(posts::table.on(
+        posts::user_id.eq(users::id).and(posts::title.eq("World")).and(users::name.eq("Sean"))
+    ));
+    let actual_data: Vec<_> = source.load(&connection).unwrap().collect();
+
+    assert_eq!(expected_data, actual_data);
+}
+
+#[test]
+fn left_outer_join_with_explicit_on_clause() {
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    connection.execute("INSERT INTO posts (id, user_id, title) VALUES
+        (1, 1, 'Hello'),
+        (2, 2, 'World')
+    ").unwrap();
+
+    let sean = User::new(1, "Sean");
+    let tess = User::new(2, "Tess");
+    let seans_post = Post::new(1, 1, "Hello", None);
+
+    let expected_data = vec![(sean, Some(seans_post)), (tess, None)];
+    let source = users::table.left_outer_join(posts::table.on(
+        posts::user_id.eq(users::id).and(posts::title.eq("Hello"))
+    )).order(users::id);
+    let actual_data: Vec<_> = source.load(&connection).unwrap().collect();
+
+    assert_eq!(expected_data, actual_data);
+}
+
+#[test]
+fn explicit_on_clause_does_not_need_an_association() {
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    connection.execute("INSERT INTO posts (id, user_id, title) VALUES
+        (1, 1, 'Hello'),
+        (2, 1, 'World')
+    ").unwrap();
+    connection.execute("INSERT INTO comments (id, post_id, text) VALUES (1, 2, 'OMG')")
+        .unwrap();
+
+    let expected_data = vec![("Tess".to_string(), 1)];
+    let source = users::table.inner_join(comments::table.on(comments::post_id.eq(users::id)))
+        .select((users::name, comments::id));
+    let actual_data: Vec<_> = source.load(&connection).unwrap().collect();
+
+    assert_eq!(expected_data, actual_data);
+}
+
 #[test]
 fn select_then_join() {
     use schema::users::dsl::*;
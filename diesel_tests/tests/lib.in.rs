@@ -1,5 +1,8 @@
 mod annotations;
 mod deserialization;
+mod embedded_migrations;
 mod insert;
+mod newtypes;
 mod schema;
+mod text_enums;
 mod update;
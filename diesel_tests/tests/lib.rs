@@ -11,6 +11,7 @@ include!("lib.in.rs");
 include!(concat!(env!("OUT_DIR"), "/lib.rs"));
 
 mod associations;
+mod distinct;
 mod expressions;
 mod filter;
 mod filter_operators;
@@ -18,6 +19,7 @@ mod find;
 mod internal_details;
 mod joins;
 mod macros;
+mod migrations;
 mod order;
 mod perf_details;
 mod select;
@@ -0,0 +1,646 @@
+use schema;
+use diesel::*;
+use diesel::connection::SimpleConnection;
+use std::io;
+use std::time::Duration;
+
+/// Takes the migrations lock before the test changes anything, so it waits for
+/// any other test which is running migrations instead of deadlocking with it.
+fn connection() -> PgConnection {
+    let connection = schema::connection();
+    let lock_sql = migrations::MigrationConfig::default().transaction_lock_sql();
+    connection.execute(&lock_sql).unwrap();
+    connection
+}
+
+#[test]
+fn pending_migrations_script_only_includes_migrations_which_have_not_run() {
+    let connection = connection();
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '20160107090901'")
+        .unwrap();
+
+    let mut script = Vec::new();
+    migrations::write_pending_migrations_script(&connection, &mut script).unwrap();
+    let script = String::from_utf8(script).unwrap();
+
+    assert!(script.starts_with("CREATE TABLE IF NOT EXISTS \"__diesel_schema_migrations\""));
+    assert!(script.contains("\n-- Migration 20160107090901\nBEGIN;\n"));
+    assert!(script.contains("INSERT INTO \"__diesel_schema_migrations\" (version, checksum, duration_ms, run_by) VALUES ('20160107090901', '"));
+    assert!(!script.contains("20151219180527"));
+    assert!(!script.contains("20160116104628"));
+}
+
+#[test]
+fn writing_a_migrations_script_does_not_run_the_migrations() {
+    let connection = connection();
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '20160107090901'")
+        .unwrap();
+
+    migrations::write_pending_migrations_script(&connection, &mut Vec::new()).unwrap();
+
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '20160107090901'";
+    assert_eq!(Ok(0), connection.execute(query));
+}
+
+#[test]
+fn migrations_which_opt_out_of_transactions_are_not_wrapped_in_one_in_the_script() {
+    let connection = connection();
+    let source = vec![migrations::EmbeddedMigration {
+        version: "00000000000004",
+        up_sql: "CREATE INDEX CONCURRENTLY users_name ON users (name);",
+        down_sql: "DROP INDEX users_name",
+        run_in_transaction: false,
+    }];
+
+    let mut script = Vec::new();
+    migrations::write_pending_migrations_script_from_source(&connection, &source, &mut script).unwrap();
+    let script = String::from_utf8(script).unwrap();
+
+    assert!(script.contains("\n-- Migration 00000000000004\nCREATE INDEX CONCURRENTLY"));
+    assert!(!script.contains("BEGIN;"));
+    assert!(!script.contains("COMMIT;"));
+}
+
+fn migrations_dir() -> ::std::path::PathBuf {
+    ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../migrations")
+}
+
+#[test]
+fn pending_migrations_can_be_run_from_a_given_directory() {
+    let connection = connection();
+    connection.batch_execute(include_str!("../../migrations/20160107090901_add_tags_to_posts/down.sql"))
+        .unwrap();
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '20160107090901'")
+        .unwrap();
+
+    let mut output = Vec::new();
+    migrations::run_pending_migrations_in_directory(&connection, &migrations_dir(), &mut output)
+        .unwrap();
+
+    assert_eq!(b"Running migration 20160107090901\n".to_vec(), output);
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '20160107090901'";
+    assert_eq!(Ok(1), connection.execute(query));
+}
+
+#[test]
+fn latest_migration_can_be_reverted_from_a_given_directory() {
+    let connection = connection();
+
+    let mut output = Vec::new();
+    let reverted_version = migrations::revert_latest_migration_in_directory(
+        &connection, &migrations_dir(), &mut output).unwrap();
+
+    assert_eq!("20160116104628", reverted_version);
+    assert_eq!(b"Rolling back migration 20160116104628\n".to_vec(), output);
+}
+
+#[test]
+fn run_migrations_until_leaves_later_migrations_pending() {
+    let connection = connection();
+    connection.batch_execute(include_str!("../../migrations/20160116104628_create_special_posts_and_special_comments/down.sql"))
+        .unwrap();
+    connection.batch_execute(include_str!("../../migrations/20160107090901_add_tags_to_posts/down.sql"))
+        .unwrap();
+    connection.execute("DELETE FROM __diesel_schema_migrations
+        WHERE version IN ('20160107090901', '20160116104628')").unwrap();
+
+    migrations::run_migrations_until(&connection, "20160107090901").unwrap();
+
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '20160107090901'";
+    assert_eq!(Ok(1), connection.execute(query));
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '20160116104628'";
+    assert_eq!(Ok(0), connection.execute(query));
+}
+
+#[test]
+fn run_migrations_until_an_unknown_version_runs_nothing() {
+    let connection = connection();
+    connection.batch_execute(include_str!("../../migrations/20160107090901_add_tags_to_posts/down.sql"))
+        .unwrap();
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '20160107090901'")
+        .unwrap();
+
+    let result = migrations::run_migrations_until(&connection, "20160107090902");
+
+    let expected_error = migrations::MigrationError::UnknownMigrationVersion("20160107090902".into());
+    assert_eq!(Err(expected_error.into()), result);
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '20160107090901'";
+    assert_eq!(Ok(0), connection.execute(query));
+}
+
+#[test]
+fn redoing_the_latest_migration_leaves_it_applied() {
+    let connection = connection();
+    connection.execute("INSERT INTO special_posts (user_id, title) VALUES (1, 'Hello')")
+        .unwrap();
+
+    let redone_version = migrations::redo_latest_migration(&connection).unwrap();
+
+    assert_eq!("20160116104628", redone_version);
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '20160116104628'";
+    assert_eq!(Ok(1), connection.execute(query));
+    assert_eq!(Ok(0), connection.execute("SELECT * FROM special_posts"));
+}
+
+#[test]
+fn pending_migrations_lists_migrations_which_have_not_run() {
+    let connection = connection();
+    assert_eq!(Ok(false), migrations::any_pending_migrations(&connection));
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '20160107090901'")
+        .unwrap();
+
+    let pending = migrations::pending_migrations(&connection).unwrap();
+
+    assert_eq!(1, pending.len());
+    assert_eq!("20160107090901", pending[0].version);
+    assert_eq!(Some("add_tags_to_posts".into()), pending[0].name);
+    assert_eq!(None, pending[0].run_on);
+    assert_eq!(Ok(true), migrations::any_pending_migrations(&connection));
+}
+
+#[test]
+fn applied_migrations_lists_migrations_in_order_of_version() {
+    let connection = connection();
+
+    let applied = migrations::applied_migrations(&connection).unwrap();
+    let versions = applied.iter().map(|m| &*m.version).collect::<Vec<_>>();
+
+    assert_eq!(vec!["20151219180527", "20160107090901", "20160116104628"], versions);
+    assert_eq!(Some("create_users_and_posts_and_comments".into()), applied[0].name);
+    assert!(applied.iter().all(|m| m.run_on.is_some()));
+}
+
+#[test]
+fn applied_migrations_include_how_long_they_took_and_who_ran_them() {
+    let connection = connection();
+    let config = migrations::MigrationConfig {
+        run_by: Some("deploy-server-1".into()),
+        ..migrations::MigrationConfig::default()
+    };
+    migrations::revert_latest_migration_with_config(&connection, &config).unwrap();
+    migrations::run_pending_migrations_with_config(&connection, &config).unwrap();
+
+    let applied = migrations::applied_migrations(&connection).unwrap();
+    let latest = applied.last().unwrap();
+
+    assert_eq!("20160116104628", latest.version);
+    assert_eq!(Some("deploy-server-1".into()), latest.run_by);
+    assert!(latest.duration.is_some());
+}
+
+#[test]
+fn migration_status_includes_pending_and_missing_migrations() {
+    let connection = connection();
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '20160107090901'")
+        .unwrap();
+    connection.execute("INSERT INTO __diesel_schema_migrations (version) VALUES ('20990101000000')")
+        .unwrap();
+
+    let status = migrations::migration_status(&connection).unwrap();
+    let versions = status.iter().map(|m| &*m.version).collect::<Vec<_>>();
+
+    assert_eq!(vec!["20151219180527", "20160107090901", "20160116104628", "20990101000000"], versions);
+    assert!(status[0].applied.is_some() && status[0].source_present);
+    assert_eq!(Some("add_tags_to_posts".into()), status[1].name);
+    assert!(status[1].applied.is_none() && status[1].source_present);
+    assert_eq!(None, status[3].name);
+    assert!(status[3].applied.is_some() && !status[3].source_present);
+}
+
+struct InsertMigratedUser;
+
+impl migrations::Migration for InsertMigratedUser {
+    fn version(&self) -> &str {
+        "20160107090902"
+    }
+
+    fn run(&self, conn: &SimpleConnection) -> Result<(), migrations::RunMigrationsError> {
+        try!(conn.batch_execute("INSERT INTO users (name) VALUES ('Migrated')"));
+        Ok(())
+    }
+
+    fn revert(&self, conn: &SimpleConnection) -> Result<(), migrations::RunMigrationsError> {
+        try!(conn.batch_execute("DELETE FROM users WHERE name = 'Migrated'"));
+        Ok(())
+    }
+}
+
+#[test]
+fn migrations_written_in_rust_are_run_alongside_sql_migrations() {
+    let connection = connection();
+    connection.batch_execute(include_str!("../../migrations/20160107090901_add_tags_to_posts/down.sql"))
+        .unwrap();
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '20160107090901'")
+        .unwrap();
+
+    let rust_migrations = vec![Box::new(InsertMigratedUser) as Box<migrations::Migration>];
+    migrations::run_migrations(&connection, rust_migrations).unwrap();
+
+    let query = "SELECT 1 FROM __diesel_schema_migrations
+        WHERE version IN ('20160107090901', '20160107090902')";
+    assert_eq!(Ok(2), connection.execute(query));
+    assert_eq!(Ok(1), connection.execute("SELECT 1 FROM users WHERE name = 'Migrated'"));
+}
+
+#[test]
+fn migration_progress_can_be_written_to_any_writer() {
+    let connection = connection();
+
+    let mut output = Vec::new();
+    migrations::revert_latest_migration_with_output(&connection, &mut output).unwrap();
+    migrations::run_pending_migrations_with_output(&connection, &mut output).unwrap();
+
+    let expected_output = "Rolling back migration 20160116104628\nRunning migration 20160116104628\n";
+    assert_eq!(expected_output, String::from_utf8(output).unwrap());
+}
+
+#[test]
+fn running_migrations_atomically_commits_nothing_if_any_migration_fails() {
+    let connection = connection();
+    connection.batch_execute(include_str!("../../migrations/20160107090901_add_tags_to_posts/down.sql"))
+        .unwrap();
+    // 20160116104628 was not reverted, so running it again will fail
+    connection.execute("DELETE FROM __diesel_schema_migrations
+        WHERE version IN ('20160107090901', '20160116104628')").unwrap();
+
+    assert!(migrations::run_pending_migrations_atomically(&connection, &mut io::sink()).is_err());
+
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '20160107090901'";
+    assert_eq!(Ok(0), connection.execute(query));
+}
+
+#[test]
+fn migrations_can_be_tracked_in_a_custom_table() {
+    let connection = connection();
+    connection.execute("CREATE SCHEMA deploy").unwrap();
+    connection.execute("CREATE TABLE deploy.custom_migrations AS SELECT * FROM __diesel_schema_migrations
+        WHERE version <> '20160116104628'").unwrap();
+    connection.batch_execute(include_str!("../../migrations/20160116104628_create_special_posts_and_special_comments/down.sql"))
+        .unwrap();
+    let config = migrations::MigrationConfig {
+        table_name: "custom_migrations".into(),
+        schema: Some("deploy".into()),
+        ..migrations::MigrationConfig::default()
+    };
+
+    migrations::run_pending_migrations_with_config(&connection, &config).unwrap();
+
+    let query = "SELECT 1 FROM deploy.custom_migrations WHERE version = '20160116104628'";
+    assert_eq!(Ok(1), connection.execute(query));
+
+    let reverted_version = migrations::revert_latest_migration_with_config(&connection, &config)
+        .unwrap();
+
+    assert_eq!("20160116104628", reverted_version);
+    assert_eq!(Ok(0), connection.execute(query));
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '20160116104628'";
+    assert_eq!(Ok(1), connection.execute(query));
+}
+
+#[test]
+fn a_migrator_uses_its_config_for_every_operation() {
+    let connection = connection();
+    connection.execute("CREATE TABLE custom_migrations AS SELECT * FROM __diesel_schema_migrations
+        WHERE version <> '20160116104628'").unwrap();
+    let config = migrations::MigrationConfig {
+        table_name: "custom_migrations".into(),
+        ..migrations::MigrationConfig::default()
+    };
+    let migrator = migrations::Migrator::new(&connection)
+        .with_config(config)
+        .with_directory(migrations_dir())
+        .with_output(io::sink());
+
+    let pending = migrator.pending_migrations().unwrap();
+    let pending_versions = pending.iter().map(|m| &*m.version).collect::<Vec<_>>();
+    let applied = migrator.applied_migrations().unwrap();
+    let applied_versions = applied.iter().map(|m| &*m.version).collect::<Vec<_>>();
+
+    assert_eq!(vec!["20160116104628"], pending_versions);
+    assert_eq!(Some("create_special_posts_and_special_comments".into()), pending[0].name);
+    assert_eq!(vec!["20151219180527", "20160107090901"], applied_versions);
+    assert_eq!(Ok(Some("20160107090901".into())), migrator.database_schema_version());
+    assert_eq!(Ok(Some("20160116104628".into())), migrations::database_schema_version(&connection));
+}
+
+#[test]
+fn running_a_migration_records_its_checksum() {
+    let connection = connection();
+    migrations::revert_latest_migration(&connection).unwrap();
+    migrations::run_pending_migrations(&connection).unwrap();
+
+    let query = "SELECT 1 FROM __diesel_schema_migrations
+        WHERE version = '20160116104628' AND checksum IS NOT NULL";
+    assert_eq!(Ok(1), connection.execute(query));
+}
+
+#[test]
+fn migrations_which_changed_after_being_run_are_an_error() {
+    let connection = connection();
+    migrations::run_pending_migrations(&connection).unwrap();
+    connection.execute("UPDATE __diesel_schema_migrations SET checksum = 'not the checksum'
+        WHERE version = '20160107090901'").unwrap();
+
+    let expected_error = migrations::MigrationError::ChecksumMismatch("20160107090901".into());
+    assert_eq!(Err(expected_error.into()), migrations::run_pending_migrations(&connection));
+}
+
+#[derive(Default)]
+struct RecordingHooks {
+    events: Vec<String>,
+}
+
+impl migrations::MigrationHooks for RecordingHooks {
+    fn before_run(&mut self, version: &str) -> Result<(), migrations::RunMigrationsError> {
+        self.events.push(format!("before_run {}", version));
+        Ok(())
+    }
+
+    fn after_run(&mut self, version: &str, _: Duration) -> Result<(), migrations::RunMigrationsError> {
+        self.events.push(format!("after_run {}", version));
+        Ok(())
+    }
+
+    fn before_revert(&mut self, version: &str) -> Result<(), migrations::RunMigrationsError> {
+        self.events.push(format!("before_revert {}", version));
+        Ok(())
+    }
+
+    fn after_revert(&mut self, version: &str, _: Duration) -> Result<(), migrations::RunMigrationsError> {
+        self.events.push(format!("after_revert {}", version));
+        Ok(())
+    }
+}
+
+#[test]
+fn hooks_are_called_around_each_migration() {
+    let connection = connection();
+    let mut hooks = RecordingHooks::default();
+
+    migrations::revert_latest_migration_with_hooks(&connection, &mut hooks).unwrap();
+    migrations::run_pending_migrations_with_hooks(&connection, &mut hooks).unwrap();
+
+    let expected_events = vec![
+        "before_revert 20160116104628",
+        "after_revert 20160116104628",
+        "before_run 20160116104628",
+        "after_run 20160116104628",
+    ];
+    assert_eq!(expected_events, hooks.events);
+}
+
+struct CancelEverything;
+
+impl migrations::MigrationHooks for CancelEverything {
+    fn before_run(&mut self, version: &str) -> Result<(), migrations::RunMigrationsError> {
+        Err(migrations::MigrationError::UnknownMigrationVersion(version.into()).into())
+    }
+}
+
+#[test]
+fn errors_from_before_run_hooks_cancel_the_migration() {
+    let connection = connection();
+    migrations::revert_latest_migration_with_output(&connection, &mut Vec::new()).unwrap();
+
+    let result = migrations::run_pending_migrations_with_hooks(&connection, &mut CancelEverything);
+
+    assert!(result.is_err());
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '20160116104628'";
+    assert_eq!(Ok(0), connection.execute(query));
+}
+
+#[test]
+fn migrations_can_be_run_from_an_in_memory_source() {
+    let connection = connection();
+    let source = vec![migrations::EmbeddedMigration {
+        version: "00000000000001",
+        up_sql: "SELECT 1",
+        down_sql: "SELECT 1",
+        run_in_transaction: true,
+    }];
+
+    migrations::run_pending_migrations_from_source(&connection, &source, &mut Vec::new()).unwrap();
+
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '00000000000001'";
+    assert_eq!(Ok(1), connection.execute(query));
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '00000000000001'")
+        .unwrap();
+}
+
+#[test]
+fn failed_migrations_report_the_statement_which_failed() {
+    let connection = connection();
+    let source = vec![migrations::EmbeddedMigration {
+        version: "00000000000002",
+        up_sql: "SELECT 1;\n\n-- This table doesn't exist\nSELECT * FROM\n  no_such_table;\nSELECT 2;",
+        down_sql: "SELECT 1",
+        run_in_transaction: true,
+    }];
+
+    let result = migrations::run_pending_migrations_from_source(&connection, &source, &mut Vec::new());
+
+    match result {
+        Err(migrations::RunMigrationsError::MigrationFailed(failure)) => {
+            assert_eq!("00000000000002", failure.version);
+            assert_eq!(None, failure.path);
+            assert_eq!(Some(4), failure.line);
+            assert_eq!(Some("SELECT * FROM\n  no_such_table".to_string()), failure.statement);
+            assert!(!failure.partially_applied);
+            match *failure.error {
+                migrations::RunMigrationsError::QueryError(_) => {}
+                ref e => panic!("Expected a query error, got {:?}", e),
+            }
+        }
+        _ => panic!("Expected the migration to fail, got {:?}", result),
+    }
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '00000000000002'";
+    assert_eq!(Ok(0), connection.execute(query));
+}
+
+#[test]
+fn failed_migrations_outside_of_a_transaction_are_partially_applied() {
+    let connection = connection();
+    let source = vec![migrations::EmbeddedMigration {
+        version: "00000000000003",
+        up_sql: "CREATE TABLE partially_applied (id INTEGER); SELECT * FROM no_such_table;",
+        down_sql: "DROP TABLE partially_applied",
+        run_in_transaction: false,
+    }];
+
+    let result = migrations::run_pending_migrations_from_source(&connection, &source, &mut Vec::new());
+
+    match result {
+        Err(migrations::RunMigrationsError::MigrationFailed(ref failure)) => {
+            assert!(failure.partially_applied);
+        }
+        _ => panic!("Expected the migration to fail, got {:?}", result),
+    }
+    assert!(result.unwrap_err().to_string().ends_with("so it may have been partially applied."));
+}
+
+#[test]
+fn marking_a_migration_as_applied_records_it_without_running_it() {
+    let connection = connection();
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '20160116104628'")
+        .unwrap();
+
+    migrations::mark_migration_as_applied(&connection, "20160116104628").unwrap();
+    migrations::mark_migration_as_applied(&connection, "20160116104628").unwrap();
+
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '20160116104628'";
+    assert_eq!(Ok(1), connection.execute(query));
+    assert!(!migrations::any_pending_migrations(&connection).unwrap());
+}
+
+#[test]
+fn marking_an_unknown_migration_as_applied_is_an_error() {
+    let connection = connection();
+
+    let result = migrations::mark_migration_as_applied(&connection, "99999999999999");
+
+    let expected_error = migrations::MigrationError::UnknownMigrationVersion("99999999999999".into());
+    match result {
+        Err(migrations::RunMigrationsError::MigrationError(e)) => assert_eq!(expected_error, e),
+        _ => panic!("Expected an unknown migration version error, got {:?}", result),
+    }
+}
+
+#[test]
+fn migrations_must_match_the_configured_version_format() {
+    let connection = connection();
+    migrations::revert_latest_migration_with_output(&connection, &mut Vec::new()).unwrap();
+    let config = migrations::MigrationConfig {
+        version_format: migrations::VersionFormat::Sequential,
+        ..migrations::MigrationConfig::default()
+    };
+
+    let result = migrations::run_pending_migrations_with_config(&connection, &config);
+
+    let expected_error = migrations::MigrationError::InvalidMigrationVersion("20151219180527".into());
+    match result {
+        Err(migrations::RunMigrationsError::MigrationError(e)) => assert_eq!(expected_error, e),
+        _ => panic!("Expected an invalid migration version error, got {:?}", result),
+    }
+    assert_eq!(Ok(true), migrations::any_pending_migrations(&connection));
+}
+
+#[test]
+fn out_of_order_migrations_can_be_forbidden() {
+    let connection = connection();
+    migrations::run_pending_migrations_with_output(&connection, &mut Vec::new()).unwrap();
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '20160107090901'")
+        .unwrap();
+    let config = migrations::MigrationConfig {
+        allow_out_of_order: false,
+        ..migrations::MigrationConfig::default()
+    };
+
+    let result = migrations::run_pending_migrations_with_config(&connection, &config);
+
+    let expected_error = migrations::MigrationError::OutOfOrderMigrations(vec!["20160107090901".into()]);
+    match result {
+        Err(migrations::RunMigrationsError::MigrationError(e)) => assert_eq!(expected_error, e),
+        _ => panic!("Expected an out of order migrations error, got {:?}", result),
+    }
+    assert_eq!(Ok(true), migrations::any_pending_migrations(&connection));
+}
+
+#[test]
+fn seeds_are_tracked_separately_from_migrations() {
+    let connection = connection();
+
+    migrations::run_pending_seeds(&connection).unwrap();
+    migrations::run_pending_seeds(&connection).unwrap();
+
+    assert_eq!(Ok(1), connection.execute("SELECT 1 FROM users WHERE name = 'Seed User'"));
+    let query = "SELECT 1 FROM __diesel_seeds WHERE version = '20160201000000'";
+    assert_eq!(Ok(1), connection.execute(query));
+    let query = "SELECT 1 FROM __diesel_schema_migrations WHERE version = '20160201000000'";
+    assert_eq!(Ok(0), connection.execute(query));
+}
+
+#[test]
+fn running_migrations_takes_an_advisory_lock() {
+    let connection = schema::connection();
+    let query = "SELECT 1 FROM pg_locks WHERE locktype = 'advisory' AND pid = pg_backend_pid()";
+    assert_eq!(Ok(0), connection.execute(query));
+
+    migrations::run_pending_migrations(&connection).unwrap();
+
+    // Inside of a transaction, the lock is held until the transaction ends
+    assert_eq!(Ok(1), connection.execute(query));
+}
+
+#[test]
+fn database_schema_version_is_the_latest_migration_which_has_run() {
+    let connection = connection();
+    migrations::run_pending_migrations_with_output(&connection, &mut Vec::new()).unwrap();
+    let latest_version = migrations::applied_migrations(&connection).unwrap()
+        .pop().map(|migration| migration.version);
+
+    assert!(latest_version.is_some());
+    assert_eq!(Ok(latest_version), migrations::database_schema_version(&connection));
+}
+
+#[test]
+fn migrations_can_be_run_once_for_each_schema() {
+    let connection = connection();
+    connection.batch_execute("CREATE SCHEMA tenant_a; CREATE SCHEMA tenant_b;").unwrap();
+
+    let mut output = Vec::new();
+    migrations::run_pending_migrations_for_schemas(&connection, &["tenant_a", "tenant_b"], &mut output)
+        .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Migrating schema tenant_a\n"));
+    assert!(output.contains("Migrating schema tenant_b\n"));
+    for schema in &["tenant_a", "tenant_b"] {
+        let query = format!("SELECT 1 FROM {}.__diesel_schema_migrations \
+                             WHERE version = '20151219180527'", schema);
+        assert_eq!(Ok(1), connection.execute(&query));
+        let query = format!("SELECT 1 FROM information_schema.tables \
+                             WHERE table_schema = '{}' AND table_name = 'users'", schema);
+        assert_eq!(Ok(1), connection.execute(&query));
+    }
+    // The search path is restored, so the tests' own tables can still be found
+    assert!(connection.execute("SELECT 1 FROM users").is_ok());
+}
+
+#[test]
+fn schema_drift_reports_changes_made_outside_of_migrations() {
+    let connection = connection();
+    migrations::run_pending_migrations_with_output(&connection, &mut Vec::new()).unwrap();
+    connection.execute("ALTER TABLE users ADD COLUMN drifted INTEGER").unwrap();
+
+    let drift = migrations::schema_drift(&connection).unwrap();
+
+    let unexpected_column = migrations::SchemaDrift::UnexpectedColumn {
+        table: "users".into(),
+        column: "drifted".into(),
+    };
+    assert!(drift.contains(&unexpected_column), "{:?} did not contain {:?}", drift, unexpected_column);
+    assert!(!drift.contains(&migrations::SchemaDrift::MissingTable("users".into())));
+    let query = "SELECT 1 FROM information_schema.schemata WHERE schema_name = '__diesel_schema_drift'";
+    assert_eq!(Ok(0), connection.execute(query));
+}
+
+#[test]
+fn the_schema_can_be_dumped_after_migrations_are_run() {
+    let connection = connection();
+    connection.execute("DELETE FROM __diesel_schema_migrations WHERE version = '20160107090901'")
+        .unwrap();
+    let mut dumped_after_running = false;
+
+    migrations::run_pending_migrations_and_then(&connection, &mut Vec::new(), |conn| {
+        dumped_after_running = !migrations::any_pending_migrations(conn).unwrap();
+        Ok(())
+    }).unwrap();
+
+    assert!(dumped_after_running);
+    let mut schema = Vec::new();
+    migrations::dump_schema(&connection, &mut schema).unwrap();
+    let schema = String::from_utf8(schema).unwrap();
+    assert!(schema.contains("\nCREATE TABLE users (\n    id integer NOT NULL DEFAULT nextval('users_id_seq'::regclass),\n"));
+    assert!(schema.contains("\nALTER TABLE users ADD CONSTRAINT users_pkey PRIMARY KEY (id);\n"));
+    assert!(!schema.contains("__diesel_schema_migrations"));
+}
@@ -0,0 +1,74 @@
+use schema::*;
+use diesel::*;
+
+#[derive(DieselNewType, Debug, Clone, Copy, PartialEq)]
+struct UserId(i32);
+
+#[derive(DieselNewType, Debug, Clone, PartialEq)]
+struct UserName(String);
+
+#[derive(DieselNewType, Debug, Clone, PartialEq)]
+struct HairColor(String);
+
+#[derive(Queryable, Debug, PartialEq)]
+struct TypedUser {
+    id: UserId,
+    name: UserName,
+}
+
+#[test]
+fn new_types_can_be_loaded() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    let expected_ids = vec![UserId(1), UserId(2)];
+    let actual_ids = users.select(id).order(id).load(&connection).unwrap().collect::<Vec<UserId>>();
+    assert_eq!(expected_ids, actual_ids);
+}
+
+#[test]
+fn new_types_can_be_used_as_fields_of_queryable_structs() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    let expected_user = TypedUser {
+        id: UserId(1),
+        name: UserName("Sean".to_string()),
+    };
+    assert_eq!(Ok(expected_user), users.select((id, name)).first(&connection));
+}
+
+#[test]
+fn new_types_can_be_used_as_bind_parameters() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    let tess_id = UserId(2);
+    let by_value = users.select(name).filter(id.eq(tess_id)).first(&connection);
+    let by_ref = users.select(name).filter(id.eq(&tess_id)).first(&connection);
+    assert_eq!(Ok(UserName("Tess".to_string())), by_value);
+    assert_eq!(Ok(UserName("Tess".to_string())), by_ref);
+}
+
+#[test]
+fn optional_new_types_can_be_loaded() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+    connection.execute("UPDATE users SET hair_color = 'black' WHERE id = 1").unwrap();
+
+    let expected_colors = vec![Some(HairColor("black".to_string())), None];
+    let actual_colors = users.select(hair_color).order(id).load(&connection).unwrap()
+        .collect::<Vec<Option<HairColor>>>();
+    assert_eq!(expected_colors, actual_colors);
+}
+
+#[test]
+fn optional_new_types_can_be_used_as_bind_parameters() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+    connection.execute("UPDATE users SET hair_color = 'black' WHERE id = 1").unwrap();
+
+    let black = Some(HairColor("black".to_string()));
+    let query = users.select(id).filter(hair_color.eq(black));
+    assert_eq!(Ok(UserId(1)), query.first(&connection));
+}
@@ -53,6 +53,20 @@ fn with_safe_select() {
     assert_eq!(vec!["Sean".to_string(), "Tess".to_string()], names);
 }
 
+#[test]
+fn selecting_with_offset_and_no_limit() {
+    use schema::users::dsl::*;
+
+    let connection = connection();
+    connection.execute("INSERT INTO users (name) VALUES ('Sean'), ('Tess'), ('Jim')")
+        .unwrap();
+
+    let names: Vec<String> = users.select(name).order(id).offset(1)
+        .load(&connection).unwrap().collect();
+
+    assert_eq!(vec!["Tess".to_string(), "Jim".to_string()], names);
+}
+
 #[test]
 fn with_select_sql() {
     let connection = connection();
@@ -165,3 +179,27 @@ fn selection_using_subselect() {
 
     assert_eq!(vec!["Hello".to_string()], data);
 }
+
+#[test]
+fn selecting_literal_values_with_into_sql() {
+    use schema::users::dsl::*;
+    use diesel::types::{Integer, Nullable, VarChar};
+
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    let expected_data = vec![
+        ("Sean".to_string(), 42, None::<String>),
+        ("Tess".to_string(), 42, None::<String>),
+    ];
+    let actual_data: Vec<_> = users
+        .select((name, 42.into_sql::<Integer>(), None::<&str>.into_sql::<Nullable<VarChar>>()))
+        .order(id)
+        .load(&connection)
+        .unwrap().collect();
+    assert_eq!(expected_data, actual_data);
+
+    let tagged_users = users.select((name, "user".into_sql::<VarChar>()))
+        .filter(id.eq(1))
+        .first(&connection);
+    assert_eq!(Ok(("Sean".to_string(), "user".to_string())), tagged_users);
+}
@@ -0,0 +1,99 @@
+use schema::connection;
+use diesel::*;
+use diesel::expression::dsl::sql;
+use diesel::types::{Array, Text, VarChar};
+
+#[derive(DieselTextEnum, Debug, Clone, Copy, PartialEq)]
+enum PostStatus {
+    Draft,
+    PendingReview,
+    #[sql_value="live"]
+    Published,
+}
+
+#[test]
+fn text_enums_are_loaded_from_snake_cased_variant_names() {
+    let connection = connection();
+
+    assert_eq!(Ok(PostStatus::Draft), select(sql::<Text>("'draft'")).first(&connection));
+    assert_eq!(Ok(PostStatus::PendingReview), select(sql::<VarChar>("'pending_review'")).first(&connection));
+}
+
+#[test]
+fn text_enum_variants_can_be_renamed() {
+    let connection = connection();
+
+    assert_eq!(Ok(PostStatus::Published), select(sql::<Text>("'live'")).first(&connection));
+    assert!(select(sql::<Text>("'published'")).first::<PostStatus>(&connection).is_err());
+}
+
+#[test]
+fn text_enums_are_sent_as_their_text_value() {
+    let connection = connection();
+
+    let query = select(sql::<Text>("'live'").eq(PostStatus::Published));
+    assert_eq!(Ok(true), query.first(&connection));
+    let query = select(sql::<Text>("'pending_review'").eq(&PostStatus::PendingReview));
+    assert_eq!(Ok(true), query.first(&connection));
+}
+
+#[test]
+fn unrecognized_values_list_the_expected_values() {
+    use diesel::backend::Pg;
+    use diesel::types::FromSql;
+
+    let error = <PostStatus as FromSql<Text, Pg>>::from_sql(Some(&b"archived"[..])).unwrap_err();
+    assert_eq!("Unrecognized value for PostStatus. Expected one of `draft`, \
+               `pending_review`, `live`. Got `archived`", error.to_string());
+}
+
+struct PgPostStatus;
+
+impl ::diesel::types::HasSqlType<PgPostStatus> for ::diesel::backend::Pg {
+    fn metadata() -> ::diesel::backend::PgTypeMetadata {
+        ::diesel::backend::PgTypeMetadata { oid: 0, array_oid: 0 }
+    }
+}
+
+impl ::diesel::types::FromSql<PgPostStatus, ::diesel::backend::Pg> for String {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<::std::error::Error>> {
+        ::diesel::types::FromSql::<Text, ::diesel::backend::Pg>::from_sql(bytes)
+    }
+}
+
+#[test]
+fn arrays_of_database_enums_can_be_loaded() {
+    let connection = connection();
+    connection.execute("CREATE TYPE post_status AS ENUM ('draft', 'pending_review', 'live')").unwrap();
+
+    let query = select(sql::<Array<PgPostStatus>>("ARRAY['live', 'draft']::post_status[]"));
+    let expected = vec![PostStatus::Published, PostStatus::Draft];
+    assert_eq!(Ok(expected), query.first(&connection));
+}
+
+pg_enum_type!(PostStatusType, "post_status");
+pg_enum_type!(RegisteredPostStatusType, "post_status");
+
+#[test]
+fn database_enums_can_be_declared_with_a_macro() {
+    let connection = connection();
+    connection.execute("CREATE TYPE post_status AS ENUM ('draft', 'pending_review', 'live')").unwrap();
+
+    let query = select(sql::<PostStatusType>("'live'::post_status"));
+    assert_eq!(Ok(PostStatus::Published), query.first(&connection));
+    let query = select(sql::<PostStatusType>("'pending_review'::post_status").eq(PostStatus::PendingReview));
+    assert_eq!(Ok(true), query.first(&connection));
+    let query = select(sql::<PostStatusType>("'draft'::post_status"));
+    assert_eq!(Ok("draft".to_string()), query.first::<String>(&connection));
+}
+
+#[test]
+fn arrays_of_database_enums_can_be_sent_once_registered() {
+    let connection = connection();
+    connection.execute("CREATE TYPE post_status AS ENUM ('draft', 'pending_review', 'live')").unwrap();
+    RegisteredPostStatusType::register(&connection).unwrap();
+
+    let query = select(sql::<Array<RegisteredPostStatusType>>("ARRAY['live', 'draft']::post_status[]")
+        .eq(vec![PostStatus::Published, PostStatus::Draft]));
+    assert_eq!(Ok(true), query.first(&connection));
+}
@@ -25,6 +25,45 @@ fn boolean_to_sql() {
     assert!(!query_to_sql_equality::<Bool, bool>("'f'::bool", true));
 }
 
+#[test]
+fn i8_from_sql() {
+    assert_eq!(65, query_single_value::<TinyInt, i8>("'A'::\"char\""));
+    assert_eq!(0, query_single_value::<TinyInt, i8>("''::\"char\""));
+}
+
+#[test]
+fn i8_to_sql() {
+    assert!(query_to_sql_equality::<TinyInt, i8>("'A'::\"char\"", 65));
+    assert!(!query_to_sql_equality::<TinyInt, i8>("'A'::\"char\"", 66));
+}
+
+#[test]
+fn char_from_sql() {
+    assert_eq!('Y', query_single_value::<Char, char>("'Y'::char(1)"));
+    assert_eq!('Y', query_single_value::<TinyInt, char>("'Y'::\"char\""));
+    assert_eq!("ab ".to_string(), query_single_value::<Char, String>("'ab'::char(3)"));
+}
+
+#[test]
+fn char_to_sql() {
+    assert!(query_to_sql_equality::<Char, char>("'Y'::char(1)", 'Y'));
+    assert!(!query_to_sql_equality::<Char, char>("'Y'::char(1)", 'N'));
+    assert!(query_to_sql_equality::<TinyInt, char>("'Y'::\"char\"", 'Y'));
+}
+
+#[test]
+fn xml_from_sql() {
+    let xml = "<user><name>Sean</name></user>";
+    assert_eq!(xml.to_string(), query_single_value::<Xml, String>(&format!("'{}'::xml", xml)));
+}
+
+#[test]
+fn xml_to_sql() {
+    let connection = connection();
+    let query = select("<name>Sean</name>".into_sql::<Xml>().cast::<Text>());
+    assert_eq!(Ok("<name>Sean</name>".to_string()), query.get_result(&connection));
+}
+
 #[test]
 fn i16_from_sql() {
     assert_eq!(0, query_single_value::<SmallInt, i16>("0::int2"));
@@ -129,6 +168,19 @@ fn f64_to_sql_float() {
     assert!(!query_to_sql_equality::<Double, f64>("'-Infinity'::double precision", 1.0));
 }
 
+#[test]
+fn negative_zero_round_trips_for_floats() {
+    let zero = query_single_value::<Float, f32>("'-0'::real");
+    assert!(zero == 0.0 && zero.is_sign_negative());
+    let zero = query_single_value::<Double, f64>("'-0'::double precision");
+    assert!(zero == 0.0 && zero.is_sign_negative());
+
+    let connection = connection();
+    let query = select(AsExpression::<Double>::as_expression(-0.0f64));
+    let zero = query.get_result::<f64>(&connection).unwrap();
+    assert!(zero.is_sign_negative());
+}
+
 #[test]
 fn string_from_sql() {
     assert_eq!("hello", &query_single_value::<VarChar, String>("'hello'"));
@@ -232,6 +284,28 @@ fn to_sql_array() {
             "ARRAY['Hello', '', 'world']::varchar[]", &["Hello", "", "world"]));
 }
 
+#[test]
+fn pg_multi_dimensional_array() {
+    let query = "ARRAY[[1, 2, 3], [4, 5, 6]]";
+    let expected = vec![vec![1, 2, 3], vec![4, 5, 6]];
+    assert_eq!(expected, query_single_value::<Array<Array<Integer>>, Vec<Vec<i32>>>(query));
+    assert!(query_to_sql_equality::<Array<Array<Integer>>, Vec<Vec<i32>>>(query, expected));
+}
+
+#[test]
+fn pg_multi_dimensional_array_with_ragged_rows_is_an_error() {
+    use diesel::expression::dsl::sql;
+    use diesel::result::Error;
+
+    let connection = connection();
+    let ragged = vec![vec![1, 2, 3], vec![4, 5]];
+    let query = select(sql::<Array<Array<Integer>>>("ARRAY[[1, 2, 3], [4, 5, 6]]")
+        .is_not_distinct_from(ragged));
+    let expected = Err(Error::QueryBuilderError(
+        "Every row of a multi-dimensional array must have the same dimensions".into()));
+    assert_eq!(expected, query.get_result::<bool>(&connection));
+}
+
 #[test]
 fn pg_array_containing_null() {
     let query = "ARRAY['Hello', '', NULL, 'world']";
@@ -245,6 +319,24 @@ fn pg_array_containing_null() {
     assert_eq!(expected, data);
 }
 
+#[test]
+fn pg_integer_array_containing_null() {
+    let query = "ARRAY[1, NULL, 3]";
+    let data = query_single_value::<Array<Nullable<Integer>>, Vec<Option<i32>>>(query);
+    assert_eq!(vec![Some(1), None, Some(3)], data);
+    assert!(query_to_sql_equality::<Array<Nullable<Integer>>, Vec<Option<i32>>>(
+            query, vec![Some(1), None, Some(3)]));
+}
+
+#[test]
+fn to_sql_array_containing_null() {
+    let data = vec![Some("Hello"), Some(""), None, Some("world")];
+    assert!(query_to_sql_equality::<Array<Nullable<VarChar>>, Vec<Option<&str>>>(
+            "ARRAY['Hello', '', NULL, 'world']::varchar[]", data.clone()));
+    assert!(!query_to_sql_equality::<Array<Nullable<VarChar>>, Vec<Option<&str>>>(
+            "ARRAY['Hello', '', '', 'world']::varchar[]", data));
+}
+
 #[test]
 fn timestamp_from_sql() {
     use diesel::data_types::PgTimestamp;
@@ -271,6 +363,28 @@ fn pg_timestamp_to_sql_timestamp() {
     assert!(!query_to_sql_equality::<Timestamp, PgTimestamp>(expected_non_equal_value, value));
 }
 
+#[test]
+fn timestamptz_from_sql_is_in_utc() {
+    use diesel::data_types::PgTimestamp;
+
+    let query = "'2015-11-13 13:26:48.041057+00'::timestamptz";
+    let expected_value = PgTimestamp(500736408041057);
+    assert_eq!(expected_value, query_single_value::<Timestamptz, PgTimestamp>(query));
+    let query = "'2015-11-13 06:26:48.041057-07'::timestamptz";
+    assert_eq!(expected_value, query_single_value::<Timestamptz, PgTimestamp>(query));
+}
+
+#[test]
+fn pg_timestamp_to_sql_timestamptz() {
+    use diesel::data_types::PgTimestamp;
+
+    let expected_value = "'2015-11-13 13:26:48.041057+00'::timestamptz";
+    let value = PgTimestamp(500736408041057);
+    assert!(query_to_sql_equality::<Timestamptz, PgTimestamp>(expected_value, value));
+    let expected_non_equal_value = "'2015-11-13 13:26:48.041057-07'::timestamptz";
+    assert!(!query_to_sql_equality::<Timestamptz, PgTimestamp>(expected_non_equal_value, value));
+}
+
 #[test]
 fn pg_numeric_from_sql() {
     use diesel::data_types::PgNumeric;
@@ -0,0 +1 @@
+INSERT INTO users (name, hair_color) VALUES ('Seed User', 'Green');